@@ -5,7 +5,9 @@
 
 use std::sync::Arc;
 
-use imbue_kusama_runtime::{opaque::Block, AccountId, Balance, Index as Nonce};
+mod briefs;
+
+use imbue_kusama_runtime::{opaque::Block, AccountId, Balance, Index as Nonce, Moment, Runtime};
 
 use sc_client_api::AuxStore;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -42,8 +44,16 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: BlockBuilder<Block>,
+    C::Api: pallet_briefs::runtime_api::BriefsApi<
+        Block,
+        AccountId,
+        Balance,
+        Moment,
+        pallet_briefs::BriefData<Runtime>,
+    >,
     P: TransactionPool + Sync + Send + 'static,
 {
+    use briefs::{Briefs, BriefsApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -55,6 +65,7 @@ where
     } = deps;
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(Briefs::new(client).into_rpc())?;
     Ok(module)
 }
@@ -0,0 +1,190 @@
+//! RPC interface for the briefs pallet.
+
+use std::sync::Arc;
+
+use imbue_kusama_runtime::{
+    opaque::Block, AccountId, Balance, BlockNumber, CurrencyId, Moment, Runtime,
+};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use pallet_briefs::{runtime_api::BriefsApi as BriefsRuntimeApi, BriefData, BriefHash};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code returned when a runtime api call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// The details of a brief.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BriefDetails {
+    /// The accounts which can contribute to the brief.
+    pub brief_owners: Vec<AccountId>,
+    /// The total bounty of the brief.
+    pub budget: NumberOrHex,
+    /// The currency the bounty is paid in.
+    pub currency_id: CurrencyId,
+    /// The block the brief was created at.
+    pub created_at: BlockNumber,
+    /// The freelancer who will carry out the work.
+    pub applicant: AccountId,
+    /// The milestones the bounty is paid out over.
+    pub milestones: Vec<MilestoneDetails>,
+}
+
+/// A milestone proposed for a brief.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneDetails {
+    /// The percentage of the bounty unlocked by the milestone.
+    pub percentage_to_unlock: u32,
+    /// The hash of the milestone's title.
+    pub title_hash: H256,
+    /// The hash of the content describing the milestone.
+    pub content_hash: H256,
+    /// The block the milestone is expected to be delivered by, if any.
+    pub due_block: Option<BlockNumber>,
+}
+
+/// A contribution made to a brief.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionDetails {
+    /// The contributor.
+    pub account_id: AccountId,
+    /// The amount contributed.
+    pub value: NumberOrHex,
+    /// The time of the last contribution.
+    pub timestamp: Moment,
+}
+
+impl From<BriefData<Runtime>> for BriefDetails {
+    fn from(brief: BriefData<Runtime>) -> Self {
+        Self {
+            brief_owners: brief.brief_owners.into_inner(),
+            budget: brief.budget.into(),
+            currency_id: brief.currency_id,
+            created_at: brief.created_at,
+            applicant: brief.applicant,
+            milestones: brief
+                .milestones
+                .into_iter()
+                .map(|milestone| MilestoneDetails {
+                    percentage_to_unlock: milestone.percentage_to_unlock,
+                    title_hash: milestone.title_hash,
+                    content_hash: milestone.content_hash,
+                    due_block: milestone.due_block,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Briefs RPC methods.
+#[rpc(server)]
+pub trait BriefsApi<BlockHash> {
+    /// The amount of a brief's budget which has not yet been contributed.
+    #[method(name = "imbue_briefs_getRemainingBounty")]
+    fn get_remaining_bounty(
+        &self,
+        brief_id: BriefHash,
+        at: Option<BlockHash>,
+    ) -> RpcResult<NumberOrHex>;
+
+    /// The details of a brief, if it exists.
+    #[method(name = "imbue_briefs_getBrief")]
+    fn get_brief(
+        &self,
+        brief_id: BriefHash,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<BriefDetails>>;
+
+    /// The contributions made to a brief.
+    #[method(name = "imbue_briefs_getBriefContributions")]
+    fn get_brief_contributions(
+        &self,
+        brief_id: BriefHash,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ContributionDetails>>;
+}
+
+/// Provides the briefs RPC methods.
+pub struct Briefs<C> {
+    client: Arc<C>,
+}
+
+impl<C> Briefs<C> {
+    /// Create a new `Briefs` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+fn runtime_error(error: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query the briefs runtime api.",
+        Some(format!("{:?}", error)),
+    ))
+    .into()
+}
+
+impl<C> BriefsApiServer<<Block as BlockT>::Hash> for Briefs<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: BriefsRuntimeApi<Block, AccountId, Balance, Moment, BriefData<Runtime>>,
+{
+    fn get_remaining_bounty(
+        &self,
+        brief_id: BriefHash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<NumberOrHex> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .get_remaining_bounty(at, brief_id)
+            .map(Into::into)
+            .map_err(runtime_error)
+    }
+
+    fn get_brief(
+        &self,
+        brief_id: BriefHash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BriefDetails>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .get_brief(at, brief_id)
+            .map(|brief| brief.map(Into::into))
+            .map_err(runtime_error)
+    }
+
+    fn get_brief_contributions(
+        &self,
+        brief_id: BriefHash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ContributionDetails>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let contributions = self
+            .client
+            .runtime_api()
+            .get_brief_contributions(at, brief_id)
+            .map_err(runtime_error)?;
+        Ok(contributions
+            .into_iter()
+            .map(|(account_id, contribution)| ContributionDetails {
+                account_id,
+                value: contribution.value.into(),
+                timestamp: contribution.timestamp,
+            })
+            .collect())
+    }
+}
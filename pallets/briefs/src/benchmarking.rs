@@ -140,8 +140,9 @@ fn get_milestones<T: Config>(mut n: u32) -> BoundedProposedMilestones<T> {
     let milestones = (0..n)
         .map(|_| ProposedMilestone {
             percentage_to_unlock: 100 / n,
+            ..Default::default()
         })
-        .collect::<Vec<ProposedMilestone>>()
+        .collect::<Vec<ProposedMilestone<T::BlockNumber>>>()
         .try_into()
         .expect("qed");
 
@@ -6,7 +6,7 @@ use crate::tests::{get_brief_owners, get_milestones};
 use common_types::CurrencyId;
 use frame_support::{assert_ok, bounded_vec};
 use orml_traits::MultiCurrency;
-use pallet_proposals::{Projects, RoundType};
+use pallet_proposals::{ProjectContributions, Projects, RoundType};
 use std::convert::TryInto;
 
 // all the integration tests for a brief to proposal conversion
@@ -70,6 +70,7 @@ fn assert_state_from_brief_conversion_is_same_as_proposals_flow() {
             milestones.clone(),
             contribution_value,
             CurrencyId::Native,
+            None,
         )
         .unwrap();
 
@@ -110,11 +111,12 @@ fn assert_state_from_brief_conversion_is_same_as_proposals_flow() {
             standard_p.milestones.values().len()
         );
 
-        let contributions_standard = standard_p.contributions.values().collect::<Vec<_>>();
-        assert!(brief_p
-            .contributions
-            .values()
-            .all(|v| contributions_standard.contains(&v)));
+        let contributions_standard =
+            ProjectContributions::<Test>::iter_prefix_values(project_key + 1).collect::<Vec<_>>();
+        assert!(
+            ProjectContributions::<Test>::iter_prefix_values(project_key)
+                .all(|v| contributions_standard.contains(&v))
+        );
         assert_eq!(brief_p.currency_id, standard_p.currency_id);
         assert_eq!(brief_p.required_funds, standard_p.required_funds);
         assert_eq!(brief_p.withdrawn_funds, standard_p.withdrawn_funds);
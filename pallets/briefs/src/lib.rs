@@ -5,6 +5,8 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::*;
 
+pub mod runtime_api;
+
 #[cfg(test)]
 mod mock;
 
@@ -42,7 +44,7 @@ pub mod pallet {
         <T as Config>::MaxBriefOwners,
     >;
     pub(crate) type BoundedProposedMilestones<T> =
-        BoundedVec<ProposedMilestone, <T as Config>::MaxMilestonesPerBrief>;
+        BoundedVec<ProposedMilestone<BlockNumberFor<T>>, <T as Config>::MaxMilestonesPerBrief>;
 
     pub(crate) type BoundedBriefOwners<T> =
         BoundedVec<AccountIdOf<T>, <T as Config>::MaxBriefOwners>;
@@ -200,7 +202,7 @@ pub mod pallet {
             // Validation
             let total_percentage = milestones
                 .iter()
-                .fold(0u32, |acc: u32, ms: &ProposedMilestone| {
+                .fold(0u32, |acc: u32, ms: &ProposedMilestone<BlockNumberFor<T>>| {
                     acc.saturating_add(ms.percentage_to_unlock)
                 });
 
@@ -239,7 +241,7 @@ pub mod pallet {
             /// use sp_runtime::traits::{Zero};
             /// >
             if initial_contribution > 0u32.into() {
-                 ::<T>::try_mutate(brief_id, |contributions| {
+                BriefContributions::<T>::try_mutate(brief_id, |contributions| {
                     // This should never fail as the the bound is ensured when a brief is created.
                     let _ = contributions
                         .try_insert(
@@ -273,8 +275,6 @@ pub mod pallet {
 
         /// Add a bounty to a brief.
         /// A bounty must be fully contributed to before a piece of work is started.
-        ///
-        /// TODO: runtime api to return how much bounty exactly is left on a brief.
         #[pallet::call_index(3)]
         #[pallet::weight(<T as Config>::WeightInfo::contribute_to_brief())]
         pub fn contribute_to_brief(
@@ -353,12 +353,12 @@ pub mod pallet {
     #[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, MaxEncodedLen, TypeInfo)]
     #[scale_info(skip_type_params(T))]
     pub struct BriefData<T: Config> {
-        brief_owners: BoundedBriefOwners<T>,
-        budget: BalanceOf<T>,
-        currency_id: CurrencyId,
-        created_at: BlockNumberFor<T>,
-        applicant: AccountIdOf<T>,
-        milestones: BoundedProposedMilestones<T>,
+        pub brief_owners: BoundedBriefOwners<T>,
+        pub budget: BalanceOf<T>,
+        pub currency_id: CurrencyId,
+        pub created_at: BlockNumberFor<T>,
+        pub applicant: AccountIdOf<T>,
+        pub milestones: BoundedProposedMilestones<T>,
     }
 
     impl<T: Config> Pallet<T> {
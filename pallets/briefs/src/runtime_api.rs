@@ -1,14 +1,23 @@
-use crate::pallet::*;
+use crate::BriefHash;
+use codec::Codec;
+use pallet_proposals::Contribution;
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-    pub trait GetRemainingBounty<Balance> {
-        fn get_remaining_bounty() -> Balance;
+    pub trait BriefsApi<AccountId, Balance, Timestamp, BriefData>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        Timestamp: Codec,
+        BriefData: Codec,
+    {
+        /// The amount of a brief's budget which has not yet been contributed.
+        fn get_remaining_bounty(brief_id: BriefHash) -> Balance;
+
+        /// The details of a brief.
+        fn get_brief(brief_id: BriefHash) -> Option<BriefData>;
+
+        /// The contributions made to a brief.
+        fn get_brief_contributions(brief_id: BriefHash) -> Vec<(AccountId, Contribution<Balance, Timestamp>)>;
     }
 }
-
-// TODO IMPL into RUNTIME
-//impl sum_storage_rpc_runtime_api::GetRemainingBounty<Balance> for Runtime {
-//    fn get_remaining_bounty(brief_id: BriefId) -> Balance {
-//        BriefsMod::get_remaining_bounty(brief_id)
-//    }
-//}
\ No newline at end of file
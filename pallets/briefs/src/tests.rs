@@ -292,6 +292,39 @@ fn reserved_funds_are_transferred_to_project_kitty() {
     });
 }
 
+#[test]
+fn remaining_bounty_is_budget_less_contributions() {
+    build_test_externality().execute_with(|| {
+        let brief_id = gen_hash(1);
+        assert_eq!(BriefsMod::get_remaining_bounty(brief_id), 0);
+
+        assert_ok!(BriefsMod::create_brief(
+            RuntimeOrigin::signed(*BOB),
+            get_brief_owners(1),
+            *ALICE,
+            10000,
+            2000,
+            brief_id,
+            CurrencyId::Native,
+            get_milestones(10),
+        ));
+        assert_eq!(BriefsMod::get_remaining_bounty(brief_id), 8000);
+
+        assert_ok!(BriefsMod::contribute_to_brief(
+            RuntimeOrigin::signed(*BOB),
+            brief_id,
+            3000,
+        ));
+        assert_eq!(BriefsMod::get_remaining_bounty(brief_id), 5000);
+        assert_eq!(
+            BriefsMod::brief_contributions(brief_id)
+                .get(&*BOB)
+                .map(|contribution| contribution.value),
+            Some(5000)
+        );
+    });
+}
+
 pub(crate) fn run_to_block(n: u64) {
     while System::block_number() < n {
         System::set_block_number(System::block_number() + 1);
@@ -321,8 +354,9 @@ pub(crate) fn get_milestones(mut n: u32) -> BoundedProposedMilestones<Test> {
     let milestones = (0..n)
         .map(|_| ProposedMilestone {
             percentage_to_unlock: 100 / n,
+            ..Default::default()
         })
-        .collect::<Vec<ProposedMilestone>>()
+        .collect::<Vec<ProposedMilestone<BlockNumber>>>()
         .try_into()
         .expect("qed");
 
@@ -116,8 +116,9 @@ fn get_max_milestones<T: Config>() -> BoundedPMilestones<T> {
     let milestones = (0..n)
         .map(|_| ProposedMilestone {
             percentage_to_unlock: 100 / n,
+            ..Default::default()
         })
-        .collect::<Vec<ProposedMilestone>>()
+        .collect::<Vec<ProposedMilestone<T::BlockNumber>>>()
         .try_into()
         .expect("qed");
 
@@ -5,7 +5,7 @@ use crate::{mock::*, tests};
 use common_types::{CurrencyId, TreasuryOrigin};
 use frame_support::assert_ok;
 use orml_traits::MultiCurrency;
-use pallet_proposals::{Projects, RoundType};
+use pallet_proposals::{ProjectContributions, Projects, RoundType};
 use sp_core::bounded_vec;
 
 #[test]
@@ -69,6 +69,7 @@ fn assert_state_from_grant_conversion_is_same_as_proposal() {
                 .expect("The bounds should be the same"),
             contribution_value,
             CurrencyId::Native,
+            None,
         ));
         assert_ok!(Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -112,11 +113,12 @@ fn assert_state_from_grant_conversion_is_same_as_proposal() {
             p_grant.milestones.values().len(),
             p_normal.milestones.values().len()
         );
-        let contributions_normal = p_normal.contributions.values().collect::<Vec<_>>();
-        assert!(p_grant
-            .contributions
-            .values()
-            .all(|c| contributions_normal.contains(&c)));
+        let contributions_normal =
+            ProjectContributions::<Test>::iter_prefix_values(project_normal).collect::<Vec<_>>();
+        assert!(
+            ProjectContributions::<Test>::iter_prefix_values(project_grant)
+                .all(|c| contributions_normal.contains(&c))
+        );
 
         assert_eq!(p_grant.currency_id, p_normal.currency_id);
         assert_eq!(p_grant.required_funds, p_normal.required_funds);
@@ -7,7 +7,7 @@ use frame_system::EnsureRoot;
 use orml_traits::MultiCurrency;
 use sp_core::sr25519::{Public, Signature};
 use sp_core::H256;
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_runtime::traits::{AccountIdConversion, IdentifyAccount, Verify};
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
@@ -15,7 +15,7 @@ use sp_runtime::{
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
-type BlockNumber = u64;
+pub type BlockNumber = u64;
 pub type Balance = u64;
 type Moment = u64;
 pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
@@ -86,6 +86,18 @@ orml_traits::parameter_type_with_key! {
     };
 }
 
+orml_traits::parameter_type_with_key! {
+    pub InitiatorBond: |_currency_id: CurrencyId| -> Balance {
+        0
+    };
+}
+
+orml_traits::parameter_type_with_key! {
+    pub MinimumContribution: |_currency_id: CurrencyId| -> Balance {
+        0
+    };
+}
+
 parameter_types! {
     pub const MaxReserves: u32 = 50;
     pub MaxLocks: u32 = 2;
@@ -137,11 +149,21 @@ parameter_types! {
     pub NoConfidenceTimeLimit: BlockNumber = 100800u32.into();
     pub PercentRequiredForVoteToPass: u8 = 75u8;
     pub MaximumContributorsPerProject: u32 = 5000;
-    pub RefundsPerBlock: u8 = 2;
     pub IsIdentityRequired: bool = false;
     pub MaxMilestonesPerProject: u32 = 50;
     pub ProjectStorageDeposit: Balance = 100;
     pub ImbueFee: u8 = 5;
+    pub ExpiringRoundsPerBlock: u32 = 100;
+    pub MaxMilestoneSubmissionAttempts: u32 = 3;
+    pub CollatorPot: AccountId = PalletId(*b"PotStake").into_account_truncating();
+    pub DisputeBond: Balance = 1000;
+    pub DisputeThreshold: u8 = 50;
+    pub DisputeVotingPeriod: BlockNumber = 100;
+    pub JurorBond: Balance = 1000;
+    pub MaxJurors: u32 = 10;
+    pub JurorsPerDispute: u32 = 3;
+    pub MaxTeamMembers: u32 = 10;
+    pub MaxDelegators: u32 = 50;
 }
 
 impl pallet_proposals::Config for Test {
@@ -155,13 +177,29 @@ impl pallet_proposals::Config for Test {
     type NoConfidenceTimeLimit = NoConfidenceTimeLimit;
     type PercentRequiredForVoteToPass = PercentRequiredForVoteToPass;
     type MaximumContributorsPerProject = MaximumContributorsPerProject;
-    type RefundsPerBlock = RefundsPerBlock;
     type IsIdentityRequired = IsIdentityRequired;
     type MilestoneVotingWindow = TwoWeekBlockUnit;
     type RefundHandler = pallet_proposals::traits::MockRefundHandler<Test>;
     type MaxMilestonesPerProject = MaxMilestonesPerProject;
     type ProjectStorageDeposit = ProjectStorageDeposit;
     type ImbueFee = ImbueFee;
+    type ExpiringRoundsPerBlock = ExpiringRoundsPerBlock;
+    type MaxMilestoneSubmissionAttempts = MaxMilestoneSubmissionAttempts;
+    type CollatorPot = CollatorPot;
+    type DisputeBond = DisputeBond;
+    type DisputeThreshold = DisputeThreshold;
+    type DisputeVotingPeriod = DisputeVotingPeriod;
+    type JurorBond = JurorBond;
+    type MaxJurors = MaxJurors;
+    type JurorsPerDispute = JurorsPerDispute;
+    type MaxTeamMembers = MaxTeamMembers;
+    type MaxDelegators = MaxDelegators;
+    type InitiatorBond = InitiatorBond;
+    type MinimumContribution = MinimumContribution;
+    type ContributionReceipts = pallet_proposals::traits::MockContributionReceipts<Test>;
+    type XcmOrigin = pallet_proposals::traits::MockXcmOrigin<Test>;
+    type LocationToAccountId = pallet_proposals::traits::MockLocationToAccountId<Test>;
+    type Randomness = pallet_proposals::traits::MockRandomness<Test>;
 }
 
 parameter_types! {
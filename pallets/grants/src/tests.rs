@@ -11,6 +11,7 @@ fn ensure_milestone_percent_equal_100() {
     new_test_ext().execute_with(|| {
         let milestones: BoundedPMilestones<Test> = vec![ProposedMilestone {
             percentage_to_unlock: 50,
+            ..Default::default()
         }]
         .try_into()
         .expect("qed");
@@ -195,6 +196,7 @@ fn assert_properties_are_changed_on_edit() {
 
         let edited_milestones: BoundedPMilestones<Test> = vec![ProposedMilestone {
             percentage_to_unlock: 100,
+            ..Default::default()
         }]
         .try_into()
         .expect("qed");
@@ -262,6 +264,7 @@ fn assert_edit_fails_if_milestones_sum_less_than_100() {
         );
         let edited_milestones: BoundedPMilestones<Test> = vec![ProposedMilestone {
             percentage_to_unlock: 99,
+            ..Default::default()
         }]
         .try_into()
         .expect("qed");
@@ -384,8 +387,9 @@ pub(crate) fn get_milestones(mut n: u32) -> BoundedPMilestones<Test> {
     (0..n)
         .map(|_m| ProposedMilestone {
             percentage_to_unlock: percent.try_into().expect("qed"),
+            ..Default::default()
         })
-        .collect::<Vec<ProposedMilestone>>()
+        .collect::<Vec<ProposedMilestone<BlockNumber>>>()
         .try_into()
         .expect("qed")
 }
@@ -28,8 +28,8 @@ benchmarks! {
         let agg_hash = H256::from([10u8; 32]);
         let project_key = 0;
         let project_account = Pallet::<T>::project_account_id(project_key);
-        // (Origin, ipfs_hash, ProposedMilestones, RequiredFunds, CurrencyId)
-    }: _(RawOrigin::Signed(caller.clone()), agg_hash, milestones, required_funds, CurrencyId::Native)
+        // (Origin, ipfs_hash, ProposedMilestones, RequiredFunds, CurrencyId, ContributionLimits)
+    }: _(RawOrigin::Signed(caller.clone()), agg_hash, milestones, required_funds, CurrencyId::Native, None)
     verify {
         assert_last_event::<T>(Event::<T>::ProjectCreated(caller, agg_hash, project_key, required_funds, CurrencyId::Native, project_account).into());
     }
@@ -43,8 +43,8 @@ benchmarks! {
         let currency_id = CurrencyId::Native;
         let agg_hash = H256::from([2; 32]);
 
-        // origin, project_key, proposed_milestones, required_funds, currency_id, agreement_hash
-    }: _(RawOrigin::Signed(caller.clone()), 0,  milestones, required_funds, currency_id, agg_hash)
+        // origin, project_key, proposed_milestones, required_funds, currency_id, agreement_hash, contribution_limits
+    }: _(RawOrigin::Signed(caller.clone()), 0,  milestones, required_funds, currency_id, agg_hash, None)
     verify {
         assert_last_event::<T>(Event::ProjectUpdated(caller, 0, required_funds).into());
     }
@@ -78,9 +78,9 @@ benchmarks! {
     }
 
     schedule_round {
-        let mut project_keys: BoundedProjectKeys = vec![].try_into().unwrap();
+        let mut project_keys: BoundedProjectKeys<T> = vec![].try_into().unwrap();
 
-        for i in 0..<MaxProjectKeysPerRound as Get<u32>>::get() {
+        for i in 0..<T as Config>::MaxProjectsPerRound::get() {
             let _caller = create_project_common::<T>(u32::MAX.into());
             let _ = project_keys.try_push(i).unwrap();
         }
@@ -93,8 +93,8 @@ benchmarks! {
 
     cancel_round {
         let caller: T::AccountId = whitelisted_caller();
-        let mut project_keys: BoundedProjectKeys = vec![].try_into().unwrap();
-        for i in 0..<MaxProjectKeysPerRound as Get<u32>>::get() {
+        let mut project_keys: BoundedProjectKeys<T> = vec![].try_into().unwrap();
+        for i in 0..<T as Config>::MaxProjectsPerRound::get() {
             let _caller = create_project_common::<T>(u32::MAX.into());
             let _ = project_keys.try_push(i).unwrap();
         }
@@ -109,11 +109,11 @@ benchmarks! {
 
     contribute {
         // Setup state.
-        let a in 0 .. <MaxProjectKeysPerRound as Get<u32>>::get() - 1;
+        let a in 0 .. <T as Config>::MaxProjectsPerRound::get() - 1;
         let alice: T::AccountId = create_funded_user::<T>("candidate", 1, 100_000);
         let caller: T::AccountId = whitelisted_caller();
-        let mut project_keys: BoundedProjectKeys = vec![].try_into().unwrap();
-        for i in 0..<MaxProjectKeysPerRound as Get<u32>>::get() {
+        let mut project_keys: BoundedProjectKeys<T> = vec![].try_into().unwrap();
+        for i in 0..<T as Config>::MaxProjectsPerRound::get() {
             let _caller = create_project_common::<T>(u32::MAX.into());
             let _ = project_keys.try_push(i).unwrap();
         }
@@ -130,12 +130,12 @@ benchmarks! {
 
     approve {
         // Setup state.
-        let a in 0 .. <MaxProjectKeysPerRound as Get<u32>>::get() - 1;
+        let a in 0 .. <T as Config>::MaxProjectsPerRound::get() - 1;
         //create a funded user for contribution
         let contribution = 100_000u32;
         let alice: T::AccountId = create_funded_user::<T>("candidate", 1, contribution);
-        let mut project_keys: BoundedProjectKeys = vec![].try_into().unwrap();
-        for i in 0..<MaxProjectKeysPerRound as Get<u32>>::get() {
+        let mut project_keys: BoundedProjectKeys<T> = vec![].try_into().unwrap();
+        for i in 0..<T as Config>::MaxProjectsPerRound::get() {
             let _caller = create_project_common::<T>(100_000u32.into());
             let _ = project_keys.try_push(i).unwrap();
         }
@@ -164,13 +164,14 @@ benchmarks! {
         Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
         Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys))?;
 
-        // (Initiator, ProjectKey, MilestoneKey)
-    }: _(RawOrigin::Signed(bob.clone()), 0, 0)
+        // (Initiator, ProjectKey, MilestoneKey, EvidenceHash)
+    }: _(RawOrigin::Signed(bob.clone()), 0, 0, H256::from([1; 32]))
     verify {
        assert_last_event::<T>(Event::<T>::VotingRoundCreated(2, vec![0]).into());
     }
 
     vote_on_milestone {
+        let d in 0 .. T::MaxDelegators::get();
         let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
         let bob: T::AccountId = create_funded_user::<T>("initiator", 1, 100_000);
 
@@ -182,8 +183,9 @@ benchmarks! {
         Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
         run_to_block::<T>(5u32.into());
         Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        add_delegators::<T>(&alice, d, contribution_amount);
         Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys))?;
-        Proposals::<T>::submit_milestone(RawOrigin::Signed(bob.clone()).into(), 0, 0)?;
+        Proposals::<T>::submit_milestone(RawOrigin::Signed(bob.clone()).into(), 0, 0, H256::from([1; 32]))?;
 
         run_to_block::<T>(11u32.into());
         // (Voter, ProjectKey, MilestoneKey, Option<RoundKey>, is_approved)
@@ -205,7 +207,7 @@ benchmarks! {
         run_to_block::<T>(5u32.into());
         Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
         Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys))?;
-        Proposals::<T>::submit_milestone(RawOrigin::Signed(bob.clone()).into(), 0, 0)?;
+        Proposals::<T>::submit_milestone(RawOrigin::Signed(bob.clone()).into(), 0, 0, H256::from([1; 32]))?;
         run_to_block::<T>(11u32.into());
         Proposals::<T>::vote_on_milestone(RawOrigin::Signed(alice.clone()).into(), 0, 0, Some(2), true)?;
 
@@ -232,7 +234,7 @@ benchmarks! {
         Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys.clone()))?;
 
         for key in milestone_keys.clone() {
-            Proposals::<T>::submit_milestone(RawOrigin::Signed(bob.clone()).into(), 0, key)?;
+            Proposals::<T>::submit_milestone(RawOrigin::Signed(bob.clone()).into(), 0, key, H256::from([1; 32]))?;
             run_to_block::<T>(System::<T>::block_number() + 1u32.into());
             Proposals::<T>::vote_on_milestone(RawOrigin::Signed(alice.clone()).into(), 0, key, Some(key + 2u32), true)?;
             Proposals::<T>::finalise_milestone_voting(RawOrigin::Signed(bob.clone()).into(), 0, key)?;
@@ -247,6 +249,7 @@ benchmarks! {
     }
 
     raise_vote_of_no_confidence {
+        let d in 0 .. T::MaxDelegators::get();
         let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
         let bob: T::AccountId = create_funded_user::<T>("initiator", 1, 100_000);
         let contribution_amount = 10_000u32;
@@ -256,6 +259,7 @@ benchmarks! {
         Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
         run_to_block::<T>(5u32.into());
         Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        add_delegators::<T>(&alice, d, contribution_amount);
         Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys))?;
 
         // (Initiator, ProjectKey)
@@ -265,6 +269,7 @@ benchmarks! {
     }
 
     vote_on_no_confidence_round {
+        let d in 0 .. T::MaxDelegators::get();
         let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
         let charlie: T::AccountId = create_funded_user::<T>("contributor2", 1, 100_000);
         let bob: T::AccountId = create_funded_user::<T>("initiator", 1, 100_000);
@@ -276,6 +281,7 @@ benchmarks! {
         run_to_block::<T>(5u32.into());
         Proposals::<T>::contribute(RawOrigin::Signed(charlie.clone()).into(), Some(1), 0, contribution_amount.into())?;
         Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        add_delegators::<T>(&charlie, d, contribution_amount);
         Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys))?;
         Proposals::<T>::raise_vote_of_no_confidence(RawOrigin::Signed(alice.clone()).into() , 0)?;
 
@@ -315,6 +321,384 @@ benchmarks! {
     verify {
         assert_last_event::<T>(Event::<T>::NoConfidenceRoundFinalised(2, 0).into());
     }
+
+    withdraw_contribution {
+        let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
+        let contribution_amount = 10_000u32;
+        create_project_common::<T>((contribution_amount * 2).into());
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+
+        // Withdrawing the whole contribution also removes the contribution and burns its receipt.
+        // (Contributor, RoundKey, ProjectKey, Value)
+    }: _(RawOrigin::Signed(alice.clone()), Some(1u32), 0u32, contribution_amount.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::ContributionWithdrawn(alice.clone(), 0, contribution_amount.into(), CurrencyId::Native, 5u32.into()).into());
+        assert!(Proposals::<T>::project_contributions(0, alice).is_none());
+    }
+
+    fund_matching_pool {
+        let alice: T::AccountId = create_funded_user::<T>("sponsor", 1, 100_000);
+        let amount = 10_000u32;
+        create_project_common::<T>(amount.into());
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::fund_matching_pool(RawOrigin::Signed(alice.clone()).into(), 1, CurrencyId::Native, amount.into())?;
+
+        // (Sponsor, RoundKey, CurrencyId, Amount)
+    }: _(RawOrigin::Signed(alice.clone()), 1u32, CurrencyId::Native, amount.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::MatchingPoolFunded(1, alice, amount.into(), CurrencyId::Native).into());
+    }
+
+    close_contribution_round {
+        let p in 1 .. T::MaxProjectsPerRound::get();
+        let contribution_amount = 10_000u32;
+        // Setup state: A funded round of `p` projects which all met their threshold and share a matching pool.
+        for _ in 0..p {
+            create_project_common::<T>(contribution_amount);
+        }
+        let project_keys: BoundedProjectKeys<T> = (0..p).collect::<Vec<u32>>().try_into().unwrap();
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), project_keys, RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        for i in 0..p {
+            let acc = create_funded_user::<T>("contributor", i, 100_000);
+            Proposals::<T>::contribute(RawOrigin::Signed(acc).into(), Some(1), i, contribution_amount.into())?;
+        }
+        let sponsor: T::AccountId = create_funded_user::<T>("sponsor", 1, 100_000);
+        Proposals::<T>::fund_matching_pool(RawOrigin::Signed(sponsor).into(), 1, CurrencyId::Native, contribution_amount.into())?;
+        System::<T>::set_block_number(10u32.into());
+    }: {
+        Proposals::<T>::close_expired_round(ExpiringRound::Contribution(1));
+    }
+    verify {
+        assert_last_event::<T>(Event::<T>::FundingRoundEnded(1).into());
+    }
+
+    delegate_vote {
+        let d in 0 .. T::MaxDelegators::get() - 1;
+        let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
+        let bob: T::AccountId = create_funded_user::<T>("delegate", 1, 100_000);
+        let contribution_amount = 10_000u32;
+        create_project_common::<T>(contribution_amount.into());
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        add_delegators::<T>(&bob, d, contribution_amount);
+
+        // (Delegator, ProjectKey, Delegate)
+    }: _(RawOrigin::Signed(alice.clone()), 0u32, bob.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::VoteDelegated(alice, 0, bob).into());
+    }
+
+    undelegate_vote {
+        let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
+        let bob: T::AccountId = create_funded_user::<T>("delegate", 1, 100_000);
+        let contribution_amount = 10_000u32;
+        create_project_common::<T>(contribution_amount.into());
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        Proposals::<T>::delegate_vote(RawOrigin::Signed(alice.clone()).into(), 0, bob.clone())?;
+
+        // (Delegator, ProjectKey)
+    }: _(RawOrigin::Signed(alice.clone()), 0u32)
+    verify {
+        assert_last_event::<T>(Event::<T>::VoteUndelegated(alice, 0, bob).into());
+    }
+
+    set_funding_threshold {
+        let bob: T::AccountId = create_project_common::<T>(10_000u32);
+
+        // (Initiator, ProjectKey, FundingThreshold)
+    }: _(RawOrigin::Signed(bob), 0u32, 60u32)
+    verify {
+        assert_last_event::<T>(Event::<T>::FundingThresholdSet(0, 60).into());
+    }
+
+    set_fee_split {
+        let fee_split = FeeSplit {
+            treasury: Perbill::from_percent(50),
+            burn: Perbill::from_percent(25),
+            collators: Perbill::from_percent(25),
+        };
+        // (FeeSplit)
+    }: _(RawOrigin::Root, fee_split.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::FeeSplitSet(fee_split).into());
+    }
+
+    set_fee_rate {
+        // (FundingType, FeeRate)
+    }: _(RawOrigin::Root, FundingType::Brief, Some(2u8))
+    verify {
+        assert_last_event::<T>(Event::<T>::FeeRateSet(FundingType::Brief, Some(2)).into());
+    }
+
+    claim_refund {
+        let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 100_000);
+        let charlie: T::AccountId = create_funded_user::<T>("contributor2", 1, 100_000);
+        let contribution_amount = 10_000u32;
+        // Setup state: A project which did not reach its required funds.
+        create_project_common::<T>((contribution_amount * 3).into());
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::contribute(RawOrigin::Signed(charlie.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        Proposals::<T>::contribute(RawOrigin::Signed(alice.clone()).into(), Some(1), 0, contribution_amount.into())?;
+        run_to_block::<T>(11u32.into());
+        Proposals::<T>::claim_refund(RawOrigin::Signed(charlie).into(), 0)?;
+
+        // (Contributor, ProjectKey)
+    }: _(RawOrigin::Signed(alice.clone()), 0u32)
+    verify {
+        assert_last_event::<T>(Event::<T>::ContributionRefunded(alice, 0, contribution_amount.into(), CurrencyId::Native).into());
+    }
+
+    cancel_project {
+        let c in 1 .. T::MaximumContributorsPerProject::get();
+        let contribution_amount = 10_000u32;
+        // Setup state: A project with `c` contributors whose first milestone has been approved but not withdrawn.
+        let initiator = create_project_common::<T>(contribution_amount * c);
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        for i in 0..c {
+            let acc = create_funded_user::<T>("contributor", i, 100_000);
+            Proposals::<T>::contribute(RawOrigin::Signed(acc).into(), Some(1), 0, contribution_amount.into())?;
+        }
+        let milestone_keys: BoundedMilestoneKeys<T> = vec![0].try_into().unwrap();
+        Proposals::<T>::approve(RawOrigin::Root.into(), Some(1), 0, Some(milestone_keys))?;
+
+        // (Initiator, ProjectKey)
+    }: _(RawOrigin::Signed(initiator.clone()), 0u32)
+    verify {
+        assert_last_event::<T>(Event::<T>::ProjectCancelledByInitiator(initiator, 0).into());
+    }
+
+    add_juror {
+        let _ = create_jurors::<T>(<T as Config>::MaxJurors::get() - 1);
+        let juror = create_funded_juror::<T>(<T as Config>::MaxJurors::get());
+        // (Juror)
+    }: _(RawOrigin::Root, juror.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::JurorAdded(juror).into());
+    }
+
+    remove_juror {
+        let jurors = create_jurors::<T>(<T as Config>::MaxJurors::get());
+        let juror = jurors[jurors.len() - 1].clone();
+        // (Juror, Slash)
+    }: _(RawOrigin::Root, juror.clone(), true)
+    verify {
+        assert_last_event::<T>(Event::<T>::JurorRemoved(juror, true).into());
+    }
+
+    raise_dispute {
+        let alice = create_approved_milestone::<T>();
+        let _ = create_jurors::<T>(<T as Config>::JurorsPerDispute::get());
+        // (Contributor, ProjectKey, MilestoneKey)
+    }: _(RawOrigin::Signed(alice), 0, 0)
+    verify {
+        assert_eq!(Proposals::<T>::disputes(0, 0).unwrap().status, DisputeStatus::Drawing);
+    }
+
+    draw_arbitration_panel {
+        let j in 1 .. <T as Config>::MaxJurors::get();
+        let alice = create_approved_milestone::<T>();
+        let _ = create_jurors::<T>(j);
+        Proposals::<T>::raise_dispute(RawOrigin::Signed(alice).into(), 0, 0)?;
+        System::<T>::set_block_number(Proposals::<T>::disputes(0, 0).unwrap().deadline);
+    }: {
+        Proposals::<T>::close_expired_round(ExpiringRound::Dispute(0, 0));
+    }
+    verify {
+        assert_eq!(Proposals::<T>::disputes(0, 0).unwrap().status, DisputeStatus::Arbitration);
+    }
+
+    vote_on_dispute {
+        let alice = create_approved_milestone::<T>();
+        let _ = create_jurors::<T>(<T as Config>::JurorsPerDispute::get());
+        let panel = create_arbitrated_dispute::<T>(alice).jurors;
+        for juror in panel.iter().skip(1) {
+            Proposals::<T>::vote_on_dispute(RawOrigin::Signed(juror.clone()).into(), 0, 0, false)?;
+        }
+        // (Juror, ProjectKey, MilestoneKey, ApproveMilestone)
+    }: _(RawOrigin::Signed(panel[0].clone()), 0, 0, false)
+    verify {
+        assert_last_event::<T>(Event::<T>::DisputeResolved(0, 0, false).into());
+    }
+
+    close_dispute {
+        let alice = create_approved_milestone::<T>();
+        let _ = create_jurors::<T>(<T as Config>::JurorsPerDispute::get());
+        let dispute = create_arbitrated_dispute::<T>(alice);
+        for juror in dispute.jurors.iter().skip(1) {
+            Proposals::<T>::vote_on_dispute(RawOrigin::Signed(juror.clone()).into(), 0, 0, false)?;
+        }
+        System::<T>::set_block_number(dispute.deadline);
+    }: {
+        Proposals::<T>::close_expired_round(ExpiringRound::Dispute(0, 0));
+    }
+    verify {
+        assert_last_event::<T>(Event::<T>::DisputeResolved(0, 0, false).into());
+    }
+
+    set_payout_vesting {
+        let initiator = create_project_common::<T>(10_000u32);
+        let vesting_period: T::BlockNumber = 100u32.into();
+        // (Initiator, ProjectKey, VestingPeriod)
+    }: _(RawOrigin::Signed(initiator), 0, Some(vesting_period))
+    verify {
+        assert_last_event::<T>(Event::<T>::PayoutVestingSet(0, Some(vesting_period)).into());
+    }
+
+    propose_initiator_transfer {
+        let initiator = create_project_common::<T>(10_000u32);
+        let new_initiator: T::AccountId = create_funded_user::<T>("new_initiator", 1, 100_000);
+        // (Initiator, ProjectKey, NewInitiator)
+    }: _(RawOrigin::Signed(initiator), 0, new_initiator.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::InitiatorTransferProposed(0, new_initiator).into());
+    }
+
+    vote_on_initiator_transfer {
+        let alice = create_approved_milestone::<T>();
+        let initiator: T::AccountId = account("initiator", 1, SEED);
+        let new_initiator: T::AccountId = create_funded_user::<T>("new_initiator", 1, 100_000);
+        Proposals::<T>::propose_initiator_transfer(RawOrigin::Signed(initiator).into(), 0, new_initiator)?;
+        // (Contributor, ProjectKey, Approve)
+    }: _(RawOrigin::Signed(alice), 0, true)
+    verify {
+        assert_last_event::<T>(Event::<T>::InitiatorTransferApproved(0).into());
+    }
+
+    accept_initiator_transfer {
+        let initiator = create_project_common::<T>(10_000u32);
+        let new_initiator: T::AccountId = create_funded_user::<T>("new_initiator", 1, 100_000);
+        let _ = <T::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::deposit(
+            CurrencyId::Native,
+            &new_initiator,
+            <T as Config>::ProjectStorageDeposit::get(),
+        );
+        Proposals::<T>::propose_initiator_transfer(RawOrigin::Signed(initiator.clone()).into(), 0, new_initiator.clone())?;
+        // (NewInitiator, ProjectKey)
+    }: _(RawOrigin::Signed(new_initiator.clone()), 0)
+    verify {
+        assert_last_event::<T>(Event::<T>::InitiatorTransferred(0, initiator, new_initiator).into());
+    }
+
+    set_project_team {
+        let initiator = create_project_common::<T>(10_000u32);
+        let members: Vec<T::AccountId> = (0..<T as Config>::MaxTeamMembers::get())
+            .map(|i| account("member", i, SEED))
+            .collect();
+        let team: BoundedTeam<T> = members
+            .iter()
+            .map(|member| TeamMember {
+                account: member.clone(),
+                role: TeamRole::Treasurer,
+                payout_share: 0,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        // (Initiator, ProjectKey, Team)
+    }: _(RawOrigin::Signed(initiator), 0, team)
+    verify {
+        assert_last_event::<T>(Event::<T>::ProjectTeamSet(0, members).into());
+    }
+
+    set_project_whitelist_root {
+        let initiator = create_project_common::<T>(10_000u32);
+        let root = H256::repeat_byte(1);
+        // (Initiator, ProjectKey, Root)
+    }: _(RawOrigin::Signed(initiator), 0, root)
+    verify {
+        assert_last_event::<T>(Event::<T>::WhitelistRootSet(0, root).into());
+    }
+
+    contribute_with_proof {
+        let initiator = create_project_common::<T>(100_000u32);
+        let alice: T::AccountId = create_funded_user::<T>("candidate", 1, 100_000);
+        // A whitelist with a single uncapped leaf is its own root.
+        let leaf = Proposals::<T>::whitelist_leaf(&alice, 0u32.into());
+        let _ = Proposals::<T>::set_project_whitelist_root(RawOrigin::Signed(initiator).into(), 0, leaf);
+        let _ = Proposals::<T>::schedule_round(RawOrigin::Root.into(), 3u32.into(), 10u32.into(), vec![0].try_into().unwrap(), RoundType::ContributionRound);
+        run_to_block::<T>(5u32.into());
+        // (Origin, RoundKey, ProjectKey, Contribution, MaxCap, Proof)
+    }: _(RawOrigin::Signed(alice.clone()), Some(1u32), 0, 10_000u32.into(), 0u32.into(), Proof::new(leaf, vec![]))
+    verify {
+        assert_last_event::<T>(Event::<T>::ContributeSucceeded(alice, 0, 10_000u32.into(), CurrencyId::Native, 5u32.into()).into());
+    }
+
+    set_default_identity_policy {
+        let policy = get_max_identity_policy::<T>();
+        // (Origin, Policy)
+    }: _(RawOrigin::Root, Some(policy))
+    verify {
+        assert_last_event::<T>(Event::<T>::DefaultIdentityPolicySet.into());
+    }
+
+    set_project_identity_policy {
+        let initiator = create_project_common::<T>(10_000u32);
+        let policy = get_max_identity_policy::<T>();
+        // (Initiator, ProjectKey, Policy)
+    }: _(RawOrigin::Signed(initiator), 0, Some(policy))
+    verify {
+        assert_last_event::<T>(Event::<T>::ProjectIdentityPolicySet(0).into());
+    }
+
+    contribute_via_xcm {
+        let _ = create_project_common::<T>(100_000u32);
+        let (origin, _, remote) = create_remote_contributor::<T>()?;
+        let _ = Proposals::<T>::schedule_round(RawOrigin::Root.into(), 3u32.into(), 10u32.into(), vec![0].try_into().unwrap(), RoundType::ContributionRound);
+        run_to_block::<T>(5u32.into());
+        // (Origin, RoundKey, ProjectKey, Contribution)
+    }: _<T::RuntimeOrigin>(origin, Some(1u32), 0, 10_000u32.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::ContributeSucceeded(remote, 0, 10_000u32.into(), CurrencyId::Native, 5u32.into()).into());
+    }
+
+    claim_refund_via_xcm {
+        let (origin, _, remote) = create_remote_contributor::<T>()?;
+        let contribution_amount = 10_000u32;
+        // Setup state: A project which did not reach its required funds.
+        create_project_common::<T>(contribution_amount * 3);
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::contribute_via_xcm(origin.clone(), Some(1), 0, contribution_amount.into())?;
+        run_to_block::<T>(11u32.into());
+        // (Origin, ProjectKey)
+    }: _<T::RuntimeOrigin>(origin, 0u32)
+    verify {
+        assert_last_event::<T>(Event::<T>::ContributionRefunded(remote, 0, contribution_amount.into(), CurrencyId::Native).into());
+    }
+
+    withdraw_contribution_via_xcm {
+        let (origin, _, remote) = create_remote_contributor::<T>()?;
+        let contribution_amount = 10_000u32;
+        create_project_common::<T>(contribution_amount * 2);
+        Proposals::<T>::schedule_round(RawOrigin::Root.into(), 2u32.into(), 10u32.into(), vec![0u32].try_into().unwrap(), RoundType::ContributionRound)?;
+        run_to_block::<T>(5u32.into());
+        Proposals::<T>::contribute_via_xcm(origin.clone(), Some(1), 0, contribution_amount.into())?;
+        // (Origin, RoundKey, ProjectKey, Value)
+    }: _<T::RuntimeOrigin>(origin, Some(1u32), 0u32, contribution_amount.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::ContributionWithdrawn(remote, 0, contribution_amount.into(), CurrencyId::Native, 5u32.into()).into());
+    }
+
+    retry_remote_refund {
+        let caller: T::AccountId = whitelisted_caller();
+        let (_, location, remote) = create_remote_contributor::<T>()?;
+        let refund: BalanceOf<T> = 10_000u32.into();
+        RemoteContributors::<T>::insert(&remote, location);
+        UnsentRemoteRefunds::<T>::insert(&remote, CurrencyId::Native, refund);
+        // (Caller, Contributor, CurrencyId)
+    }: _(RawOrigin::Signed(caller), remote.clone(), CurrencyId::Native)
+    verify {
+        assert_last_event::<T>(Event::<T>::RemoteRefundSent(remote, refund).into());
+    }
 }
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent)
@@ -328,6 +712,19 @@ where
     assert_eq!(event, &system_event);
 }
 
+fn get_max_identity_policy<T: Config>() -> IdentityPolicy<T> {
+    IdentityPolicy {
+        trusted_registrars: (0..<T as pallet_identity::Config>::MaxRegistrars::get())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        required_fields: Default::default(),
+        initiators: true,
+        contributor_threshold: Some(10_000u32.into()),
+        brief_applicants: true,
+    }
+}
+
 fn create_project_common<T: Config>(contribution: u32) -> T::AccountId {
     let bob: T::AccountId = create_funded_user::<T>("initiator", 1, 100_000_000);
     let milestones = get_max_milestones::<T>();
@@ -341,11 +738,116 @@ fn create_project_common<T: Config>(contribution: u32) -> T::AccountId {
         agg_hash,
         milestones,
         required_funds,
-        currency_id
+        currency_id,
+        None
     ));
     bob
 }
 
+/// Create a project whose first milestone has been approved, returning its only contributor.
+fn create_approved_milestone<T: Config>() -> T::AccountId {
+    let alice: T::AccountId = create_funded_user::<T>("contributor", 1, 1_000_000);
+    let _ = <T::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::deposit(
+        CurrencyId::Native,
+        &alice,
+        <T as Config>::DisputeBond::get(),
+    );
+    let contribution_amount = 1_000_000u32;
+    let milestone_keys: BoundedMilestoneKeys<T> = vec![0].try_into().unwrap();
+
+    create_project_common::<T>(contribution_amount);
+    assert_ok!(Proposals::<T>::schedule_round(
+        RawOrigin::Root.into(),
+        2u32.into(),
+        10u32.into(),
+        vec![0u32].try_into().unwrap(),
+        RoundType::ContributionRound
+    ));
+    run_to_block::<T>(5u32.into());
+    assert_ok!(Proposals::<T>::contribute(
+        RawOrigin::Signed(alice.clone()).into(),
+        Some(1),
+        0,
+        contribution_amount.into()
+    ));
+    assert_ok!(Proposals::<T>::approve(
+        RawOrigin::Root.into(),
+        Some(1),
+        0,
+        Some(milestone_keys)
+    ));
+    alice
+}
+
+/// The origin, location and local account of a funded account on a sibling parachain.
+fn create_remote_contributor<T: Config>(
+) -> Result<(T::RuntimeOrigin, MultiLocation, T::AccountId), &'static str> {
+    let origin = T::XcmOrigin::try_successful_origin().map_err(|_| "no xcm origin")?;
+    let location = T::XcmOrigin::try_origin(origin.clone()).map_err(|_| "no xcm origin")?;
+    let remote: T::AccountId =
+        T::LocationToAccountId::convert(location.clone()).ok_or("no account for location")?;
+    let _ = <T::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::deposit(
+        CurrencyId::Native,
+        &remote,
+        100_000u32.into(),
+    );
+    Ok((origin, location, remote))
+}
+
+fn create_funded_juror<T: Config>(n: u32) -> T::AccountId {
+    let juror: T::AccountId = account("juror", n, SEED);
+    let _ = <T::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::deposit(
+        CurrencyId::Native,
+        &juror,
+        <T as Config>::JurorBond::get() + <T as Config>::JurorBond::get(),
+    );
+    juror
+}
+
+/// Have `d` new contributors to the first project delegate their vote to `delegate`.
+/// Must be called while the project's contribution round is open.
+fn add_delegators<T: Config>(delegate: &T::AccountId, d: u32, contribution: u32) {
+    for i in 0..d {
+        let delegator = create_funded_user::<T>("delegator", i, 100_000);
+        assert_ok!(Proposals::<T>::contribute(
+            RawOrigin::Signed(delegator.clone()).into(),
+            Some(1),
+            0,
+            contribution.into()
+        ));
+        assert_ok!(Proposals::<T>::delegate_vote(
+            RawOrigin::Signed(delegator).into(),
+            0,
+            delegate.clone()
+        ));
+    }
+}
+
+/// Raise a dispute on the first milestone which goes straight to arbitration and draw its panel.
+fn create_arbitrated_dispute<T: Config>(raiser: T::AccountId) -> Dispute<T> {
+    assert_ok!(Proposals::<T>::raise_dispute(
+        RawOrigin::Signed(raiser).into(),
+        0,
+        0
+    ));
+    System::<T>::set_block_number(Proposals::<T>::disputes(0, 0).unwrap().deadline);
+    Proposals::<T>::close_expired_round(ExpiringRound::Dispute(0, 0));
+    Proposals::<T>::disputes(0, 0).unwrap()
+}
+
+fn create_jurors<T: Config>(n: u32) -> Vec<T::AccountId> {
+    (0..n)
+        .map(|i| {
+            let juror = create_funded_juror::<T>(i);
+            assert_ok!(Proposals::<T>::add_juror(
+                RawOrigin::Root.into(),
+                juror.clone()
+            ));
+            juror
+        })
+        .collect()
+}
+
 fn run_to_block<T: Config>(n: T::BlockNumber) {
     while System::<T>::block_number() < n {
         if System::<T>::block_number() > 1u32.into() {
@@ -381,8 +883,9 @@ fn get_milestones<T: Config>(mut n: u32) -> BoundedProposedMilestones<T> {
     let milestones = (0..n)
         .map(|_| ProposedMilestone {
             percentage_to_unlock: 100 / n,
+            ..Default::default()
         })
-        .collect::<Vec<ProposedMilestone>>()
+        .collect::<Vec<ProposedMilestone<T::BlockNumber>>>()
         .try_into()
         .expect("qed");
 
@@ -1,8 +1,14 @@
+use crate::traits::WhitelistVerifier;
 use crate::*;
 use common_types::milestone_origin::FundingType;
-use pallet_identity::Judgement;
-use sp_runtime::traits::Saturating;
-use sp_std::{collections::btree_map::BTreeMap, vec};
+use orml_traits::BalanceStatus;
+use pallet_identity::{Data, IdentityField, IdentityInfo, Judgement};
+use proofs::Verifier;
+use sp_runtime::{
+    traits::{IntegerSquareRoot, One, Saturating, Zero},
+    PerThing, Perbill,
+};
+use sp_std::{vec, vec::Vec};
 pub const MAX_PERCENTAGE: u32 = 100u32;
 use scale_info::prelude::format;
 
@@ -33,6 +39,11 @@ impl<T: Config> Pallet<T> {
         T::PalletId::get().into_sub_account_truncating(format!("//{key}"))
     }
 
+    /// The account holding the matching pool of a round until it is distributed.
+    pub fn matching_pool_account_id(round_key: RoundKey) -> T::AccountId {
+        T::PalletId::get().into_sub_account_truncating(format!("//matching//{round_key}"))
+    }
+
     pub fn new_project(
         who: T::AccountId,
         agreement_hash: H256,
@@ -40,49 +51,68 @@ impl<T: Config> Pallet<T> {
         required_funds: BalanceOf<T>,
         currency_id: common_types::CurrencyId,
         funding_type: FundingType,
+        contribution_limits: Option<ContributionLimits<BalanceOf<T>>>,
     ) -> Result<ProjectKey, DispatchError> {
         // Check if identity is required
         if <T as Config>::IsIdentityRequired::get() {
             Self::ensure_identity_is_decent(&who)?;
         }
-
-        <T as Config>::MultiCurrency::reserve(
-            CurrencyId::Native,
-            &who,
-            T::ProjectStorageDeposit::get(),
-        )
-        .map_err(|_| Error::<T>::ImbueRequiredForStorageDep)?;
+        if let Some(policy) = Self::default_identity_policy() {
+            if policy.initiators {
+                Self::ensure_identity_meets(&who, &policy)?;
+            }
+        }
+        if let Some(limits) = contribution_limits {
+            Self::ensure_valid_contribution_limits(&limits)?;
+        }
 
         let project_key = ProjectCount::<T>::get();
         let next_project_key = project_key.checked_add(1).ok_or(Error::<T>::Overflow)?;
 
         let mut milestone_key: u32 = 0;
 
-        let mut milestones: BTreeMap<MilestoneKey, Milestone> = BTreeMap::new();
+        let mut milestones: BoundedMilestones<T> = BoundedBTreeMap::new();
+        let now = <frame_system::Pallet<T>>::block_number();
 
         // Fill in the projects structure in advance
         for milestone in proposed_milestones {
+            if let Some(due_block) = milestone.due_block {
+                ensure!(due_block > now, Error::<T>::MilestoneDueBlockInPast);
+            }
             let milestone = Milestone {
                 project_key,
                 milestone_key,
                 percentage_to_unlock: milestone.percentage_to_unlock,
                 is_approved: false,
+                title_hash: milestone.title_hash,
+                content_hash: milestone.content_hash,
+                due_block: milestone.due_block,
             };
-            milestones.insert(milestone_key, milestone);
+            milestones
+                .try_insert(milestone_key, milestone)
+                .map_err(|_| Error::<T>::ParamLimitExceed)?;
             milestone_key = milestone_key.checked_add(1).ok_or(Error::<T>::Overflow)?;
         }
 
+        Self::reserve_initiator_bond(project_key, &who, currency_id)?;
+        <T as Config>::MultiCurrency::reserve(
+            CurrencyId::Native,
+            &who,
+            T::ProjectStorageDeposit::get(),
+        )
+        .map_err(|_| Error::<T>::ImbueRequiredForStorageDep)?;
+
         // Create a project
         let project = Project {
             agreement_hash,
             milestones,
-            contributions: BTreeMap::new(),
             required_funds,
+            funding_threshold: MAX_PERCENTAGE,
             currency_id,
             raised_funds: (0_u32).into(),
             withdrawn_funds: (0_u32).into(),
             initiator: who.clone(),
-            created_on: <frame_system::Pallet<T>>::block_number(),
+            created_on: now,
             approved_for_funding: false,
             funding_threshold_met: false,
             cancelled: false,
@@ -91,6 +121,7 @@ impl<T: Config> Pallet<T> {
 
         // Add project to list
         <Projects<T>>::insert(project_key, project);
+        ProjectContributionLimits::<T>::set(project_key, contribution_limits);
         ProjectCount::<T>::put(next_project_key);
         let project_account = Self::project_account_id(project_key);
         Self::deposit_event(Event::ProjectCreated(
@@ -112,11 +143,20 @@ impl<T: Config> Pallet<T> {
         required_funds: BalanceOf<T>,
         currency_id: CurrencyId,
         agreement_hash: H256,
+        contribution_limits: Option<ContributionLimits<BalanceOf<T>>>,
     ) -> DispatchResultWithPostInfo {
         // Check if identity is required
         if <T as Config>::IsIdentityRequired::get() {
             Self::ensure_identity_is_decent(&who)?;
         }
+        if let Some(policy) = Self::identity_policy_of(project_key) {
+            if policy.initiators {
+                Self::ensure_identity_meets(&who, &policy)?;
+            }
+        }
+        if let Some(limits) = contribution_limits {
+            Self::ensure_valid_contribution_limits(&limits)?;
+        }
 
         //check to ensure valid and existing project
         let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
@@ -130,20 +170,35 @@ impl<T: Config> Pallet<T> {
 
         let mut milestone_key: u32 = 0;
 
-        let mut milestones: BTreeMap<MilestoneKey, Milestone> = BTreeMap::new();
+        let mut milestones: BoundedMilestones<T> = BoundedBTreeMap::new();
+        let now = <frame_system::Pallet<T>>::block_number();
 
         // Fill in the projects structure in advance
         for milestone in proposed_milestones {
+            if let Some(due_block) = milestone.due_block {
+                ensure!(due_block > now, Error::<T>::MilestoneDueBlockInPast);
+            }
             let milestone = Milestone {
                 project_key,
                 milestone_key,
                 percentage_to_unlock: milestone.percentage_to_unlock,
                 is_approved: false,
+                title_hash: milestone.title_hash,
+                content_hash: milestone.content_hash,
+                due_block: milestone.due_block,
             };
-            milestones.insert(milestone_key, milestone.clone());
+            milestones
+                .try_insert(milestone_key, milestone)
+                .map_err(|_| Error::<T>::ParamLimitExceed)?;
             milestone_key = milestone_key.checked_add(1).ok_or(Error::<T>::Overflow)?;
         }
 
+        // The bond is held in the project's currency.
+        if currency_id != project.currency_id {
+            Self::return_initiator_bond(project_key, &project);
+            Self::reserve_initiator_bond(project_key, &who, currency_id)?;
+        }
+
         // Update project
         project.milestones = milestones;
         project.required_funds = required_funds;
@@ -157,6 +212,7 @@ impl<T: Config> Pallet<T> {
         /// >
         // Add project to list
         <Projects<T>>::insert(project_key, project);
+        ProjectContributionLimits::<T>::set(project_key, contribution_limits);
 
         Ok(().into())
     }
@@ -164,7 +220,7 @@ impl<T: Config> Pallet<T> {
     pub fn new_round(
         start: T::BlockNumber,
         end: T::BlockNumber,
-        project_keys: BoundedProjectKeys,
+        project_keys: BoundedProjectKeys<T>,
         round_type: RoundType,
     ) -> DispatchResultWithPostInfo {
         // Find the last valid round
@@ -173,11 +229,15 @@ impl<T: Config> Pallet<T> {
             .ok_or(Error::<T>::Overflow)?;
         RoundCount::<T>::put(round_key);
 
-        let round = RoundOf::<T>::new(start, end, project_keys.clone().into(), round_type.clone());
+        let round = RoundOf::<T>::new(start, end, project_keys.clone(), round_type.clone());
 
         // Add project round to list
         <Rounds<T>>::insert(round_key, Some(round));
 
+        if round_type == RoundType::ContributionRound {
+            Self::schedule_round_expiry(end, ExpiringRound::Contribution(round_key))?;
+        }
+
         // Project keys is bounded to 5 projects maximum.
         for project_key in project_keys.iter() {
             //Try update project as approved for funding, assuming only RoundType::Contribution will be used.
@@ -187,6 +247,7 @@ impl<T: Config> Pallet<T> {
                 }
                 Ok(())
             })?;
+            ProjectRounds::<T>::insert(project_key, round_key, ());
         }
 
         match round_type {
@@ -207,6 +268,7 @@ impl<T: Config> Pallet<T> {
         round_key: RoundKey,
         project_key: ProjectKey,
         value: BalanceOf<T>,
+        whitelist_proof: Option<(BalanceOf<T>, Proof<H256>)>,
     ) -> DispatchResultWithPostInfo {
         /// <HB SBP Review:
         ///
@@ -227,7 +289,7 @@ impl<T: Config> Pallet<T> {
 
         ensure!(round.start <= now, Error::<T>::StartBlockNumberInvalid);
 
-        ensure!(round.end >= now, Error::<T>::EndBlockNumberInvalid);
+        ensure!(round.end > now, Error::<T>::EndBlockNumberInvalid);
 
         ensure!(
             round.project_keys.contains(&project_key),
@@ -235,12 +297,50 @@ impl<T: Config> Pallet<T> {
         );
 
         let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(!project.cancelled, Error::<T>::ProjectCancelled);
 
-        let new_amount = match project.contributions.get(&who) {
+        let new_amount = match ProjectContributions::<T>::get(project_key, &who) {
             Some(contribution) => contribution.value,
             None => BalanceOf::<T>::default(),
         }
         .saturating_add(value);
+        let is_new_contributor = !ProjectContributions::<T>::contains_key(project_key, &who);
+        ensure!(
+            !is_new_contributor
+                || Self::project_contributor_count(project_key)
+                    < T::MaximumContributorsPerProject::get(),
+            Error::<T>::TooManyContributors
+        );
+
+        ensure!(
+            value >= T::MinimumContribution::get(&project.currency_id),
+            Error::<T>::ContributionBelowMinimum
+        );
+        if let Some(limits) = Self::contribution_limits(project_key) {
+            ensure!(
+                value >= limits.min_contribution,
+                Error::<T>::ContributionBelowMinimum
+            );
+            ensure!(
+                limits.max_per_contributor.is_zero() || new_amount <= limits.max_per_contributor,
+                Error::<T>::ContributionAboveMaximum
+            );
+            ensure!(
+                limits.hard_cap.is_zero()
+                    || project.raised_funds.saturating_add(value) <= limits.hard_cap,
+                Error::<T>::HardCapReached
+            );
+        }
+
+        // Large contributors may need an identity.
+        if let Some(policy) = Self::identity_policy_of(project_key) {
+            if policy
+                .contributor_threshold
+                .map_or(false, |threshold| new_amount > threshold)
+            {
+                Self::ensure_identity_meets(&who, &policy)?;
+            }
+        }
 
         // Find whitelist if exists
         if WhitelistSpots::<T>::contains_key(project_key) {
@@ -260,6 +360,21 @@ impl<T: Config> Pallet<T> {
             );
         }
 
+        // Or prove a spot in the merkle whitelist.
+        if let Some(root) = WhitelistRoots::<T>::get(project_key) {
+            let (max_cap, proof) =
+                whitelist_proof.ok_or(Error::<T>::OnlyWhitelistedAccountsCanContribute)?;
+            ensure!(
+                proof.leaf_hash == Self::whitelist_leaf(&who, max_cap)
+                    && WhitelistVerifier.verify_proof(root, &proof),
+                Error::<T>::InvalidWhitelistProof
+            );
+            ensure!(
+                max_cap == Zero::zero() || max_cap >= new_amount,
+                Error::<T>::ContributionMustBeLowerThanMaxCap
+            );
+        }
+
         // Transfer contribute to project account
         T::MultiCurrency::transfer(
             project.currency_id,
@@ -267,6 +382,13 @@ impl<T: Config> Pallet<T> {
             &Self::project_account_id(project_key),
             value,
         )?;
+        T::ContributionReceipts::issue_receipt(
+            project_key,
+            &who,
+            new_amount,
+            project.currency_id,
+            now,
+        )?;
 
         Self::deposit_event(Event::ContributeSucceeded(
             who.clone(),
@@ -278,13 +400,24 @@ impl<T: Config> Pallet<T> {
 
         let timestamp = <pallet_timestamp::Pallet<T>>::get();
 
-        project.contributions.insert(
+        ProjectContributions::<T>::insert(
+            project_key,
             who,
             Contribution {
                 value: new_amount,
                 timestamp,
             },
         );
+        Self::update_contribution_root_sum(
+            project_key,
+            new_amount.saturating_sub(value),
+            new_amount,
+        );
+        if is_new_contributor {
+            ProjectContributorCount::<T>::mutate(project_key, |count| {
+                *count = count.saturating_add(1)
+            });
+        }
         project.raised_funds = project.raised_funds.saturating_add(value);
 
         // Update storage item to include the new contributions.
@@ -293,6 +426,100 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
+    /// Return some or all of a contribution to the contributor while the round is open.
+    /// Whitelist caps are checked against `ProjectContributions` so the freed amount can be
+    /// contributed again later in the round.
+    pub fn do_withdraw_contribution(
+        who: T::AccountId,
+        round_key: RoundKey,
+        project_key: ProjectKey,
+        value: BalanceOf<T>,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(value > (0_u32).into(), Error::<T>::InvalidParam);
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let round = Self::rounds(round_key).ok_or(Error::<T>::KeyNotFound)?;
+        ensure!(
+            round.round_type == RoundType::ContributionRound,
+            Error::<T>::InvalidRoundType
+        );
+        ensure!(!round.is_canceled, Error::<T>::RoundCanceled);
+        ensure!(round.start <= now, Error::<T>::StartBlockNumberInvalid);
+        ensure!(round.end > now, Error::<T>::EndBlockNumberInvalid);
+        ensure!(
+            round.project_keys.contains(&project_key),
+            Error::<T>::ProjectNotInRound
+        );
+
+        let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(!project.cancelled, Error::<T>::ProjectCancelled);
+        ensure!(
+            !project.funding_threshold_met,
+            Error::<T>::FundingThresholdMet
+        );
+
+        let contributed = Self::ensure_contributor_of(project_key, &who)?;
+        ensure!(
+            value <= contributed,
+            Error::<T>::WithdrawalExceedsContribution
+        );
+        // A partial withdrawal must not leave a contribution the project would not have accepted.
+        let remaining = contributed.saturating_sub(value);
+        let minimum = Self::contribution_limits(project_key)
+            .map_or(Zero::zero(), |limits| limits.min_contribution)
+            .max(T::MinimumContribution::get(&project.currency_id));
+        ensure!(
+            remaining.is_zero() || remaining >= minimum,
+            Error::<T>::ContributionBelowMinimum
+        );
+
+        T::MultiCurrency::transfer(
+            project.currency_id,
+            &Self::project_account_id(project_key),
+            &who,
+            value,
+        )?;
+        Self::return_to_remote_contributor(&who, value, project.currency_id);
+
+        Self::update_contribution_root_sum(project_key, contributed, remaining);
+        if remaining == (0_u32).into() {
+            T::ContributionReceipts::burn_receipt(project_key, &who)?;
+            ProjectContributions::<T>::remove(project_key, &who);
+            ProjectContributorCount::<T>::mutate(project_key, |count| {
+                *count = count.saturating_sub(1)
+            });
+        } else {
+            T::ContributionReceipts::issue_receipt(
+                project_key,
+                &who,
+                remaining,
+                project.currency_id,
+                now,
+            )?;
+            ProjectContributions::<T>::insert(
+                project_key,
+                who.clone(),
+                Contribution {
+                    value: remaining,
+                    timestamp: <pallet_timestamp::Pallet<T>>::get(),
+                },
+            );
+        }
+        project.raised_funds = project.raised_funds.saturating_sub(value);
+        let currency_id = project.currency_id;
+        <Projects<T>>::insert(project_key, project);
+
+        Self::deposit_event(Event::ContributionWithdrawn(
+            who,
+            project_key,
+            value,
+            currency_id,
+            now,
+        ));
+
+        Ok(().into())
+    }
+
     pub fn do_approve(
         project_key: ProjectKey,
         round_key: RoundKey,
@@ -306,14 +533,18 @@ impl<T: Config> Pallet<T> {
         ensure!(!round.is_canceled, Error::<T>::RoundCanceled);
         let now = <frame_system::Pallet<T>>::block_number();
         let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
         let total_contribution_amount: BalanceOf<T> = project.raised_funds;
 
         let funds_matched = total_contribution_amount >= project.required_funds;
         if !funds_matched {
             // If the funds have not been matched then check if the round is over
-            ensure!(round.end < now, Error::<T>::RoundNotEnded);
-            // TODO: PR for this exists.
-            // Once the round ends, check for the funding threshold met. (set threshold for 75%)
+            ensure!(round.end <= now, Error::<T>::RoundNotEnded);
+            // Once the round ends, a partially funded project can proceed if it has met its funding threshold.
+            ensure!(
+                total_contribution_amount >= Self::funding_threshold_amount(&project),
+                Error::<T>::FundingThresholdNotMet
+            );
         }
         project.funding_threshold_met = true;
         // Warning: This will allow the withdrawal of funds, approve is a governance action so should not be a problem.
@@ -343,6 +574,7 @@ impl<T: Config> Pallet<T> {
                     milestone_key,
                     now,
                 ));
+                Self::start_payout_vesting(project_key, milestone_key);
 
                 let mut milestone = project
                     .milestones
@@ -358,20 +590,48 @@ impl<T: Config> Pallet<T> {
     }
 
     // Take an approved project and submit an associated milestone.
+    // A milestone which has been rejected can be submitted again until it runs out of attempts.
     pub fn new_milestone_submission(
         who: T::AccountId,
         project_key: ProjectKey,
         milestone_key: MilestoneKey,
+        evidence_hash: H256,
     ) -> DispatchResultWithPostInfo {
         let now = <frame_system::Pallet<T>>::block_number();
         let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
 
-        // Ensure that only the initiator has submitted and the project has been approved.
-        ensure!(project.initiator == who, Error::<T>::UserIsNotInitiator);
+        // Ensure that only the initiator or a submitter has submitted and the project has been approved.
+        ensure!(
+            Self::has_team_role(project_key, &project, &who, TeamRole::Submitter),
+            Error::<T>::UserIsNotInitiator
+        );
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
         ensure!(
             project.funding_threshold_met,
             Error::<T>::OnlyApprovedProjectsCanSubmitMilestones
         );
+        ensure!(
+            project.milestones.contains_key(&milestone_key),
+            Error::<T>::MilestoneDoesNotExist
+        );
+
+        let vote_lookup_key = (project_key, milestone_key);
+        ensure!(
+            !MilestoneVotingDeadlines::<T>::contains_key(vote_lookup_key),
+            Error::<T>::MilestoneVotingWindowOpen
+        );
+        ensure!(
+            !Disputes::<T>::contains_key(project_key, milestone_key),
+            Error::<T>::MilestoneDisputed
+        );
+
+        let previous_submission = Self::milestone_submissions(vote_lookup_key);
+        if let Some(submission) = &previous_submission {
+            ensure!(
+                !submission.failed,
+                Error::<T>::MilestoneSubmissionAttemptsExhausted
+            );
+        }
 
         let end = now + <T as Config>::MilestoneVotingWindow::get();
 
@@ -379,14 +639,42 @@ impl<T: Config> Pallet<T> {
             .checked_add(1)
             .ok_or(Error::<T>::Overflow)?;
 
-        let round = RoundOf::<T>::new(now, end, vec![project_key], RoundType::VotingRound);
+        let project_keys = vec![project_key]
+            .try_into()
+            .map_err(|_| Error::<T>::ParamLimitExceed)?;
+        let round = RoundOf::<T>::new(now, end, project_keys, RoundType::VotingRound);
+
+        Self::schedule_round_expiry(
+            end,
+            ExpiringRound::MilestoneVote(round_key, project_key, milestone_key),
+        )?;
+
+        let attempts = previous_submission
+            .as_ref()
+            .map_or(0, |submission| submission.attempts)
+            .saturating_add(1);
+        let submission = MilestoneSubmission {
+            evidence_hash,
+            round_key,
+            submitted_at: now,
+            attempts,
+            last_rejection: previous_submission.and_then(|submission| submission.last_rejection),
+            failed: false,
+        };
 
         let vote = Vote::default();
-        let vote_lookup_key = (project_key, milestone_key);
         <MilestoneVotes<T>>::insert(vote_lookup_key, vote);
-        Self::deposit_event(Event::MilestoneSubmitted(who, project_key, milestone_key));
+        <MilestoneVotingDeadlines<T>>::insert(vote_lookup_key, end);
+        <MilestoneSubmissions<T>>::insert(vote_lookup_key, submission);
+        Self::deposit_event(Event::MilestoneSubmitted(
+            who,
+            project_key,
+            milestone_key,
+            evidence_hash,
+        ));
         // Add project round to list
         <Rounds<T>>::insert(round_key, Some(round));
+        ProjectRounds::<T>::insert(project_key, round_key, ());
         RoundCount::<T>::put(round_key);
         Self::deposit_event(Event::VotingRoundCreated(round_key, vec![project_key]));
         Ok(().into())
@@ -405,6 +693,7 @@ impl<T: Config> Pallet<T> {
 
         // round list must be not none
         let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
         let round = Self::rounds(round_key).ok_or(Error::<T>::KeyNotFound)?;
         ensure!(
             round.round_type == RoundType::VotingRound,
@@ -421,39 +710,55 @@ impl<T: Config> Pallet<T> {
         );
 
         ensure!(
-            project.contributions.contains_key(&who),
-            Error::<T>::OnlyContributorsCanVote
+            Self::milestone_voting_deadlines((project_key, milestone_key))
+                .map_or(false, |deadline| deadline > now),
+            Error::<T>::MilestoneVotingWindowClosed
         );
-        let contribution_amount = Self::ensure_contributor_of(&project, &who)?;
-        let vote_lookup_key = (who.clone(), project_key, milestone_key, round_key);
 
-        let vote_exists = UserVotes::<T>::contains_key(&vote_lookup_key);
-        ensure!(!vote_exists, Error::<T>::VoteAlreadyExists);
+        let delegators = Delegators::<T>::iter_key_prefix((project_key, &who)).collect::<Vec<_>>();
+        ensure!(
+            ProjectContributions::<T>::contains_key(project_key, &who) || !delegators.is_empty(),
+            Error::<T>::OnlyContributorsCanVote
+        );
 
-        <UserVotes<T>>::insert(vote_lookup_key, approve_milestone);
+        let delegator_count = delegators.len() as u32;
 
-        let existing_milestone_vote =
+        let mut updated_vote =
             Self::milestone_votes((project_key, milestone_key)).ok_or(Error::<T>::KeyNotFound)?;
-        let mut updated_vote = Vote::default();
+        let previous_vote = UserVotes::<T>::get((&who, project_key, milestone_key, round_key));
+        ensure!(
+            previous_vote.is_none() || !updated_vote.is_approved,
+            Error::<T>::MilestoneAlreadyApproved
+        );
+
+        // Delegates without a contribution of their own still record their vote so their delegators can follow it.
+        let mut vote_counted = Self::tally_own_vote(
+            &mut updated_vote,
+            (who.clone(), project_key, milestone_key, round_key),
+            approve_milestone,
+        );
+        vote_counted |= Self::tally_delegated_votes(
+            &mut updated_vote,
+            project_key,
+            delegators,
+            milestone_key,
+            round_key,
+            approve_milestone,
+        );
+        ensure!(vote_counted, Error::<T>::VoteAlreadyExists);
+
+        Self::deposit_event(Event::VoteComplete(
+            who,
+            project_key,
+            milestone_key,
+            approve_milestone,
+            now,
+        ));
+
         if approve_milestone {
-            updated_vote = Vote {
-                yay: existing_milestone_vote
-                    .yay
-                    .saturating_add(contribution_amount),
-                nay: existing_milestone_vote.nay,
-                is_approved: existing_milestone_vote.is_approved,
-            };
-            Self::deposit_event(Event::VoteComplete(
-                who,
-                project_key,
-                milestone_key,
-                approve_milestone,
-                now,
-            ));
             //once the voting is complete check if the milestone is eligible for auto approval
             //Getting the total threshold required for the milestone to be approved based on the raised funds
-            let funding_threshold: BalanceOf<T> = project
-                .raised_funds
+            let funding_threshold: BalanceOf<T> = Self::contributed_funds(project_key, &project)
                 .saturating_mul(T::PercentRequiredForVoteToPass::get().into())
                 / 100u32.into();
 
@@ -464,40 +769,91 @@ impl<T: Config> Pallet<T> {
             //if the yay votes are both greater than the nay votes and the funding threshold then the milestone is approved
             if updated_vote.yay >= funding_threshold {
                 milestone.is_approved = true;
-                updated_vote = Vote {
-                    yay: updated_vote.yay,
-                    nay: updated_vote.nay,
-                    is_approved: true,
-                };
-                let now = <frame_system::Pallet<T>>::block_number();
+                updated_vote.is_approved = true;
                 Self::deposit_event(Event::MilestoneApproved(
                     project.initiator.clone(),
                     project_key,
                     milestone_key,
                     now,
                 ));
+                Self::start_payout_vesting(project_key, milestone_key);
                 <Projects<T>>::insert(project_key, &project);
             }
-        } else {
-            updated_vote = Vote {
-                yay: existing_milestone_vote.yay,
-                nay: existing_milestone_vote
-                    .nay
-                    .saturating_add(contribution_amount),
-                is_approved: existing_milestone_vote.is_approved,
-            };
-            Self::deposit_event(Event::VoteComplete(
-                who,
-                project_key,
-                milestone_key,
-                approve_milestone,
-                now,
-            ));
         }
         <Rounds<T>>::insert(round_key, Some(round));
         <MilestoneVotes<T>>::insert((project_key, milestone_key), &updated_vote);
 
-        Ok(().into())
+        Ok(Some(<T as Config>::WeightInfo::vote_on_milestone(
+            delegator_count,
+        ))
+        .into())
+    }
+
+    /// Count a vote towards a tally, taking back the voter's previous vote if they are changing sides.
+    /// Returns false if the voter has already voted this way.
+    fn tally_vote(
+        tally: &mut Vote<BalanceOf<T>>,
+        vote_lookup_key: (T::AccountId, ProjectKey, MilestoneKey, RoundKey),
+        is_yay: bool,
+        weight: BalanceOf<T>,
+    ) -> bool {
+        let previous_vote = UserVotes::<T>::get(&vote_lookup_key);
+        if previous_vote == Some(is_yay) {
+            return false;
+        }
+        if is_yay {
+            if previous_vote.is_some() {
+                tally.nay = tally.nay.saturating_sub(weight);
+            }
+            tally.yay = tally.yay.saturating_add(weight);
+        } else {
+            if previous_vote.is_some() {
+                tally.yay = tally.yay.saturating_sub(weight);
+            }
+            tally.nay = tally.nay.saturating_add(weight);
+        }
+        UserVotes::<T>::insert(vote_lookup_key, is_yay);
+        true
+    }
+
+    /// Count a vote cast by the voter, weighted by their own contribution.
+    /// A vote their delegate cast for them becomes their own, so it is counted even if on the same side.
+    fn tally_own_vote(
+        tally: &mut Vote<BalanceOf<T>>,
+        vote_lookup_key: (T::AccountId, ProjectKey, MilestoneKey, RoundKey),
+        is_yay: bool,
+    ) -> bool {
+        let contribution = ProjectContributions::<T>::get(vote_lookup_key.1, &vote_lookup_key.0)
+            .map_or_else(Zero::zero, |contribution| contribution.value);
+        let was_delegated = DelegatedVotes::<T>::take(&vote_lookup_key).is_some();
+        Self::tally_vote(tally, vote_lookup_key, is_yay, contribution) || was_delegated
+    }
+
+    /// Cast a delegate's vote for each of their delegators, weighted by the delegator's contribution.
+    /// Delegators who have voted themselves keep their own vote.
+    fn tally_delegated_votes(
+        tally: &mut Vote<BalanceOf<T>>,
+        project_key: ProjectKey,
+        delegators: Vec<T::AccountId>,
+        milestone_key: MilestoneKey,
+        round_key: RoundKey,
+        is_yay: bool,
+    ) -> bool {
+        let mut vote_counted = false;
+        for delegator in delegators {
+            if let Some(contribution) = ProjectContributions::<T>::get(project_key, &delegator) {
+                let vote_lookup_key = (delegator, project_key, milestone_key, round_key);
+                if UserVotes::<T>::contains_key(&vote_lookup_key)
+                    && !DelegatedVotes::<T>::contains_key(&vote_lookup_key)
+                {
+                    continue;
+                }
+                DelegatedVotes::<T>::insert(&vote_lookup_key, ());
+                vote_counted |=
+                    Self::tally_vote(tally, vote_lookup_key, is_yay, contribution.value);
+            }
+        }
+        vote_counted
     }
 
     pub fn do_finalise_milestone_voting(
@@ -507,7 +863,7 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResultWithPostInfo {
         let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
         ensure!(
-            project.initiator == who,
+            Self::has_team_role(project_key, &project, &who, TeamRole::Submitter),
             Error::<T>::OnlyInitiatorOrAdminCanApproveMilestone
         );
 
@@ -528,8 +884,7 @@ impl<T: Config> Pallet<T> {
         let vote = Self::milestone_votes(vote_lookup_key).ok_or(Error::<T>::KeyNotFound)?;
 
         // let the 100 x threshold required = total_votes * majority required
-        let threshold_votes: BalanceOf<T> = project
-            .raised_funds
+        let threshold_votes: BalanceOf<T> = Self::contributed_funds(project_key, &project)
             .saturating_mul(T::PercentRequiredForVoteToPass::get().into());
         let percent_multiple: BalanceOf<T> = 100u32.into();
 
@@ -537,6 +892,7 @@ impl<T: Config> Pallet<T> {
             percent_multiple.saturating_mul(vote.yay + vote.nay) >= threshold_votes,
             Error::<T>::MilestoneVotingNotComplete
         );
+        let now = <frame_system::Pallet<T>>::block_number();
         if vote.yay > vote.nay {
             milestone.is_approved = true;
             let updated_vote = Vote {
@@ -544,16 +900,24 @@ impl<T: Config> Pallet<T> {
                 nay: vote.nay,
                 is_approved: true,
             };
-            let now = <frame_system::Pallet<T>>::block_number();
             Self::deposit_event(Event::MilestoneApproved(
                 project.initiator.clone(),
                 project_key,
                 milestone_key,
                 now,
             ));
+            Self::start_payout_vesting(project_key, milestone_key);
             <MilestoneVotes<T>>::insert(vote_lookup_key, updated_vote);
+        } else {
+            // A rejection uses up one of the initiator's attempts, so contributors keep the
+            // whole window to change the outcome.
+            ensure!(
+                Self::milestone_voting_deadlines(vote_lookup_key)
+                    .map_or(true, |deadline| deadline <= now),
+                Error::<T>::MilestoneVotingWindowOpen
+            );
+            Self::reject_milestone(project_key, milestone_key, vote);
         }
-        //TODO: Case for equal votes?
 
         project.milestones.insert(milestone_key, milestone);
         <Projects<T>>::insert(project_key, project);
@@ -567,60 +931,41 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResultWithPostInfo {
         let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
 
-        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
-        ensure!(who == project.initiator, Error::<T>::InvalidAccount);
-        //ensure that the project is approved_for_funding?
-
-        let unlocked_funds: BalanceOf<T> =
-            project
-                .milestones
-                .iter()
-                .fold(Default::default(), |acc, ms| {
-                    if ms.1.is_approved {
-                        let per_milestone = project
-                            .raised_funds
-                            .saturating_mul(ms.1.percentage_to_unlock.into())
-                            / MAX_PERCENTAGE.into();
-                        acc.saturating_add(per_milestone)
-                    } else {
-                        acc
-                    }
-                });
+        ensure!(!project.cancelled, Error::<T>::ProjectCancelled);
+        ensure!(
+            Self::has_team_role(project_key, &project, &who, TeamRole::Treasurer),
+            Error::<T>::InvalidAccount
+        );
+        ensure!(
+            project.funding_threshold_met,
+            Error::<T>::FundingThresholdNotMet
+        );
+        // Escalated disputes hold up all payouts, the funds of a milestone whose
+        // dispute is still waiting for backers are held back until it expires.
+        ensure!(
+            Disputes::<T>::iter_prefix_values(project_key)
+                .all(|dispute| dispute.status == DisputeStatus::Backing),
+            Error::<T>::MilestoneDisputed
+        );
 
-        let withdrawable: BalanceOf<T> = unlocked_funds.saturating_sub(project.withdrawn_funds);
+        let withdrawable: BalanceOf<T> = Self::unlocked_funds(project_key, &project)
+            .saturating_sub(Self::disputed_funds(project_key, &project));
 
         ensure!(
             withdrawable > (0_u32).into(),
             Error::<T>::NoAvailableFundsToWithdraw
         );
 
-        /// HB SBP Review:
-        ///
-        /// This is a good example about how sp_arithmetic can be used to manage percentages in a safe way.
-        ///
-        /// >
-        let fee = withdrawable.saturating_mul(<T as Config>::ImbueFee::get().into())
-            / MAX_PERCENTAGE.into();
-        let withdrawn = withdrawable.saturating_sub(fee);
-
-        let project_account = Self::project_account_id(project_key);
-        let pallet_account = Self::account_id();
-
-        // Take the fee
-        T::MultiCurrency::transfer(project.currency_id, &project_account, &pallet_account, fee)?;
-
-        T::MultiCurrency::transfer(
-            project.currency_id,
-            &project_account,
-            &project.initiator,
-            withdrawn,
-        )?;
+        let withdrawn = Self::pay_out_funds(project_key, &project, withdrawable)?;
 
         Projects::<T>::mutate_exists(project_key, |project| -> DispatchResult {
             if let Some(p) = project {
                 p.withdrawn_funds = p.withdrawn_funds.saturating_add(withdrawable);
                 if p.withdrawn_funds == p.raised_funds {
                     Self::reinstate_storage_deposit(&p.initiator)?;
+                    Self::return_initiator_bond(project_key, p);
+                    MatchedFunds::<T>::remove(project_key);
+                    Self::remove_project_records(project_key);
                     *project = None;
                 }
             }
@@ -637,108 +982,563 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
-    /// Appends a list of refunds to the queue to be used by the hooks.
-    // DEPRICATED, PLS REMOOVE.
-    pub fn add_refunds_to_queue_depricated(project_key: ProjectKey) -> DispatchResultWithPostInfo {
-        let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
-
-        //getting the locked milestone percentage - these are also milestones that have not been approved
-        let mut refunded_funds: BalanceOf<T> = 0_u32.into();
-        let mut locked_milestone_percentage: u32 = 0;
-        for (_milestone_key, milestone) in project.milestones.clone() {
-            if !milestone.is_approved {
-                locked_milestone_percentage =
-                    locked_milestone_percentage.saturating_add(milestone.percentage_to_unlock);
-            }
-        }
-
-        let mut current_refunds = RefundQueue::<T>::get();
-
-        // TODO: How can we refund all contributions without looping?
-        for (who, contribution) in project.contributions.iter() {
-            let project_account_id = Self::project_account_id(project_key);
-
-            /// <HB SBP Review:
-            ///
-            /// Unsafe operation. Please use checked_mul or saturated_mul instead.
-            /// And for divisions i always try to use cheched_div just to be sure.
-            /// >
-            let refund_amount: BalanceOf<T> =
-                ((contribution).value * locked_milestone_percentage.into()) / MAX_PERCENTAGE.into();
+    /// Pay out funds released by a project's milestones, taking the fee and paying the team their shares
+    /// and the rest to the initiator. Returns the amount paid out after the fee.
+    fn pay_out_funds(
+        project_key: ProjectKey,
+        project: &Project<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        /// HB SBP Review:
+        ///
+        /// This is a good example about how sp_arithmetic can be used to manage percentages in a safe way.
+        ///
+        /// >
+        let fee_rate =
+            Self::fee_rates(project.funding_type).unwrap_or_else(<T as Config>::ImbueFee::get);
+        let fee = amount.saturating_mul(fee_rate.into()) / MAX_PERCENTAGE.into();
+        let withdrawn = amount.saturating_sub(fee);
 
-            current_refunds.push((
-                who.clone(),
-                project_account_id.clone(),
-                refund_amount,
+        let project_account = Self::project_account_id(project_key);
+
+        // Take the fee
+        Self::distribute_fee(project_key, project.currency_id, &project_account, fee)?;
+
+        // Pay the team their shares and the rest to the initiator.
+        let mut initiator_payout = withdrawn;
+        for member in Self::project_teams(project_key).iter() {
+            let payout = Perbill::from_percent(member.payout_share.into()).mul_floor(withdrawn);
+            T::MultiCurrency::transfer(
                 project.currency_id,
-            ));
-            //
-            refunded_funds = refunded_funds.saturating_add(refund_amount);
+                &project_account,
+                &member.account,
+                payout,
+            )?;
+            initiator_payout = initiator_payout.saturating_sub(payout);
+        }
+        T::MultiCurrency::transfer(
+            project.currency_id,
+            &project_account,
+            &project.initiator,
+            initiator_payout,
+        )?;
+        Ok(withdrawn)
+    }
+
+    /// Refund the caller's contribution to a cancelled project.
+    /// Only the share locked in unapproved milestones is returned, once every contributor
+    /// has claimed the project is removed and the storage deposit returned to the initiator.
+    #[transactional]
+    pub fn do_claim_refund(
+        who: T::AccountId,
+        project_key: ProjectKey,
+    ) -> DispatchResultWithPostInfo {
+        let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(project.cancelled, Error::<T>::ProjectNotRefundable);
+        let contribution = ProjectContributions::<T>::take(project_key, &who)
+            .ok_or(Error::<T>::NothingToRefund)?;
+        ProjectContributorCount::<T>::mutate(project_key, |count| *count = count.saturating_sub(1));
+
+        let locked_share = Self::locked_share(project_key, &project);
+        let refund_amount: BalanceOf<T> = locked_share.mul_floor(contribution.value);
+
+        let project_account_id = Self::project_account_id(project_key);
+        match project.funding_type {
+            FundingType::Brief | FundingType::Proposal => {
+                <T as Config>::MultiCurrency::transfer(
+                    project.currency_id,
+                    &project_account_id,
+                    &who,
+                    refund_amount,
+                )?;
+                Self::return_to_remote_contributor(&who, refund_amount, project.currency_id);
+            }
+            FundingType::Treasury(_) => {
+                <T as Config>::RefundHandler::send_refund_message_to_treasury(
+                    project_account_id,
+                    refund_amount,
+                    project.currency_id,
+                    project.funding_type,
+                )?;
+            }
         }
+        T::ContributionReceipts::burn_receipt(project_key, &who)?;
 
-        // Updated new project status to cancelled
-        project.cancelled = true;
-        <Projects<T>>::insert(project_key, project);
-        RefundQueue::<T>::put(current_refunds);
+        let currency_id = project.currency_id;
+        if ProjectContributions::<T>::iter_key_prefix(project_key)
+            .next()
+            .is_none()
+        {
+            // The bond was returned when the project was cancelled.
+            Self::return_locked_matched_funds(project_key, currency_id, locked_share)?;
+            Self::reinstate_storage_deposit(&project.initiator)?;
+            Self::remove_project_records(project_key);
+            Projects::<T>::remove(project_key);
+        } else {
+            Projects::<T>::insert(project_key, project);
+        }
 
-        Self::deposit_event(Event::ProjectFundsAddedToRefundQueue(
+        Self::deposit_event(Event::ContributionRefunded(
+            who,
             project_key,
-            refunded_funds,
+            refund_amount,
+            currency_id,
         ));
         Ok(().into())
     }
 
-    /// Using the parameters provided (which should be from the refund queue),
-    /// Process a refund.
-    /// Used in hooks so cannot panic.
-    /// DEPRICATED
-    pub fn refund_item_in_queue_depricated(
-        from: &T::AccountId,
-        to: &T::AccountId,
+    /// Move funds into the matching pool of an active contribution round.
+    pub fn do_fund_matching_pool(
+        who: T::AccountId,
+        round_key: RoundKey,
+        currency_id: CurrencyId,
         amount: BalanceOf<T>,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(amount > Zero::zero(), Error::<T>::InvalidParam);
+        let now = <frame_system::Pallet<T>>::block_number();
+        let round = Self::rounds(round_key).ok_or(Error::<T>::KeyNotFound)?;
+        ensure!(
+            round.round_type == RoundType::ContributionRound,
+            Error::<T>::InvalidRoundType
+        );
+        ensure!(!round.is_canceled, Error::<T>::RoundCanceled);
+        // Funding a round that has not started would allow it to be cancelled with the pool inside.
+        ensure!(round.start <= now, Error::<T>::StartBlockNumberInvalid);
+        ensure!(round.end > now, Error::<T>::EndBlockNumberInvalid);
+
+        MatchingPools::<T>::try_mutate(round_key, |maybe_pool| -> DispatchResult {
+            match maybe_pool {
+                Some(pool) => {
+                    ensure!(
+                        pool.currency_id == currency_id,
+                        Error::<T>::MatchingPoolCurrencyMismatch
+                    );
+                    pool.amount = pool.amount.saturating_add(amount);
+                }
+                None => {
+                    *maybe_pool = Some(MatchingPool {
+                        currency_id,
+                        amount,
+                    })
+                }
+            }
+            T::MultiCurrency::transfer(
+                currency_id,
+                &who,
+                &Self::matching_pool_account_id(round_key),
+                amount,
+            )
+        })?;
+
+        Self::deposit_event(Event::MatchingPoolFunded(
+            round_key,
+            who,
+            amount,
+            currency_id,
+        ));
+        Ok(().into())
+    }
+
+    /// The funds unlocked by a project's approved milestones which have not yet been withdrawn.
+    /// The funds of a milestone are unlocked as they vest if the project streams its payouts.
+    fn unlocked_funds(project_key: ProjectKey, project: &Project<T>) -> BalanceOf<T> {
+        let unlocked_funds: BalanceOf<T> =
+            project
+                .milestones
+                .iter()
+                .fold(Default::default(), |acc, ms| {
+                    if ms.1.is_approved {
+                        let per_milestone = project
+                            .raised_funds
+                            .saturating_mul(ms.1.percentage_to_unlock.into())
+                            / MAX_PERCENTAGE.into();
+                        let vested = Self::vested_share(project_key, *ms.0);
+                        acc.saturating_add(vested.mul_floor(per_milestone))
+                    } else {
+                        acc
+                    }
+                });
+
+        unlocked_funds.saturating_sub(project.withdrawn_funds)
+    }
+
+    /// The funds of the approved milestones whose disputes are waiting for backers.
+    /// They stay in the project account so they are still there if the dispute is escalated.
+    fn disputed_funds(project_key: ProjectKey, project: &Project<T>) -> BalanceOf<T> {
+        Disputes::<T>::iter_prefix(project_key)
+            .filter(|(_, dispute)| {
+                dispute.raised_by == DisputeRaiser::Contributors
+                    && dispute.status == DisputeStatus::Backing
+            })
+            .filter_map(|(milestone_key, _)| project.milestones.get(&milestone_key))
+            .fold(Zero::zero(), |acc: BalanceOf<T>, milestone| {
+                acc.saturating_add(
+                    project
+                        .raised_funds
+                        .saturating_mul(milestone.percentage_to_unlock.into())
+                        / MAX_PERCENTAGE.into(),
+                )
+            })
+    }
+
+    /// The share of an approved milestone's funds which has vested.
+    /// Milestones of projects which do not stream their payouts vest in full on approval.
+    fn vested_share(project_key: ProjectKey, milestone_key: MilestoneKey) -> Perbill {
+        match (
+            Self::payout_vesting_periods(project_key),
+            Self::milestone_vesting_starts(project_key, milestone_key),
+        ) {
+            (Some(period), Some(start)) => {
+                let elapsed = <frame_system::Pallet<T>>::block_number().saturating_sub(start);
+                Perbill::from_rational(elapsed.min(period), period)
+            }
+            _ => Perbill::one(),
+        }
+    }
+
+    /// The share of a project's funds which is still locked, in unapproved milestones
+    /// or in approved milestones which have not yet vested.
+    fn locked_share(project_key: ProjectKey, project: &Project<T>) -> Perbill {
+        project
+            .milestones
+            .iter()
+            .fold(Perbill::zero(), |acc, (milestone_key, milestone)| {
+                let milestone_share = Perbill::from_percent(milestone.percentage_to_unlock);
+                let locked = if milestone.is_approved {
+                    milestone_share.saturating_mul(
+                        Perbill::one()
+                            .saturating_sub(Self::vested_share(project_key, *milestone_key)),
+                    )
+                } else {
+                    milestone_share
+                };
+                acc.saturating_add(locked)
+            })
+    }
+
+    /// Start streaming the funds of a milestone which has just been approved, if the project vests its payouts.
+    fn start_payout_vesting(project_key: ProjectKey, milestone_key: MilestoneKey) {
+        if PayoutVestingPeriods::<T>::contains_key(project_key)
+            && !MilestoneVestingStarts::<T>::contains_key(project_key, milestone_key)
+        {
+            MilestoneVestingStarts::<T>::insert(
+                project_key,
+                milestone_key,
+                <frame_system::Pallet<T>>::block_number(),
+            );
+        }
+    }
+
+    /// The amount the initiator of a project can currently withdraw, before the Imbue fee is taken.
+    /// Used by the runtime api.
+    pub fn withdrawable_funds(project_key: ProjectKey) -> BalanceOf<T> {
+        match Self::projects(project_key) {
+            Some(project) if !project.cancelled && project.funding_threshold_met => {
+                Self::unlocked_funds(project_key, &project)
+                    .saturating_sub(Self::disputed_funds(project_key, &project))
+            }
+            _ => Zero::zero(),
+        }
+    }
+
+    /// The vote tallies of each of a project's milestones which have been submitted.
+    /// Used by the runtime api.
+    pub fn project_milestone_votes(
+        project_key: ProjectKey,
+    ) -> Vec<(MilestoneKey, Vote<BalanceOf<T>>)> {
+        Self::projects(project_key)
+            .map(|project| {
+                project
+                    .milestones
+                    .keys()
+                    .filter_map(|milestone_key| {
+                        Self::milestone_votes((project_key, milestone_key))
+                            .map(|vote| (*milestone_key, vote))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The keys of the rounds a project belongs to which have not been cancelled or ended.
+    /// Used by the runtime api.
+    pub fn project_active_rounds(project_key: ProjectKey) -> Vec<RoundKey> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let mut round_keys = ProjectRounds::<T>::iter_key_prefix(project_key)
+            .filter(|round_key| {
+                Self::rounds(round_key).map_or(false, |round| !round.is_canceled && round.end > now)
+            })
+            .collect::<Vec<RoundKey>>();
+        round_keys.sort();
+        round_keys
+    }
+
+    /// The contribution an account has made to a project.
+    /// Used by the runtime api.
+    pub fn project_contribution(
+        project_key: ProjectKey,
+        account_id: T::AccountId,
+    ) -> Option<Contribution<BalanceOf<T>, TimestampOf<T>>> {
+        Self::project_contributions(project_key, account_id)
+    }
+
+    /// The share of a round's matching pool each project would receive if the round ended now.
+    /// Used by the runtime api to preview the matching.
+    pub fn matching_estimate(round_key: RoundKey) -> Vec<(ProjectKey, BalanceOf<T>)> {
+        match (Self::rounds(round_key), Self::matching_pools(round_key)) {
+            (Some(round), Some(pool)) => Self::calculate_matching(&round, &pool),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Replace the square root of a contributor's old contribution with that of their new one
+    /// in the sum used for matching.
+    fn update_contribution_root_sum(
+        project_key: ProjectKey,
+        old_value: BalanceOf<T>,
+        new_value: BalanceOf<T>,
+    ) {
+        ContributionRootSums::<T>::mutate(project_key, |sum| {
+            *sum = sum
+                .saturating_sub(old_value.integer_sqrt())
+                .saturating_add(new_value.integer_sqrt())
+        });
+    }
+
+    /// Split a matching pool between the projects of a round using quadratic funding.
+    /// Each project is weighted by the square of the sum of the square roots of its contributions,
+    /// less the contributions themselves, so many small contributions attract more matching than
+    /// a few large ones. Cancelled projects and projects in another currency are not matched.
+    /// Only a fixed number of reads are made per project so this is bounded by the round size.
+    fn calculate_matching(
+        round: &RoundOf<T>,
+        pool: &MatchingPool<BalanceOf<T>>,
+    ) -> Vec<(ProjectKey, BalanceOf<T>)> {
+        let scores: Vec<(ProjectKey, BalanceOf<T>)> = round
+            .project_keys
+            .iter()
+            .filter_map(|project_key| {
+                let project = Projects::<T>::get(project_key)?;
+                if project.cancelled || project.currency_id != pool.currency_id {
+                    return None;
+                }
+                let sum_of_roots = ContributionRootSums::<T>::get(project_key);
+                let sum_of_contributions = Self::contributed_funds(*project_key, &project);
+                let score = sum_of_roots
+                    .saturating_mul(sum_of_roots)
+                    .saturating_sub(sum_of_contributions);
+                Some((*project_key, score))
+            })
+            .collect();
+
+        let total_score = scores
+            .iter()
+            .fold(BalanceOf::<T>::zero(), |acc, (_, score)| {
+                acc.saturating_add(*score)
+            });
+        if total_score.is_zero() {
+            return scores;
+        }
+
+        scores
+            .into_iter()
+            .map(|(project_key, score)| {
+                (
+                    project_key,
+                    Perbill::from_rational(score, total_score) * pool.amount,
+                )
+            })
+            .collect()
+    }
+
+    /// Pay out a round's matching pool to its projects once the round has ended.
+    /// Anything left over, such as rounding dust or a pool nobody could be matched against,
+    /// is sent to the treasury.
+    #[transactional]
+    fn distribute_matching_pool(round_key: RoundKey, round: &RoundOf<T>) -> DispatchResult {
+        let pool = MatchingPools::<T>::take(round_key).ok_or(Error::<T>::KeyNotFound)?;
+        let pool_account = Self::matching_pool_account_id(round_key);
+        let mut distributed: BalanceOf<T> = Zero::zero();
+
+        for (project_key, amount) in Self::calculate_matching(round, &pool) {
+            if amount.is_zero() {
+                continue;
+            }
+            T::MultiCurrency::transfer(
+                pool.currency_id,
+                &pool_account,
+                &Self::project_account_id(project_key),
+                amount,
+            )?;
+            Projects::<T>::try_mutate(project_key, |maybe_project| -> DispatchResult {
+                let project = maybe_project
+                    .as_mut()
+                    .ok_or(Error::<T>::ProjectDoesNotExist)?;
+                project.raised_funds = project.raised_funds.saturating_add(amount);
+                Ok(())
+            })?;
+            MatchedFunds::<T>::mutate(project_key, |matched| {
+                *matched = matched.saturating_add(amount)
+            });
+            distributed = distributed.saturating_add(amount);
+            Self::deposit_event(Event::MatchingFundsDistributed(
+                round_key,
+                project_key,
+                amount,
+            ));
+        }
+
+        let remainder = pool.amount.saturating_sub(distributed);
+        if !remainder.is_zero() {
+            let treasury_account =
+                <T as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)?;
+            T::MultiCurrency::transfer(
+                pool.currency_id,
+                &pool_account,
+                &treasury_account,
+                remainder,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the share of a project's matched funds which is still locked to the treasury.
+    /// Used when a project is refunded as those funds do not belong to any contributor.
+    fn return_locked_matched_funds(
+        project_key: ProjectKey,
         currency_id: CurrencyId,
-    ) -> bool {
-        let can_withraw: DispatchResult =
-            T::MultiCurrency::ensure_can_withdraw(currency_id, from, amount);
-        if can_withraw.is_ok() {
-            // this should pass now, but i will not return early
-            let _ = T::MultiCurrency::transfer(currency_id, from, to, amount);
-            true
-        } else {
-            false
+        locked_share: Perbill,
+    ) -> DispatchResult {
+        let matched = MatchedFunds::<T>::take(project_key);
+        let locked: BalanceOf<T> = locked_share.mul_floor(matched);
+        if !locked.is_zero() {
+            let treasury_account =
+                <T as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)?;
+            T::MultiCurrency::transfer(
+                currency_id,
+                &Self::project_account_id(project_key),
+                &treasury_account,
+                locked,
+            )?;
         }
+        Ok(())
     }
 
-    /// Split off an amount of refunds off the vector and place into refund storage.
-    /// Returns a boolean if a split off has succeeded.
-    /// Used in hooks so cannot panic.
-    /// DEPRICATED
-    pub fn split_off_refunds_depricated(refunds: &mut Refunds<T>, c: u32) -> bool {
-        // split_off panics when at > len:
-        // https://paritytech.github.io/substrate/master/sp_std/vec/struct.Vec.html#method.split_off
-        // If the length is zero do nothing
-        if c == 0 {
-            return false;
+    /// Share out the fee taken from a withdrawal between the treasury, burning and the collator pot.
+    /// Any remainder left by rounding goes to the collator pot.
+    fn distribute_fee(
+        project_key: ProjectKey,
+        currency_id: CurrencyId,
+        from: &T::AccountId,
+        fee: BalanceOf<T>,
+    ) -> DispatchResult {
+        let fee_split = Self::fee_split();
+        let to_treasury = fee_split.treasury.mul_floor(fee);
+        let burnt = fee_split.burn.mul_floor(fee);
+        let to_collators = fee.saturating_sub(to_treasury).saturating_sub(burnt);
+
+        if !to_treasury.is_zero() {
+            let treasury_account =
+                <T as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)?;
+            T::MultiCurrency::transfer(currency_id, from, &treasury_account, to_treasury)?;
+        }
+        if !burnt.is_zero() {
+            T::MultiCurrency::withdraw(currency_id, from, burnt)?;
+        }
+        if !to_collators.is_zero() {
+            T::MultiCurrency::transfer(currency_id, from, &T::CollatorPot::get(), to_collators)?;
         }
 
-        if c as usize <= refunds.len() {
-            // If its a legitimate operation, split off.
-            RefundQueue::<T>::put(refunds.split_off(c as usize));
-            true
-        } else {
-            // panic case we will place in an empty vec as the counter is wrong.
-            RefundQueue::<T>::kill();
-            false
+        Self::deposit_event(Event::FeeDistributed(
+            project_key,
+            currency_id,
+            to_treasury,
+            burnt,
+            to_collators,
+        ));
+        Ok(())
+    }
+
+    pub fn do_set_fee_split(fee_split: FeeSplit) -> DispatchResultWithPostInfo {
+        let total = fee_split
+            .treasury
+            .deconstruct()
+            .saturating_add(fee_split.burn.deconstruct())
+            .saturating_add(fee_split.collators.deconstruct());
+        ensure!(
+            total == Perbill::one().deconstruct(),
+            Error::<T>::InvalidFeeSplit
+        );
+        FeeSplits::<T>::put(&fee_split);
+        Self::deposit_event(Event::FeeSplitSet(fee_split));
+        Ok(().into())
+    }
+
+    pub fn do_set_fee_rate(
+        funding_type: FundingType,
+        fee_rate: Option<u8>,
+    ) -> DispatchResultWithPostInfo {
+        if let Some(rate) = fee_rate {
+            ensure!(u32::from(rate) < MAX_PERCENTAGE, Error::<T>::InvalidFeeRate);
         }
+        FeeRates::<T>::set(funding_type, fee_rate);
+        Self::deposit_event(Event::FeeRateSet(funding_type, fee_rate));
+        Ok(().into())
+    }
+
+    /// The amount a project must raise by the end of its funding round to proceed.
+    pub fn funding_threshold_amount(project: &Project<T>) -> BalanceOf<T> {
+        project
+            .required_funds
+            .saturating_mul(project.funding_threshold.into())
+            / MAX_PERCENTAGE.into()
+    }
+
+    /// Cancel a project which has not met its funding threshold by the end of its funding round
+    /// so that contributors can claim their refunds. Projects without contributions are removed.
+    pub(crate) fn fail_underfunded_project(
+        round_key: RoundKey,
+        project_key: ProjectKey,
+    ) -> DispatchResult {
+        Projects::<T>::mutate_exists(project_key, |maybe_project| -> DispatchResult {
+            if let Some(project) = maybe_project {
+                if project.funding_threshold_met
+                    || project.cancelled
+                    || project.raised_funds >= Self::funding_threshold_amount(project)
+                {
+                    return Ok(());
+                }
+                // The bond is returned here, a cancelled project is never reinstated.
+                // The storage deposit is held until the project is removed.
+                Self::return_initiator_bond(project_key, project);
+                if ProjectContributions::<T>::iter_key_prefix(project_key)
+                    .next()
+                    .is_none()
+                {
+                    Self::reinstate_storage_deposit(&project.initiator)?;
+                    *maybe_project = None;
+                } else {
+                    project.cancelled = true;
+                }
+                Self::deposit_event(Event::ProjectFundingFailed(round_key, project_key));
+            }
+            Ok(())
+        })
     }
 
     /// This function raises a vote of no confidence.
     /// This round can only be called once and there after can only be voted on.
     /// The person calling it must be a contributor.
-    pub fn raise_no_confidence_round(who: T::AccountId, project_key: ProjectKey) -> DispatchResult {
+    pub fn raise_no_confidence_round(
+        who: T::AccountId,
+        project_key: ProjectKey,
+    ) -> DispatchResultWithPostInfo {
         //ensure that who is a contributor or root
-        let project = Self::projects(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
-        let contribution = Self::ensure_contributor_of(&project, &who)?;
+        ensure!(
+            Projects::<T>::contains_key(project_key),
+            Error::<T>::ProjectDoesNotExist
+        );
+        let contribution = Self::ensure_contributor_of(project_key, &who)?;
 
         // Also ensure that a vote has not already been raised.
         ensure!(
@@ -747,68 +1547,83 @@ impl<T: Config> Pallet<T> {
         );
 
         // Create the accosiated vote struct, index can be used as an ensure on length has been called.
-        let vote = Vote {
+        let mut vote = Vote {
             yay: Default::default(),
             nay: contribution,
             // not using this so approved will be false.
             is_approved: false,
         };
         let now = frame_system::Pallet::<T>::block_number();
+        let end = now + T::NoConfidenceTimeLimit::get();
         // Create the accosiated round.
-        let round = RoundOf::<T>::new(
-            now,
-            now + T::NoConfidenceTimeLimit::get(),
-            vec![project_key],
-            RoundType::VoteOfNoConfidence,
-        );
+        let project_keys = vec![project_key]
+            .try_into()
+            .map_err(|_| Error::<T>::ParamLimitExceed)?;
+        let round = RoundOf::<T>::new(now, end, project_keys, RoundType::VoteOfNoConfidence);
 
         let round_key = RoundCount::<T>::get()
             .checked_add(1)
             .ok_or(Error::<T>::Overflow)?;
+        Self::schedule_round_expiry(end, ExpiringRound::NoConfidence(round_key, project_key))?;
+        // The raiser's delegators vote with them.
+        let delegators = Delegators::<T>::iter_key_prefix((project_key, &who)).collect::<Vec<_>>();
+        let delegator_count = delegators.len() as u32;
+        Self::tally_delegated_votes(&mut vote, project_key, delegators, 0, round_key, false);
         // Insert the new round and votes into storage and update the RoundCount and UserVotes.
         NoConfidenceVotes::<T>::insert(project_key, vote);
         Rounds::<T>::insert(round_key, Some(round));
+        ProjectRounds::<T>::insert(project_key, round_key, ());
         RoundCount::<T>::mutate(|c| *c = c.saturating_add(1u32));
-        UserVotes::<T>::insert((who, project_key, 0, round_key), true);
+        // The contributor raising the vote is against the project continuing.
+        UserVotes::<T>::insert((who, project_key, 0, round_key), false);
         Self::deposit_event(Event::NoConfidenceRoundCreated(round_key, project_key));
 
-        Ok(())
+        Ok(Some(<T as Config>::WeightInfo::raise_vote_of_no_confidence(
+            delegator_count,
+        ))
+        .into())
     }
 
     /// Allows a contributer to agree or disagree with a vote of no confidence.
-    /// Additional contributions after the vote is set are not counted and cannot be voted on again, todo?
+    /// A contributor who has already voted can change their vote while the round is open.
     pub fn add_vote_no_confidence(
         who: T::AccountId,
         round_key: RoundKey,
         project_key: ProjectKey,
         is_yay: bool,
-    ) -> DispatchResult {
+    ) -> DispatchResultWithPostInfo {
         let round = Self::rounds(round_key).ok_or(Error::<T>::KeyNotFound)?;
         ensure!(
             round.project_keys.contains(&project_key),
             Error::<T>::ProjectNotInRound
         );
-        let project = Self::projects(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
-        let contribution = Self::ensure_contributor_of(&project, &who)?;
-
-        let mut vote = NoConfidenceVotes::<T>::get(project_key).ok_or(Error::<T>::NoActiveRound)?;
         ensure!(
-            UserVotes::<T>::get((&who, project_key, 0, round_key)).is_none(),
-            Error::<T>::VoteAlreadyExists
+            Projects::<T>::contains_key(project_key),
+            Error::<T>::ProjectDoesNotExist
+        );
+        let delegators = Delegators::<T>::iter_key_prefix((project_key, &who)).collect::<Vec<_>>();
+        ensure!(
+            ProjectContributions::<T>::contains_key(project_key, &who) || !delegators.is_empty(),
+            Error::<T>::OnlyContributorsCanVote
         );
 
-        if is_yay {
-            vote.yay = vote.yay.saturating_add(contribution);
-        } else {
-            vote.nay = vote.nay.saturating_add(contribution);
-        }
+        let delegator_count = delegators.len() as u32;
+
+        let mut vote = NoConfidenceVotes::<T>::get(project_key).ok_or(Error::<T>::NoActiveRound)?;
+        let mut vote_counted =
+            Self::tally_own_vote(&mut vote, (who.clone(), project_key, 0, round_key), is_yay);
+        vote_counted |=
+            Self::tally_delegated_votes(&mut vote, project_key, delegators, 0, round_key, is_yay);
+        ensure!(vote_counted, Error::<T>::VoteAlreadyExists);
 
         NoConfidenceVotes::<T>::insert(project_key, vote);
-        UserVotes::<T>::insert((who, project_key, 0, round_key), true);
 
         Self::deposit_event(Event::NoConfidenceRoundVotedUpon(round_key, project_key));
 
-        Ok(())
+        Ok(Some(<T as Config>::WeightInfo::vote_on_no_confidence_round(
+            delegator_count,
+        ))
+        .into())
     }
 
     /// Called when a contributor wants to finalise a vote of no confidence.
@@ -820,6 +1635,21 @@ impl<T: Config> Pallet<T> {
         round_key: RoundKey,
         project_key: ProjectKey,
         majority_required: u8,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            Projects::<T>::contains_key(project_key),
+            Error::<T>::ProjectDoesNotExist
+        );
+        let _ = Self::ensure_contributor_of(project_key, &who)?;
+        Self::do_finalise_no_confidence_vote(round_key, project_key, majority_required)
+    }
+
+    /// Finalise a vote of no confidence without checking who is asking for it.
+    /// Used by both the extrinsic and the hooks once the round has expired.
+    pub(crate) fn do_finalise_no_confidence_vote(
+        round_key: RoundKey,
+        project_key: ProjectKey,
+        majority_required: u8,
     ) -> DispatchResultWithPostInfo {
         let mut round = Self::rounds(round_key).ok_or(Error::<T>::KeyNotFound)?;
         ensure!(
@@ -828,10 +1658,9 @@ impl<T: Config> Pallet<T> {
         );
         let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
 
-        let _ = Self::ensure_contributor_of(&project, &who)?;
         let vote = NoConfidenceVotes::<T>::get(project_key).ok_or(Error::<T>::NoActiveRound)?;
 
-        let total_contribute = project.raised_funds;
+        let total_contribute = Self::contributed_funds(project_key, &project);
 
         // 100 * Threshold =  (total_contribute * majority_required%)
         let threshold_votes: BalanceOf<T> =
@@ -845,53 +1674,11 @@ impl<T: Config> Pallet<T> {
             // TODO: Need a sane bound on contributors in a project.
             // TODO: the same thing but with milestones.
 
-            let locked_milestone_percentage = project.milestones.iter().fold(0, |acc, ms| {
-                if !ms.1.is_approved {
-                    acc.saturating_add(ms.1.percentage_to_unlock)
-                } else {
-                    acc
-                }
-            });
-
-            let project_account_id = Self::project_account_id(project_key);
-
-            match project.funding_type {
-                FundingType::Brief | FundingType::Proposal => {
-                    // Handle refunds on native chain, there is no need to deal with xcm here.
-                    // Todo: Batch call using pallet-utility?
-                    for (acc_id, contribution) in project.contributions.iter() {
-                        let refund_amount: BalanceOf<T> = contribution
-                            .value
-                            .saturating_mul(locked_milestone_percentage.into())
-                            / MAX_PERCENTAGE.into();
-                        <T as Config>::MultiCurrency::transfer(
-                            project.currency_id,
-                            &project_account_id,
-                            acc_id,
-                            refund_amount,
-                        )?;
-                    }
-                }
-                FundingType::Treasury(_) => {
-                    let mut refund_amount: BalanceOf<T> = Default::default();
-                    // Sum the contributions and send a single xcm.
-                    for (_acc_id, contribution) in project.contributions.iter() {
-                        let per_contributor = contribution
-                            .value
-                            .saturating_mul(locked_milestone_percentage.into())
-                            / MAX_PERCENTAGE.into();
-                        refund_amount = refund_amount.saturating_add(per_contributor);
-                    }
-                    <T as Config>::RefundHandler::send_refund_message_to_treasury(
-                        project_account_id,
-                        refund_amount,
-                        project.currency_id,
-                        project.funding_type,
-                    )?;
-                }
-            }
+            Self::refund_locked_contributions(project_key, &project)?;
+            Self::slash_initiator_bond(project_key, &project);
             // Remove the project and return the storage deposit
             Self::reinstate_storage_deposit(&project.initiator)?;
+            Self::remove_project_records(project_key);
             Projects::<T>::remove(project_key);
 
             Self::deposit_event(Event::NoConfidenceRoundFinalised(round_key, project_key));
@@ -901,31 +1688,1288 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
-    // Called to ensure that an account is is a contributor to a project.
-    fn ensure_contributor_of<'a>(
-        project: &'a Project<T::AccountId, BalanceOf<T>, T::BlockNumber, TimestampOf<T>>,
-        account_id: &'a T::AccountId,
-    ) -> Result<BalanceOf<T>, Error<T>> {
-        let contribution = project.contributions.get(account_id);
-        match contribution {
-            Some(c) => Ok(c.value),
-            _ => Err(Error::<T>::OnlyContributorsCanVote),
+    /// The local account of an account on a sibling parachain dispatching through XCM, and its location.
+    pub(crate) fn ensure_remote_account(
+        origin: T::RuntimeOrigin,
+    ) -> Result<(T::AccountId, MultiLocation), DispatchError> {
+        let location = T::XcmOrigin::ensure_origin(origin)?;
+        let who =
+            T::LocationToAccountId::convert(location.clone()).ok_or(Error::<T>::UnknownLocation)?;
+        Ok((who, location))
+    }
+
+    /// Send funds returned to the local account of a remote contributor back to the chain they contributed from.
+    /// A failed transfer leaves the funds with the local account rather than blocking the refund,
+    /// and is recorded so it can be sent again.
+    fn return_to_remote_contributor(
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+        currency_id: CurrencyId,
+    ) {
+        if let Some(location) = RemoteContributors::<T>::get(who) {
+            if <T as Config>::RefundHandler::send_refund_to_location(
+                who.clone(),
+                amount,
+                currency_id,
+                location,
+            )
+            .is_err()
+            {
+                UnsentRemoteRefunds::<T>::mutate(who, currency_id, |unsent| {
+                    *unsent = unsent.saturating_add(amount)
+                });
+                Self::deposit_event(Event::RemoteRefundNotSent(who.clone(), amount));
+            }
         }
     }
 
-    fn ensure_identity_is_decent(who: &T::AccountId) -> Result<(), Error<T>> {
-        let identity =
-            pallet_identity::Pallet::<T>::identity(who).ok_or(Error::<T>::IdentityNeeded)?;
+    pub fn do_retry_remote_refund(
+        who: T::AccountId,
+        currency_id: CurrencyId,
+    ) -> DispatchResultWithPostInfo {
+        let amount = UnsentRemoteRefunds::<T>::get(&who, currency_id);
+        ensure!(!amount.is_zero(), Error::<T>::NothingToRefund);
+        let location = RemoteContributors::<T>::get(&who).ok_or(Error::<T>::UnknownLocation)?;
 
-        if identity
-            .judgements
-            .iter()
-            .any(|j| j.1 == Judgement::Reasonable || j.1 == Judgement::KnownGood)
-        {
-            Ok(())
-        } else {
-            Err(Error::<T>::InvalidAccount)
+        <T as Config>::RefundHandler::send_refund_to_location(
+            who.clone(),
+            amount,
+            currency_id,
+            location,
+        )?;
+        UnsentRemoteRefunds::<T>::remove(&who, currency_id);
+
+        Self::deposit_event(Event::RemoteRefundSent(who, amount));
+        Ok(().into())
+    }
+
+    /// Refund each contributor their share of the funds locked in the unapproved milestones of a project,
+    /// and of the funds of approved milestones which have not yet vested.
+    /// Treasury funded projects are refunded to the treasury in a single message.
+    fn refund_locked_contributions(
+        project_key: ProjectKey,
+        project: &Project<T>,
+    ) -> DispatchResult {
+        let locked_share = Self::locked_share(project_key, project);
+
+        let project_account_id = Self::project_account_id(project_key);
+
+        match project.funding_type {
+            FundingType::Brief | FundingType::Proposal => {
+                // Handle refunds on native chain, there is no need to deal with xcm here.
+                // Todo: Batch call using pallet-utility?
+                for (acc_id, contribution) in ProjectContributions::<T>::iter_prefix(project_key) {
+                    let refund_amount: BalanceOf<T> = locked_share.mul_floor(contribution.value);
+                    <T as Config>::MultiCurrency::transfer(
+                        project.currency_id,
+                        &project_account_id,
+                        &acc_id,
+                        refund_amount,
+                    )?;
+                    Self::return_to_remote_contributor(&acc_id, refund_amount, project.currency_id);
+                    T::ContributionReceipts::burn_receipt(project_key, &acc_id)?;
+                }
+                Self::return_locked_matched_funds(project_key, project.currency_id, locked_share)?;
+            }
+            FundingType::Treasury(_) => {
+                let mut refund_amount: BalanceOf<T> = Default::default();
+                // Sum the contributions and send a single xcm.
+                for (acc_id, contribution) in ProjectContributions::<T>::iter_prefix(project_key) {
+                    let per_contributor = locked_share.mul_floor(contribution.value);
+                    refund_amount = refund_amount.saturating_add(per_contributor);
+                    T::ContributionReceipts::burn_receipt(project_key, &acc_id)?;
+                }
+                <T as Config>::RefundHandler::send_refund_message_to_treasury(
+                    project_account_id,
+                    refund_amount,
+                    project.currency_id,
+                    project.funding_type,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Cancel a project at the request of its initiator, paying out the funds already unlocked and refunding
+    /// the funds locked in unapproved milestones. The weight is charged for the actual number of contributors.
+    /// The project is kept as cancelled so it can no longer be funded, voted on or withdrawn from.
+    #[transactional]
+    pub fn do_cancel_project(
+        who: T::AccountId,
+        project_key: ProjectKey,
+    ) -> DispatchResultWithPostInfo {
+        let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(project.initiator == who, Error::<T>::UserIsNotInitiator);
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
+        // Cancelling pays out the approved milestones, so it waits for any dispute over them.
+        ensure!(
+            Disputes::<T>::iter_prefix_values(project_key)
+                .next()
+                .is_none(),
+            Error::<T>::MilestoneDisputed
+        );
+        let contributor_count = Self::project_contributor_count(project_key);
+
+        // Funds already released by approved milestones are paid out before the rest is refunded.
+        let withdrawable = Self::unlocked_funds(project_key, &project);
+        if withdrawable > Zero::zero() {
+            let withdrawn = Self::pay_out_funds(project_key, &project, withdrawable)?;
+            project.withdrawn_funds = project.withdrawn_funds.saturating_add(withdrawable);
+            Self::deposit_event(Event::ProjectFundsWithdrawn(
+                who.clone(),
+                project_key,
+                withdrawn,
+                project.currency_id,
+            ));
+        }
+        Self::refund_locked_contributions(project_key, &project)?;
+        // Walking away from a funded project forfeits the bond.
+        if project.funding_threshold_met {
+            Self::slash_initiator_bond(project_key, &project);
+        } else {
+            Self::return_initiator_bond(project_key, &project);
+        }
+        Self::reinstate_storage_deposit(&project.initiator)?;
+        // Take the project out of the rounds it is still in so it can no longer be contributed to.
+        for round_key in ProjectRounds::<T>::iter_key_prefix(project_key) {
+            if let Some(mut round) = Self::rounds(round_key) {
+                round.project_keys.retain(|key| *key != project_key);
+                Rounds::<T>::insert(round_key, Some(round));
+            }
+        }
+        Self::remove_project_records(project_key);
+        NoConfidenceVotes::<T>::remove(project_key);
+
+        project.cancelled = true;
+        Projects::<T>::insert(project_key, project);
+
+        Self::deposit_event(Event::ProjectCancelledByInitiator(who, project_key));
+        Ok(Some(<T as Config>::WeightInfo::cancel_project(contributor_count)).into())
+    }
+
+    pub fn do_add_juror(juror: T::AccountId) -> DispatchResultWithPostInfo {
+        let mut jurors = Self::jurors();
+        ensure!(!jurors.contains(&juror), Error::<T>::AlreadyJuror);
+        jurors
+            .try_push(juror.clone())
+            .map_err(|_| Error::<T>::TooManyJurors)?;
+        <T as Config>::MultiCurrency::reserve(CurrencyId::Native, &juror, T::JurorBond::get())?;
+        Jurors::<T>::put(jurors);
+
+        Self::deposit_event(Event::JurorAdded(juror));
+        Ok(().into())
+    }
+
+    /// Jurors can be removed while sitting on a panel, the dispute is resolved by the remaining votes.
+    pub fn do_remove_juror(juror: T::AccountId, slash: bool) -> DispatchResultWithPostInfo {
+        let mut jurors = Self::jurors();
+        let index = jurors
+            .iter()
+            .position(|j| j == &juror)
+            .ok_or(Error::<T>::NotJuror)?;
+        jurors.remove(index);
+
+        if slash {
+            let treasury =
+                <T as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)?;
+            let _ = <T as Config>::MultiCurrency::repatriate_reserved(
+                CurrencyId::Native,
+                &juror,
+                &treasury,
+                T::JurorBond::get(),
+                BalanceStatus::Free,
+            );
+        } else {
+            let _ = <T as Config>::MultiCurrency::unreserve(
+                CurrencyId::Native,
+                &juror,
+                T::JurorBond::get(),
+            );
+        }
+        Jurors::<T>::put(jurors);
+
+        Self::deposit_event(Event::JurorRemoved(juror, slash));
+        Ok(().into())
+    }
+
+    /// Raise a dispute on a milestone, or back one raised by another contributor.
+    #[transactional]
+    pub fn do_raise_dispute(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+    ) -> DispatchResultWithPostInfo {
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
+        let milestone = project
+            .milestones
+            .get(&milestone_key)
+            .ok_or(Error::<T>::MilestoneDoesNotExist)?;
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let mut dispute = match Self::disputes(project_key, milestone_key) {
+            Some(dispute) => {
+                ensure!(
+                    dispute.raised_by == DisputeRaiser::Contributors
+                        && dispute.status == DisputeStatus::Backing,
+                    Error::<T>::DisputeAlreadyRaised
+                );
+                ensure!(
+                    !dispute.backers.contains(&who),
+                    Error::<T>::DisputeAlreadyRaised
+                );
+                dispute
+            }
+            None if who == project.initiator => {
+                // Only a milestone which contributors have voted against can be disputed.
+                let rejected = Self::milestone_submissions((project_key, milestone_key))
+                    .map_or(false, |submission| submission.last_rejection.is_some());
+                ensure!(
+                    rejected
+                        && !milestone.is_approved
+                        && !MilestoneVotingDeadlines::<T>::contains_key((
+                            project_key,
+                            milestone_key
+                        )),
+                    Error::<T>::MilestoneNotDisputable
+                );
+                Dispute {
+                    raised_by: DisputeRaiser::Initiator,
+                    status: DisputeStatus::Backing,
+                    backers: Default::default(),
+                    support: Zero::zero(),
+                    jurors: Default::default(),
+                    deadline: now,
+                    approvals: 0,
+                    rejections: 0,
+                }
+            }
+            None => {
+                // The funds released by the milestone must not have been withdrawn yet.
+                let milestone_funds = project
+                    .raised_funds
+                    .saturating_mul(milestone.percentage_to_unlock.into())
+                    / MAX_PERCENTAGE.into();
+                let approved_funds = project
+                    .milestones
+                    .values()
+                    .filter(|ms| ms.is_approved)
+                    .fold(BalanceOf::<T>::zero(), |acc, ms| {
+                        acc.saturating_add(
+                            project
+                                .raised_funds
+                                .saturating_mul(ms.percentage_to_unlock.into())
+                                / MAX_PERCENTAGE.into(),
+                        )
+                    });
+                ensure!(
+                    milestone.is_approved
+                        && approved_funds.saturating_sub(project.withdrawn_funds)
+                            >= milestone_funds
+                        && !milestone_funds.is_zero(),
+                    Error::<T>::MilestoneNotDisputable
+                );
+                Dispute {
+                    raised_by: DisputeRaiser::Contributors,
+                    status: DisputeStatus::Backing,
+                    backers: Default::default(),
+                    support: Zero::zero(),
+                    jurors: Default::default(),
+                    deadline: now.saturating_add(T::DisputeVotingPeriod::get()),
+                    approvals: 0,
+                    rejections: 0,
+                }
+            }
+        };
+
+        let escalate = match dispute.raised_by {
+            DisputeRaiser::Initiator => true,
+            DisputeRaiser::Contributors => {
+                let contribution = Self::ensure_contributor_of(project_key, &who)?;
+                dispute.support = dispute.support.saturating_add(contribution);
+                let percent_multiple: BalanceOf<T> = 100u32.into();
+                dispute.support.saturating_mul(percent_multiple)
+                    >= Self::contributed_funds(project_key, &project)
+                        .saturating_mul(T::DisputeThreshold::get().into())
+            }
+        };
+        dispute
+            .backers
+            .try_push(who.clone())
+            .map_err(|_| Error::<T>::TooManyDisputeBackers)?;
+        <T as Config>::MultiCurrency::reserve(CurrencyId::Native, &who, T::DisputeBond::get())?;
+
+        if dispute.backers.len() == 1 {
+            Self::deposit_event(Event::DisputeRaised(
+                project_key,
+                milestone_key,
+                dispute.raised_by,
+            ));
+            if !escalate {
+                Self::schedule_round_expiry(
+                    dispute.deadline,
+                    ExpiringRound::Dispute(project_key, milestone_key),
+                )?;
+            }
+        } else {
+            Self::deposit_event(Event::DisputeBacked(who, project_key, milestone_key));
+        }
+
+        if escalate {
+            Self::escalate_dispute(&project, project_key, milestone_key, &mut dispute)?;
+        }
+        Disputes::<T>::insert(project_key, milestone_key, dispute);
+        Ok(().into())
+    }
+
+    /// The jurors who have no stake in a project and so can sit on the panel of its disputes.
+    fn eligible_jurors(project: &Project<T>, project_key: ProjectKey) -> Vec<T::AccountId> {
+        Self::jurors()
+            .into_iter()
+            .filter(|juror| {
+                juror != &project.initiator
+                    && !ProjectContributions::<T>::contains_key(project_key, juror)
+            })
+            .collect()
+    }
+
+    /// Send a dispute to arbitration, the panel is drawn by the hooks once the randomness source
+    /// has moved past the current block. Randomness known now could be used by the account
+    /// escalating the dispute to choose its panel, so the draw waits for as many blocks as the
+    /// source lags behind.
+    fn escalate_dispute(
+        project: &Project<T>,
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+        dispute: &mut Dispute<T>,
+    ) -> DispatchResult {
+        ensure!(
+            !Self::eligible_jurors(project, project_key).is_empty(),
+            Error::<T>::NotEnoughJurors
+        );
+        let now = <frame_system::Pallet<T>>::block_number();
+        let (_, known_since) = T::Randomness::random(&(project_key, milestone_key).encode());
+        let draw_at = now
+            .saturating_add(now.saturating_sub(known_since))
+            .saturating_add(One::one());
+        Self::schedule_round_expiry(draw_at, ExpiringRound::Dispute(project_key, milestone_key))?;
+
+        dispute.status = DisputeStatus::Drawing;
+        dispute.deadline = draw_at;
+        Ok(())
+    }
+
+    /// Draw the arbitration panel of an escalated dispute and open the panel's vote.
+    /// The panel is a rotation of the eligible jurors starting from a random index.
+    /// If nobody is eligible any more the dispute is dropped and the bonds returned.
+    fn draw_arbitration_panel(
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+        mut dispute: Dispute<T>,
+    ) -> DispatchResult {
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        let eligible = Self::eligible_jurors(&project, project_key);
+        if eligible.is_empty() {
+            Self::settle_dispute_bonds(&dispute, false);
+            Disputes::<T>::remove(project_key, milestone_key);
+            Self::deposit_event(Event::DisputeDropped(project_key, milestone_key));
+            return Ok(());
+        }
+
+        let (seed, _) = T::Randomness::random(&(project_key, milestone_key).encode());
+        let mut seed_bytes = [0u8; 4];
+        seed_bytes.copy_from_slice(&seed.as_ref()[..4]);
+        let start = u32::from_le_bytes(seed_bytes) as usize % eligible.len();
+        let panel_size = eligible.len().min(T::JurorsPerDispute::get() as usize);
+        let panel: Vec<T::AccountId> = eligible
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(panel_size)
+            .cloned()
+            .collect();
+
+        let now = <frame_system::Pallet<T>>::block_number();
+        let deadline = now.saturating_add(T::DisputeVotingPeriod::get());
+        Self::schedule_round_expiry(deadline, ExpiringRound::Dispute(project_key, milestone_key))?;
+
+        dispute.jurors = panel
+            .clone()
+            .try_into()
+            .map_err(|_| Error::<T>::NotEnoughJurors)?;
+        dispute.status = DisputeStatus::Arbitration;
+        dispute.deadline = deadline;
+        Disputes::<T>::insert(project_key, milestone_key, dispute);
+
+        Self::deposit_event(Event::DisputeEscalated(project_key, milestone_key, panel));
+        Ok(())
+    }
+
+    pub fn do_vote_on_dispute(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+        approve_milestone: bool,
+    ) -> DispatchResultWithPostInfo {
+        let mut dispute =
+            Self::disputes(project_key, milestone_key).ok_or(Error::<T>::DisputeNotFound)?;
+        let now = <frame_system::Pallet<T>>::block_number();
+        ensure!(
+            dispute.status == DisputeStatus::Arbitration && dispute.deadline >= now,
+            Error::<T>::DisputeVotingClosed
+        );
+        ensure!(dispute.jurors.contains(&who), Error::<T>::NotOnDisputePanel);
+        let vote_lookup_key = (project_key, milestone_key);
+        ensure!(
+            !DisputeVotes::<T>::contains_key(vote_lookup_key, &who),
+            Error::<T>::VoteAlreadyExists
+        );
+
+        if approve_milestone {
+            dispute.approvals = dispute.approvals.saturating_add(1);
+        } else {
+            dispute.rejections = dispute.rejections.saturating_add(1);
+        }
+        DisputeVotes::<T>::insert(vote_lookup_key, &who, approve_milestone);
+        Self::deposit_event(Event::DisputeVoteCast(
+            who,
+            project_key,
+            milestone_key,
+            approve_milestone,
+        ));
+
+        // Resolve early once the whole panel has voted.
+        if dispute.approvals.saturating_add(dispute.rejections) as usize >= dispute.jurors.len() {
+            Self::resolve_dispute(project_key, milestone_key, dispute)?;
+        } else {
+            Disputes::<T>::insert(project_key, milestone_key, dispute);
+        }
+        Ok(().into())
+    }
+
+    /// Close a dispute whose deadline has been reached.
+    /// A dispute that was not backed in time is dropped, an escalated one has its panel drawn
+    /// and one in arbitration is resolved on the votes cast.
+    fn close_dispute(project_key: ProjectKey, milestone_key: MilestoneKey) -> DispatchResult {
+        let dispute =
+            Self::disputes(project_key, milestone_key).ok_or(Error::<T>::DisputeNotFound)?;
+        // The dispute was escalated and has a later deadline.
+        if dispute.deadline != <frame_system::Pallet<T>>::block_number() {
+            return Ok(());
+        }
+        match dispute.status {
+            // A dispute nobody backed in time is treated as frivolous.
+            DisputeStatus::Backing => {
+                Self::settle_dispute_bonds(&dispute, true);
+                Disputes::<T>::remove(project_key, milestone_key);
+                Self::deposit_event(Event::DisputeExpired(project_key, milestone_key));
+                Ok(())
+            }
+            DisputeStatus::Drawing => {
+                Self::draw_arbitration_panel(project_key, milestone_key, dispute)
+            }
+            DisputeStatus::Arbitration => {
+                Self::resolve_dispute(project_key, milestone_key, dispute)
+            }
+        }
+    }
+
+    /// Apply the decision of an arbitration panel to the milestone.
+    /// The raiser of the dispute needs a majority of the votes cast, a tie keeps the original decision.
+    /// The bonds of the backers are returned if they won and sent to the treasury if they lost.
+    fn resolve_dispute(
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+        dispute: Dispute<T>,
+    ) -> DispatchResult {
+        let (approved, raiser_won) = match dispute.raised_by {
+            DisputeRaiser::Initiator => {
+                let won = dispute.approvals > dispute.rejections;
+                (won, won)
+            }
+            DisputeRaiser::Contributors => {
+                let won = dispute.rejections > dispute.approvals;
+                (!won, won)
+            }
+        };
+
+        let project = Projects::<T>::try_mutate(project_key, |maybe_project| {
+            let project = maybe_project
+                .as_mut()
+                .ok_or(Error::<T>::ProjectDoesNotExist)?;
+            let milestone = project
+                .milestones
+                .get_mut(&milestone_key)
+                .ok_or(Error::<T>::MilestoneDoesNotExist)?;
+            milestone.is_approved = approved;
+            Ok::<Project<T>, DispatchError>(project.clone())
+        })?;
+        // Contributors proved the milestone should not have been approved.
+        if dispute.raised_by == DisputeRaiser::Contributors && raiser_won {
+            Self::slash_initiator_bond(project_key, &project);
+        }
+        if approved {
+            Self::start_payout_vesting(project_key, milestone_key);
+        } else {
+            MilestoneVotes::<T>::remove((project_key, milestone_key));
+            MilestoneVestingStarts::<T>::remove(project_key, milestone_key);
+        }
+
+        Self::settle_dispute_bonds(&dispute, !raiser_won);
+        let _ = DisputeVotes::<T>::clear_prefix((project_key, milestone_key), u32::MAX, None);
+        Disputes::<T>::remove(project_key, milestone_key);
+
+        Self::deposit_event(Event::DisputeResolved(project_key, milestone_key, approved));
+        Ok(())
+    }
+
+    /// Return the bonds of the backers of a dispute, or send them to the treasury if slashed.
+    fn settle_dispute_bonds(dispute: &Dispute<T>, slash: bool) {
+        let treasury = if slash {
+            <T as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue).ok()
+        } else {
+            None
+        };
+        for backer in dispute.backers.iter() {
+            match &treasury {
+                Some(treasury) => {
+                    let _ = <T as Config>::MultiCurrency::repatriate_reserved(
+                        CurrencyId::Native,
+                        backer,
+                        treasury,
+                        T::DisputeBond::get(),
+                        BalanceStatus::Free,
+                    );
+                }
+                None => {
+                    let _ = <T as Config>::MultiCurrency::unreserve(
+                        CurrencyId::Native,
+                        backer,
+                        T::DisputeBond::get(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Add a round to the list of rounds the hooks will close at the given block.
+    pub(crate) fn schedule_round_expiry(
+        at: T::BlockNumber,
+        expiring_round: ExpiringRound,
+    ) -> Result<(), Error<T>> {
+        RoundsExpiring::<T>::try_mutate(at, |expiring_rounds| {
+            expiring_rounds
+                .try_push(expiring_round)
+                .map_err(|_| Error::<T>::TooManyRoundsExpiringInBlock)
+        })
+    }
+
+    /// Close a round whose end block has been reached.
+    /// Used in hooks so cannot panic, failures are skipped and the round is left as it was.
+    pub(crate) fn close_expired_round(expiring_round: ExpiringRound) -> Weight {
+        match expiring_round {
+            ExpiringRound::Contribution(round_key) => {
+                let round = match Self::rounds(round_key) {
+                    Some(round) => round,
+                    None => return T::DbWeight::get().reads(1),
+                };
+                for project_key in round.project_keys.iter() {
+                    ProjectRounds::<T>::remove(project_key, round_key);
+                }
+                if !round.is_canceled {
+                    // Underfunded projects are failed before the matching pool is shared out,
+                    // so the funding threshold must be met by contributors alone and
+                    // matching is only paid to projects which are going ahead.
+                    for project_key in round.project_keys.iter() {
+                        let _ = Self::fail_underfunded_project(round_key, *project_key);
+                    }
+                    if MatchingPools::<T>::contains_key(round_key) {
+                        let _ = Self::distribute_matching_pool(round_key, &round);
+                    }
+                    Self::deposit_event(Event::FundingRoundEnded(round_key));
+                }
+                <T as Config>::WeightInfo::close_contribution_round(round.project_keys.len() as u32)
+            }
+            ExpiringRound::MilestoneVote(round_key, project_key, milestone_key) => {
+                ProjectRounds::<T>::remove(project_key, round_key);
+                let _ = Self::close_milestone_voting(round_key, project_key, milestone_key);
+                <T as Config>::WeightInfo::finalise_milestone_voting()
+            }
+            ExpiringRound::NoConfidence(round_key, project_key) => {
+                ProjectRounds::<T>::remove(project_key, round_key);
+                let _ = Self::close_no_confidence_round(round_key, project_key);
+                <T as Config>::WeightInfo::finalise_no_confidence_round()
+            }
+            ExpiringRound::Dispute(project_key, milestone_key) => {
+                let _ = Self::close_dispute(project_key, milestone_key);
+                <T as Config>::WeightInfo::close_dispute().max(
+                    <T as Config>::WeightInfo::draw_arbitration_panel(T::MaxJurors::get()),
+                )
+            }
+        }
+    }
+
+    /// Resolve a milestone vote once its voting window has closed.
+    /// If enough contributors voted the milestone is approved or rejected based on the tally,
+    /// otherwise the votes are cleared and the milestone can be submitted again.
+    fn close_milestone_voting(
+        round_key: RoundKey,
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+    ) -> DispatchResult {
+        let vote_lookup_key = (project_key, milestone_key);
+        // The milestone was rejected early and submitted again, its new vote is still open.
+        if Self::milestone_submissions(vote_lookup_key)
+            .map_or(false, |submission| submission.round_key != round_key)
+        {
+            return Ok(());
+        }
+        MilestoneVotingDeadlines::<T>::remove(vote_lookup_key);
+
+        let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        if project.cancelled {
+            MilestoneVotes::<T>::remove(vote_lookup_key);
+            return Ok(());
+        }
+        let vote = Self::milestone_votes(vote_lookup_key).ok_or(Error::<T>::KeyNotFound)?;
+        let milestone = project
+            .milestones
+            .get_mut(&milestone_key)
+            .ok_or(Error::<T>::MilestoneDoesNotExist)?;
+
+        // Already approved through voting or by the authority.
+        if vote.is_approved || milestone.is_approved {
+            return Ok(());
+        }
+
+        let threshold_votes: BalanceOf<T> = Self::contributed_funds(project_key, &project)
+            .saturating_mul(T::PercentRequiredForVoteToPass::get().into());
+        let percent_multiple: BalanceOf<T> = 100u32.into();
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        if percent_multiple.saturating_mul(vote.yay.saturating_add(vote.nay)) < threshold_votes {
+            <MilestoneVotes<T>>::remove(vote_lookup_key);
+            Self::deposit_event(Event::MilestoneReopened(project_key, milestone_key, now));
+            Self::end_unapproved_submission(project_key, milestone_key, None);
+        } else if vote.yay > vote.nay {
+            milestone.is_approved = true;
+            <MilestoneVotes<T>>::insert(
+                vote_lookup_key,
+                Vote {
+                    yay: vote.yay,
+                    nay: vote.nay,
+                    is_approved: true,
+                },
+            );
+            Self::deposit_event(Event::MilestoneApproved(
+                project.initiator.clone(),
+                project_key,
+                milestone_key,
+                now,
+            ));
+            Self::start_payout_vesting(project_key, milestone_key);
+            <Projects<T>>::insert(project_key, project);
+        } else {
+            Self::reject_milestone(project_key, milestone_key, vote);
+        }
+
+        Ok(())
+    }
+
+    /// Close the vote on a milestone which contributors have voted against.
+    /// The tally is kept with the submission and the milestone can be submitted again.
+    fn reject_milestone(
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+        vote: Vote<BalanceOf<T>>,
+    ) {
+        let vote_lookup_key = (project_key, milestone_key);
+        <MilestoneVotes<T>>::remove(vote_lookup_key);
+        <MilestoneVotingDeadlines<T>>::remove(vote_lookup_key);
+        Self::deposit_event(Event::MilestoneRejected(
+            project_key,
+            milestone_key,
+            vote.yay,
+            vote.nay,
+            <frame_system::Pallet<T>>::block_number(),
+        ));
+        Self::end_unapproved_submission(project_key, milestone_key, Some(vote));
+    }
+
+    /// Record the outcome of a submission that was not approved.
+    /// Once all of its attempts have been used the milestone fails and cannot be submitted again.
+    fn end_unapproved_submission(
+        project_key: ProjectKey,
+        milestone_key: MilestoneKey,
+        rejection: Option<Vote<BalanceOf<T>>>,
+    ) {
+        MilestoneSubmissions::<T>::mutate((project_key, milestone_key), |maybe_submission| {
+            if let Some(submission) = maybe_submission {
+                if rejection.is_some() {
+                    submission.last_rejection = rejection;
+                }
+                if submission.attempts >= T::MaxMilestoneSubmissionAttempts::get() {
+                    submission.failed = true;
+                    Self::deposit_event(Event::MilestoneFailed(project_key, milestone_key));
+                }
+            }
+        });
+    }
+
+    /// Resolve a vote of no confidence once its time limit has been reached.
+    /// If the threshold has not been met the vote is removed so that another can be raised.
+    #[transactional]
+    fn close_no_confidence_round(round_key: RoundKey, project_key: ProjectKey) -> DispatchResult {
+        // The vote may have already been finalised by a contributor.
+        if !NoConfidenceVotes::<T>::contains_key(project_key) {
+            return Ok(());
+        }
+
+        let result = Self::do_finalise_no_confidence_vote(
+            round_key,
+            project_key,
+            T::PercentRequiredForVoteToPass::get(),
+        );
+
+        let threshold_not_met: DispatchError = Error::<T>::VoteThresholdNotMet.into();
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if e.error == threshold_not_met => {
+                NoConfidenceVotes::<T>::remove(project_key);
+                Self::deposit_event(Event::NoConfidenceRoundFailed(round_key, project_key));
+                Ok(())
+            }
+            Err(e) => Err(e.error),
+        }
+    }
+
+    // Called to ensure that an account is is a contributor to a project.
+    pub fn do_delegate_vote(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        delegate: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(who != delegate, Error::<T>::CannotDelegateToSelf);
+        ensure!(
+            Projects::<T>::contains_key(project_key),
+            Error::<T>::ProjectDoesNotExist
+        );
+        let _ = Self::ensure_contributor_of(project_key, &who)?;
+
+        let previous_delegate = Self::delegations(project_key, &who);
+        ensure!(
+            previous_delegate.as_ref() != Some(&delegate),
+            Error::<T>::AlreadyDelegated
+        );
+        let delegator_count =
+            Delegators::<T>::iter_key_prefix((project_key, &delegate)).count() as u32;
+        ensure!(
+            delegator_count < T::MaxDelegators::get(),
+            Error::<T>::TooManyDelegators
+        );
+        if let Some(previous_delegate) = previous_delegate {
+            Delegators::<T>::remove((project_key, previous_delegate), &who);
+        }
+        Delegations::<T>::insert(project_key, &who, &delegate);
+        Delegators::<T>::insert((project_key, &delegate), &who, ());
+
+        Self::deposit_event(Event::VoteDelegated(who, project_key, delegate));
+        Ok(Some(<T as Config>::WeightInfo::delegate_vote(delegator_count)).into())
+    }
+
+    pub fn do_undelegate_vote(
+        who: T::AccountId,
+        project_key: ProjectKey,
+    ) -> DispatchResultWithPostInfo {
+        let delegate = Delegations::<T>::take(project_key, &who).ok_or(Error::<T>::NotDelegated)?;
+        Delegators::<T>::remove((project_key, &delegate), &who);
+
+        Self::deposit_event(Event::VoteUndelegated(who, project_key, delegate));
+        Ok(().into())
+    }
+
+    pub fn do_set_funding_threshold(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        funding_threshold: u32,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            funding_threshold > 0 && funding_threshold <= MAX_PERCENTAGE,
+            Error::<T>::InvalidFundingThreshold
+        );
+        Projects::<T>::try_mutate(project_key, |maybe_project| -> DispatchResult {
+            let project = maybe_project
+                .as_mut()
+                .ok_or(Error::<T>::ProjectDoesNotExist)?;
+            ensure!(project.initiator == who, Error::<T>::UserIsNotInitiator);
+            ensure!(
+                !project.approved_for_funding,
+                Error::<T>::ProjectAlreadyApproved
+            );
+            project.funding_threshold = funding_threshold;
+            Ok(())
+        })?;
+
+        Self::deposit_event(Event::FundingThresholdSet(project_key, funding_threshold));
+        Ok(().into())
+    }
+
+    pub fn do_set_payout_vesting(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        vesting_period: Option<T::BlockNumber>,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            vesting_period.map_or(true, |period| !period.is_zero()),
+            Error::<T>::InvalidVestingPeriod
+        );
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(project.initiator == who, Error::<T>::UserIsNotInitiator);
+        ensure!(
+            !project.approved_for_funding,
+            Error::<T>::ProjectAlreadyApproved
+        );
+        PayoutVestingPeriods::<T>::set(project_key, vesting_period);
+
+        Self::deposit_event(Event::PayoutVestingSet(project_key, vesting_period));
+        Ok(().into())
+    }
+
+    pub fn do_propose_initiator_transfer(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        new_initiator: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(project.initiator == who, Error::<T>::UserIsNotInitiator);
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
+        ensure!(new_initiator != who, Error::<T>::InvalidAccount);
+
+        let _ = InitiatorTransferVotes::<T>::clear_prefix(project_key, u32::MAX, None);
+        InitiatorTransfers::<T>::insert(
+            project_key,
+            InitiatorTransfer {
+                new_initiator: new_initiator.clone(),
+                yay: Zero::zero(),
+                nay: Zero::zero(),
+                approved: !project.funding_threshold_met,
+            },
+        );
+
+        Self::deposit_event(Event::InitiatorTransferProposed(project_key, new_initiator));
+        Ok(().into())
+    }
+
+    pub fn do_vote_on_initiator_transfer(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        approve: bool,
+    ) -> DispatchResultWithPostInfo {
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        let mut transfer =
+            Self::initiator_transfers(project_key).ok_or(Error::<T>::NoInitiatorTransfer)?;
+        ensure!(
+            !Self::initiator_transfer_approved(project_key, &project, &transfer),
+            Error::<T>::InitiatorTransferAlreadyApproved
+        );
+        let contribution = Self::ensure_contributor_of(project_key, &who)?;
+        ensure!(
+            !InitiatorTransferVotes::<T>::contains_key(project_key, &who),
+            Error::<T>::VoteAlreadyExists
+        );
+
+        if approve {
+            transfer.yay = transfer.yay.saturating_add(contribution);
+        } else {
+            transfer.nay = transfer.nay.saturating_add(contribution);
+        }
+        InitiatorTransferVotes::<T>::insert(project_key, &who, approve);
+        Self::deposit_event(Event::InitiatorTransferVoteCast(who, project_key, approve));
+
+        let percent_multiple: BalanceOf<T> = 100u32.into();
+        let required: u8 = T::PercentRequiredForVoteToPass::get();
+        if Self::initiator_transfer_approved(project_key, &project, &transfer) {
+            transfer.approved = true;
+            Self::deposit_event(Event::InitiatorTransferApproved(project_key));
+        } else if percent_multiple.saturating_mul(transfer.nay)
+            > Self::contributed_funds(project_key, &project)
+                .saturating_mul(100u8.saturating_sub(required).into())
+        {
+            // The transfer can no longer reach the required share of the raised funds.
+            InitiatorTransfers::<T>::remove(project_key);
+            let _ = InitiatorTransferVotes::<T>::clear_prefix(project_key, u32::MAX, None);
+            Self::deposit_event(Event::InitiatorTransferRejected(project_key));
+            return Ok(().into());
+        }
+        InitiatorTransfers::<T>::insert(project_key, transfer);
+        Ok(().into())
+    }
+
+    #[transactional]
+    pub fn do_accept_initiator_transfer(
+        who: T::AccountId,
+        project_key: ProjectKey,
+    ) -> DispatchResultWithPostInfo {
+        let mut project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
+        let transfer =
+            Self::initiator_transfers(project_key).ok_or(Error::<T>::NoInitiatorTransfer)?;
+        ensure!(transfer.new_initiator == who, Error::<T>::NotNewInitiator);
+        ensure!(
+            Self::initiator_transfer_approved(project_key, &project, &transfer),
+            Error::<T>::InitiatorTransferNotApproved
+        );
+
+        <T as Config>::MultiCurrency::reserve(
+            CurrencyId::Native,
+            &who,
+            T::ProjectStorageDeposit::get(),
+        )
+        .map_err(|_| Error::<T>::ImbueRequiredForStorageDep)?;
+        Self::reinstate_storage_deposit(&project.initiator)?;
+        Self::return_initiator_bond(project_key, &project);
+        Self::reserve_initiator_bond(project_key, &who, project.currency_id)?;
+
+        let old_initiator = sp_std::mem::replace(&mut project.initiator, who.clone());
+        Projects::<T>::insert(project_key, project);
+        InitiatorTransfers::<T>::remove(project_key);
+        let _ = InitiatorTransferVotes::<T>::clear_prefix(project_key, u32::MAX, None);
+
+        Self::deposit_event(Event::InitiatorTransferred(project_key, old_initiator, who));
+        Ok(().into())
+    }
+
+    /// Whether an initiator transfer may be accepted.
+    /// The transfer of a funded project needs the required share of the raised funds to have voted for it,
+    /// even if it was approved without a vote while the project was unfunded.
+    fn initiator_transfer_approved(
+        project_key: ProjectKey,
+        project: &Project<T>,
+        transfer: &InitiatorTransfer<T::AccountId, BalanceOf<T>>,
+    ) -> bool {
+        if !project.funding_threshold_met {
+            return transfer.approved;
+        }
+        let percent_multiple: BalanceOf<T> = 100u32.into();
+        let required: u8 = T::PercentRequiredForVoteToPass::get();
+        percent_multiple.saturating_mul(transfer.yay)
+            >= Self::contributed_funds(project_key, project).saturating_mul(required.into())
+    }
+
+    /// The funds raised by a project from its contributors, without the funds matched from a matching pool.
+    /// Contributions are weighed against this total in every vote, as nobody votes with the matched funds.
+    pub fn contributed_funds(project_key: ProjectKey, project: &Project<T>) -> BalanceOf<T> {
+        project
+            .raised_funds
+            .saturating_sub(MatchedFunds::<T>::get(project_key))
+    }
+
+    pub fn do_set_project_team(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        team: BoundedTeam<T>,
+    ) -> DispatchResultWithPostInfo {
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(
+            Self::has_team_role(project_key, &project, &who, TeamRole::Admin),
+            Error::<T>::UserIsNotInitiator
+        );
+        ensure!(!project.cancelled, Error::<T>::ProjectWithdrawn);
+
+        let mut total_share: u32 = 0;
+        for (i, member) in team.iter().enumerate() {
+            ensure!(
+                !team.iter().skip(i + 1).any(|m| m.account == member.account),
+                Error::<T>::InvalidTeam
+            );
+            total_share = total_share.saturating_add(member.payout_share.into());
+        }
+        ensure!(total_share <= MAX_PERCENTAGE, Error::<T>::InvalidTeam);
+
+        // Payouts are taken from the initiator's share, so only the initiator can change them.
+        if project.initiator != who {
+            let current = Self::project_teams(project_key);
+            let share_of = |members: &[TeamMember<T::AccountId>], account: &T::AccountId| {
+                members
+                    .iter()
+                    .find(|m| m.account == *account)
+                    .map_or(0, |m| m.payout_share)
+            };
+            ensure!(
+                current
+                    .iter()
+                    .chain(team.iter())
+                    .all(|m| share_of(&current, &m.account) == share_of(&team, &m.account)),
+                Error::<T>::UserIsNotInitiator
+            );
+        }
+
+        let members = team.iter().map(|m| m.account.clone()).collect();
+        if team.is_empty() {
+            ProjectTeams::<T>::remove(project_key);
+        } else {
+            ProjectTeams::<T>::insert(project_key, team);
+        }
+
+        Self::deposit_event(Event::ProjectTeamSet(project_key, members));
+        Ok(().into())
+    }
+
+    /// Check whether an account can act for a project in the given role.
+    /// The initiator and team admins hold every role.
+    fn has_team_role(
+        project_key: ProjectKey,
+        project: &Project<T>,
+        who: &T::AccountId,
+        role: TeamRole,
+    ) -> bool {
+        project.initiator == *who
+            || Self::project_teams(project_key)
+                .iter()
+                .any(|m| m.account == *who && (m.role == role || m.role == TeamRole::Admin))
+    }
+
+    /// Remove the contributions, delegations and other records kept for a project once it has been removed.
+    fn remove_project_records(project_key: ProjectKey) {
+        let _ = ProjectContributions::<T>::clear_prefix(
+            project_key,
+            T::MaximumContributorsPerProject::get(),
+            None,
+        );
+        ProjectContributorCount::<T>::remove(project_key);
+        ContributionRootSums::<T>::remove(project_key);
+        let _ = ProjectRounds::<T>::clear_prefix(project_key, u32::MAX, None);
+        for (delegator, delegate) in Delegations::<T>::drain_prefix(project_key) {
+            Delegators::<T>::remove((project_key, delegate), delegator);
+        }
+        PayoutVestingPeriods::<T>::remove(project_key);
+        let _ = MilestoneVestingStarts::<T>::clear_prefix(project_key, u32::MAX, None);
+        InitiatorTransfers::<T>::remove(project_key);
+        let _ = InitiatorTransferVotes::<T>::clear_prefix(project_key, u32::MAX, None);
+        ProjectTeams::<T>::remove(project_key);
+        ProjectIdentityPolicies::<T>::remove(project_key);
+        ProjectContributionLimits::<T>::remove(project_key);
+        // Open disputes are dropped and their bonds returned.
+        for (milestone_key, dispute) in Disputes::<T>::drain_prefix(project_key) {
+            Self::settle_dispute_bonds(&dispute, false);
+            let _ = DisputeVotes::<T>::clear_prefix((project_key, milestone_key), u32::MAX, None);
+        }
+    }
+
+    /// The leaf of a merkle whitelist for an account and its maximum contribution, zero meaning uncapped.
+    pub fn whitelist_leaf(who: &T::AccountId, max_cap: BalanceOf<T>) -> H256 {
+        sp_io::hashing::blake2_256(&(who, max_cap).encode()).into()
+    }
+
+    fn ensure_contributor_of(
+        project_key: ProjectKey,
+        account_id: &T::AccountId,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let contribution = ProjectContributions::<T>::get(project_key, account_id);
+        match contribution {
+            Some(c) => Ok(c.value),
+            _ => Err(Error::<T>::OnlyContributorsCanVote),
+        }
+    }
+
+    fn ensure_identity_is_decent(who: &T::AccountId) -> Result<(), Error<T>> {
+        let identity =
+            pallet_identity::Pallet::<T>::identity(who).ok_or(Error::<T>::IdentityNeeded)?;
+
+        if identity
+            .judgements
+            .iter()
+            .any(|j| j.1 == Judgement::Reasonable || j.1 == Judgement::KnownGood)
+        {
+            Ok(())
+        } else {
+            Err(Error::<T>::InvalidAccount)
+        }
+    }
+
+    /// Ensure the minimum contribution does not exceed the other limits which have been set.
+    fn ensure_valid_contribution_limits(
+        limits: &ContributionLimits<BalanceOf<T>>,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            (limits.max_per_contributor.is_zero()
+                || limits.min_contribution <= limits.max_per_contributor)
+                && (limits.hard_cap.is_zero() || limits.min_contribution <= limits.hard_cap),
+            Error::<T>::InvalidContributionLimits
+        );
+        Ok(())
+    }
+
+    /// The identity policy of a project, the stricter of its own policy and the default policy set by governance.
+    pub fn identity_policy_of(project_key: ProjectKey) -> Option<IdentityPolicy<T>> {
+        match (
+            Self::project_identity_policies(project_key),
+            Self::default_identity_policy(),
+        ) {
+            (Some(project), Some(default)) => {
+                Some(Self::stricter_identity_policy(project, default))
+            }
+            (project, default) => project.or(default),
+        }
+    }
+
+    /// Combine a project's identity policy with the default so that it can only add requirements.
+    /// If the trusted registrars of the two policies do not overlap, those of the default are kept.
+    fn stricter_identity_policy(
+        project: IdentityPolicy<T>,
+        default: IdentityPolicy<T>,
+    ) -> IdentityPolicy<T> {
+        let trusted_registrars = if project.trusted_registrars.is_empty() {
+            default.trusted_registrars
+        } else if default.trusted_registrars.is_empty() {
+            project.trusted_registrars
+        } else {
+            let mut shared = project.trusted_registrars;
+            shared.retain(|registrar| default.trusted_registrars.contains(registrar));
+            if shared.is_empty() {
+                default.trusted_registrars
+            } else {
+                shared
+            }
+        };
+        let contributor_threshold =
+            match (project.contributor_threshold, default.contributor_threshold) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+
+        IdentityPolicy {
+            trusted_registrars,
+            required_fields: IdentityFields(project.required_fields.0 | default.required_fields.0),
+            initiators: project.initiators || default.initiators,
+            contributor_threshold,
+            brief_applicants: project.brief_applicants || default.brief_applicants,
+        }
+    }
+
+    /// Ensure an identity has been judged `Reasonable` or `KnownGood` by a registrar the policy trusts,
+    /// and has every field the policy requires.
+    pub fn ensure_identity_meets(
+        who: &T::AccountId,
+        policy: &IdentityPolicy<T>,
+    ) -> Result<(), Error<T>> {
+        let identity =
+            pallet_identity::Pallet::<T>::identity(who).ok_or(Error::<T>::IdentityNeeded)?;
+        ensure!(
+            identity.judgements.iter().any(|(registrar, judgement)| {
+                (policy.trusted_registrars.is_empty()
+                    || policy.trusted_registrars.contains(registrar))
+                    && (*judgement == Judgement::Reasonable || *judgement == Judgement::KnownGood)
+            }),
+            Error::<T>::InvalidAccount
+        );
+        ensure!(
+            policy
+                .required_fields
+                .0
+                .iter()
+                .all(|field| Self::has_identity_field(&identity.info, field)),
+            Error::<T>::IdentityFieldMissing
+        );
+        Ok(())
+    }
+
+    fn has_identity_field(
+        info: &IdentityInfo<T::MaxAdditionalFields>,
+        field: IdentityField,
+    ) -> bool {
+        match field {
+            IdentityField::Display => info.display != Data::None,
+            IdentityField::Legal => info.legal != Data::None,
+            IdentityField::Web => info.web != Data::None,
+            IdentityField::Riot => info.riot != Data::None,
+            IdentityField::Email => info.email != Data::None,
+            IdentityField::PgpFingerprint => info.pgp_fingerprint.is_some(),
+            IdentityField::Image => info.image != Data::None,
+            IdentityField::Twitter => info.twitter != Data::None,
+        }
+    }
+
+    pub fn do_set_project_identity_policy(
+        who: T::AccountId,
+        project_key: ProjectKey,
+        policy: Option<IdentityPolicy<T>>,
+    ) -> DispatchResultWithPostInfo {
+        let project = Projects::<T>::get(project_key).ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(project.initiator == who, Error::<T>::UserIsNotInitiator);
+        ensure!(
+            !project.approved_for_funding,
+            Error::<T>::ProjectAlreadyApproved
+        );
+        ProjectIdentityPolicies::<T>::set(project_key, policy);
+
+        Self::deposit_event(Event::ProjectIdentityPolicySet(project_key));
+        Ok(().into())
+    }
+
+    /// Reserve the initiator bond for a project in the given currency.
+    pub(crate) fn reserve_initiator_bond(
+        project_key: ProjectKey,
+        who: &AccountIdOf<T>,
+        currency_id: CurrencyId,
+    ) -> DispatchResult {
+        let bond = T::InitiatorBond::get(&currency_id);
+        if !bond.is_zero() {
+            <T as Config>::MultiCurrency::reserve(currency_id, who, bond)
+                .map_err(|_| Error::<T>::InitiatorBondRequired)?;
+            InitiatorBonds::<T>::insert(project_key, bond);
+        }
+        Ok(())
+    }
+
+    /// Return the initiator bond of a project which has completed, or never received funding.
+    fn return_initiator_bond(project_key: ProjectKey, project: &Project<T>) {
+        if let Some(bond) = InitiatorBonds::<T>::take(project_key) {
+            let _ = <T as Config>::MultiCurrency::unreserve(
+                project.currency_id,
+                &project.initiator,
+                bond,
+            );
+        }
+    }
+
+    /// Slash the initiator bond of a failed project to its contributors in proportion to their contributions.
+    /// Treasury funded projects, and anything left over from rounding, are slashed to the treasury.
+    /// Must be called before the contributions are removed.
+    fn slash_initiator_bond(project_key: ProjectKey, project: &Project<T>) {
+        let bond = match InitiatorBonds::<T>::take(project_key) {
+            Some(bond) => bond,
+            None => return,
+        };
+        let mut remaining = bond;
+        let contributed_funds = Self::contributed_funds(project_key, project);
+        if !matches!(project.funding_type, FundingType::Treasury(_)) && !contributed_funds.is_zero()
+        {
+            for (contributor, contribution) in ProjectContributions::<T>::iter_prefix(project_key) {
+                let share =
+                    Perbill::from_rational(contribution.value, contributed_funds).mul_floor(bond);
+                let _ = <T as Config>::MultiCurrency::repatriate_reserved(
+                    project.currency_id,
+                    &project.initiator,
+                    &contributor,
+                    share,
+                    BalanceStatus::Free,
+                );
+                remaining = remaining.saturating_sub(share);
+            }
+        }
+        if let Ok(treasury) =
+            <T as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+        {
+            let _ = <T as Config>::MultiCurrency::repatriate_reserved(
+                project.currency_id,
+                &project.initiator,
+                &treasury,
+                remaining,
+                BalanceStatus::Free,
+            );
         }
+        Self::deposit_event(Event::InitiatorBondSlashed(project_key, bond));
     }
 
     /// Call this to remove a project from storage and reinstate the deposit.
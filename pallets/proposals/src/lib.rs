@@ -1,23 +1,29 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use common_types::{CurrencyId, FundingType};
+use common_types::{CurrencyId, FundingType, TreasuryOrigin};
 use frame_support::{
     pallet_prelude::*,
     storage::bounded_btree_map::BoundedBTreeMap,
-    traits::{ConstU32, EnsureOrigin},
+    traits::{ConstU32, EnsureOrigin, Randomness},
     transactional, PalletId,
 };
 use frame_system::pallet_prelude::*;
-use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use orml_traits::{GetByKey, MultiCurrency, MultiReservableCurrency};
 pub use pallet::*;
+use pallet_identity::{IdentityFields, RegistrarIndex};
+pub use proofs::Proof;
 use scale_info::TypeInfo;
 use sp_core::H256;
-use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::{
+    traits::{AccountIdConversion, Convert},
+    Perbill,
+};
 use sp_std::{collections::btree_map::BTreeMap, convert::TryInto, prelude::*};
+use xcm::latest::MultiLocation;
 
 pub mod traits;
-use traits::RefundHandler;
+use traits::{ContributionReceipts, RefundHandler};
 
 #[cfg(test)]
 mod mock;
@@ -36,6 +42,8 @@ pub mod migration;
 pub mod impls;
 pub use impls::*;
 
+pub mod runtime_api;
+
 /// <HB SBP Review:
 ///
 ///
@@ -43,7 +51,6 @@ pub use impls::*;
 ///
 /// >
 // The Constants associated with the bounded parameters
-type MaxProjectKeysPerRound = ConstU32<1000>;
 type MaxWhitelistPerProject = ConstU32<10000>;
 
 pub type RoundKey = u32;
@@ -51,22 +58,25 @@ pub type ProjectKey = u32;
 pub type MilestoneKey = u32;
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
-type RoundOf<T> = Round<<T as frame_system::Config>::BlockNumber>;
+type RoundOf<T> = Round<T>;
 pub type TimestampOf<T> = <T as pallet_timestamp::Config>::Moment;
 pub type ProjectAccountId<T> = <T as frame_system::Config>::AccountId;
-pub type Refunds<T> = Vec<(
-    AccountIdOf<T>,
-    ProjectAccountId<T>,
-    BalanceOf<T>,
-    CurrencyId,
-)>;
 // These are the bounded types which are suitable for handling user input due to their restriction of vector length.
 type BoundedWhitelistSpots<T> =
     BoundedBTreeMap<AccountIdOf<T>, BalanceOf<T>, MaxWhitelistPerProject>;
-type BoundedProjectKeys = BoundedVec<ProjectKey, MaxProjectKeysPerRound>;
+type BoundedProjectKeys<T> = BoundedVec<ProjectKey, <T as Config>::MaxProjectsPerRound>;
+pub type BoundedMilestones<T> = BoundedBTreeMap<
+    MilestoneKey,
+    Milestone<<T as frame_system::Config>::BlockNumber>,
+    <T as Config>::MaxMilestonesPerProject,
+>;
 type BoundedMilestoneKeys<T> = BoundedVec<ProjectKey, <T as Config>::MaxMilestonesPerProject>;
-pub type BoundedProposedMilestones<T> =
-    BoundedVec<ProposedMilestone, <T as Config>::MaxMilestonesPerProject>;
+pub type BoundedProposedMilestones<T> = BoundedVec<
+    ProposedMilestone<<T as frame_system::Config>::BlockNumber>,
+    <T as Config>::MaxMilestonesPerProject,
+>;
+type BoundedExpiringRounds<T> = BoundedVec<ExpiringRound, <T as Config>::ExpiringRoundsPerBlock>;
+pub type BoundedTeam<T> = BoundedVec<TeamMember<AccountIdOf<T>>, <T as Config>::MaxTeamMembers>;
 
 /// <HB SBP Review:
 ///
@@ -107,13 +117,10 @@ pub mod pallet {
         /// Maximum number of contributors per project.
         type MaximumContributorsPerProject: Get<u32>;
 
-        // DEPRICATED DO NOT USE AND REMOVE
-        type RefundsPerBlock: Get<u8>;
-
         // Defines wether an identity is required when creating a proposal.
         type IsIdentityRequired: Get<bool>;
 
-        /// TODO: not in use
+        /// The number of blocks contributors have to vote on a milestone once it is submitted.
         type MilestoneVotingWindow: Get<Self::BlockNumber>;
 
         /// The type responisble for handling refunds.
@@ -126,33 +133,100 @@ pub mod pallet {
 
         // Imbue fee in percent 0-99
         type ImbueFee: Get<u8>;
+
+        /// The maximum number of rounds that can be closed by the hooks in a single block.
+        type ExpiringRoundsPerBlock: Get<u32>;
+
+        /// The number of times a milestone can be submitted for a vote before it fails.
+        type MaxMilestoneSubmissionAttempts: Get<u32>;
+
+        /// The account which receives the collators' share of the Imbue fee.
+        type CollatorPot: Get<AccountIdOf<Self>>;
+
+        /// The bond reserved by the initiator, or each contributor, raising a dispute. Slashed if the dispute is lost.
+        type DisputeBond: Get<BalanceOf<Self>>;
+
+        /// The percentage of a project's raised funds the contributors backing a dispute must hold for it to go to arbitration.
+        type DisputeThreshold: Get<u8>;
+
+        /// The number of blocks contributors have to back a dispute, and an arbitration panel has to vote on it.
+        type DisputeVotingPeriod: Get<Self::BlockNumber>;
+
+        /// The bond reserved by an account when it joins the juror set.
+        type JurorBond: Get<BalanceOf<Self>>;
+
+        /// The maximum number of accounts in the juror set.
+        type MaxJurors: Get<u32>;
+
+        /// The number of jurors drawn onto the arbitration panel of a dispute.
+        type JurorsPerDispute: Get<u32>;
+
+        /// The maximum number of members in a project team.
+        type MaxTeamMembers: Get<u32>;
+
+        /// The maximum number of contributors who can delegate their vote on a project to one account.
+        /// Bounds the work done when a delegate votes.
+        type MaxDelegators: Get<u32>;
+
+        /// The bond reserved from the initiator in the project's currency when a project is created.
+        /// It is returned once the project completes and slashed to contributors if it fails.
+        type InitiatorBond: GetByKey<CurrencyId, BalanceOf<Self>>;
+
+        /// The smallest contribution accepted in each currency, protecting projects from dust contributions.
+        type MinimumContribution: GetByKey<CurrencyId, BalanceOf<Self>>;
+
+        /// The type responsible for minting, updating and burning the non-transferable receipts held by contributors.
+        type ContributionReceipts: traits::ContributionReceipts<
+            AccountIdOf<Self>,
+            BalanceOf<Self>,
+            Self::BlockNumber,
+        >;
+
+        /// The origin of contributions dispatched by XCM `Transact` from accounts on sibling parachains,
+        /// resolving to the location of the remote account.
+        type XcmOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+
+        /// Converts the location of a remote account into the local account it contributes as.
+        /// This must agree with the conversion used by the XCM executor when assets are deposited.
+        type LocationToAccountId: Convert<MultiLocation, Option<AccountIdOf<Self>>>;
+
+        /// The source of randomness used to draw the jury of an escalated dispute.
+        /// The draw waits until the block number it returns is past the escalation.
+        type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    /// <HB SBP Review:
-    ///
-    /// CRITICAL: This macro should be removed asap. This basically allows storing unbounded Vecs on storage items.
-    ///
-    /// >
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::storage]
     #[pallet::getter(fn projects)]
-    pub type Projects<T: Config> = StorageMap<
+    pub type Projects<T: Config> = StorageMap<_, Identity, ProjectKey, Project<T>, OptionQuery>;
+
+    /// The contributions made to a project, keyed by (project, contributor).
+    #[pallet::storage]
+    #[pallet::getter(fn project_contributions)]
+    pub type ProjectContributions<T: Config> = StorageDoubleMap<
         _,
         Identity,
         ProjectKey,
-        Project<T::AccountId, BalanceOf<T>, T::BlockNumber, TimestampOf<T>>,
+        Blake2_128Concat,
+        T::AccountId,
+        Contribution<BalanceOf<T>, TimestampOf<T>>,
         OptionQuery,
     >;
 
+    /// The number of accounts contributing to a project, bounded by `MaximumContributorsPerProject`.
+    #[pallet::storage]
+    #[pallet::getter(fn project_contributor_count)]
+    pub type ProjectContributorCount<T> = StorageMap<_, Identity, ProjectKey, u32, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn whitelist_spots)]
     pub type WhitelistSpots<T: Config> =
-        StorageMap<_, Identity, ProjectKey, BTreeMap<T::AccountId, BalanceOf<T>>, OptionQuery>;
+        StorageMap<_, Identity, ProjectKey, BoundedWhitelistSpots<T>, OptionQuery>;
 
+    /// The side each contributor has voted on, they can switch sides while the vote is open.
     #[pallet::storage]
     #[pallet::getter(fn user_votes)]
     pub(super) type UserVotes<T: Config> = StorageMap<
@@ -163,11 +237,65 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// The votes in `UserVotes` which were cast by the voter's delegate rather than the voter.
+    /// A delegate changing their vote only moves these, a voter's own vote is left as it is.
+    #[pallet::storage]
+    pub(super) type DelegatedVotes<T: Config> = StorageMap<
+        _,
+        Identity,
+        (T::AccountId, ProjectKey, MilestoneKey, RoundKey),
+        (),
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn milestone_votes)]
     pub(super) type MilestoneVotes<T: Config> =
         StorageMap<_, Identity, (ProjectKey, MilestoneKey), Vote<BalanceOf<T>>, OptionQuery>;
 
+    /// The block at which voting closes on a submitted milestone.
+    /// Removed once the vote has been resolved by the hooks.
+    #[pallet::storage]
+    #[pallet::getter(fn milestone_voting_deadlines)]
+    pub type MilestoneVotingDeadlines<T: Config> =
+        StorageMap<_, Identity, (ProjectKey, MilestoneKey), T::BlockNumber, OptionQuery>;
+
+    /// The latest submission of a milestone and the outcome of its previous votes.
+    #[pallet::storage]
+    #[pallet::getter(fn milestone_submissions)]
+    pub type MilestoneSubmissions<T: Config> = StorageMap<
+        _,
+        Identity,
+        (ProjectKey, MilestoneKey),
+        MilestoneSubmission<T::BlockNumber, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// The account a contributor has delegated their vote on a project to.
+    #[pallet::storage]
+    #[pallet::getter(fn delegations)]
+    pub type Delegations<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        ProjectKey,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// The contributors who have delegated their vote on a project to an account, keyed by (project, delegate).
+    #[pallet::storage]
+    pub type Delegators<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (ProjectKey, T::AccountId),
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
     /// This holds the votes when a no confidence round is raised.
     #[pallet::storage]
     #[pallet::getter(fn no_confidence_votes)]
@@ -182,6 +310,11 @@ pub mod pallet {
     #[pallet::getter(fn rounds)]
     pub type Rounds<T> = StorageMap<_, Identity, RoundKey, Option<RoundOf<T>>, ValueQuery>;
 
+    /// The rounds a project has been added to which have not yet been closed by the hooks.
+    #[pallet::storage]
+    pub type ProjectRounds<T> =
+        StorageDoubleMap<_, Identity, ProjectKey, Identity, RoundKey, (), ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn round_count)]
     pub type RoundCount<T> = StorageValue<_, RoundKey, ValueQuery>;
@@ -195,25 +328,166 @@ pub mod pallet {
     #[pallet::getter(fn storage_version)]
     pub(super) type StorageVersion<T: Config> = StorageValue<_, Release, ValueQuery>;
 
-    /// TODO: Use a multilocation for the refunds
+    /// The rounds which are due to be closed by the hooks, keyed by the block they end on.
+    #[pallet::storage]
+    #[pallet::getter(fn rounds_expiring)]
+    pub type RoundsExpiring<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::BlockNumber, BoundedExpiringRounds<T>, ValueQuery>;
+
+    /// The quadratic funding matching pool of a contribution round, distributed when the round ends.
     #[pallet::storage]
-    #[pallet::getter(fn refund_queue)]
-    pub type RefundQueue<T> = StorageValue<
+    #[pallet::getter(fn matching_pools)]
+    pub type MatchingPools<T: Config> =
+        StorageMap<_, Identity, RoundKey, MatchingPool<BalanceOf<T>>, OptionQuery>;
+
+    /// The amount a project has received from a matching pool.
+    #[pallet::storage]
+    #[pallet::getter(fn matched_funds)]
+    pub type MatchedFunds<T: Config> =
+        StorageMap<_, Identity, ProjectKey, BalanceOf<T>, ValueQuery>;
+
+    /// The sum of the square roots of each contribution to a project.
+    /// Kept up to date as contributions change so matching does not need to walk the contributions.
+    #[pallet::storage]
+    #[pallet::getter(fn contribution_root_sums)]
+    pub type ContributionRootSums<T: Config> =
+        StorageMap<_, Identity, ProjectKey, BalanceOf<T>, ValueQuery>;
+
+    /// How the Imbue fee is shared out, set by governance.
+    #[pallet::storage]
+    #[pallet::getter(fn fee_split)]
+    pub type FeeSplits<T: Config> = StorageValue<_, FeeSplit, ValueQuery>;
+
+    /// The Imbue fee in percent charged on withdrawals for a funding type.
+    /// Funding types without a rate are charged the default `ImbueFee`.
+    #[pallet::storage]
+    #[pallet::getter(fn fee_rates)]
+    pub type FeeRates<T: Config> = StorageMap<_, Blake2_128Concat, FundingType, u8, OptionQuery>;
+
+    /// The number of blocks over which the funds of each approved milestone are streamed to the initiator.
+    /// Projects without a period are paid out in full on approval.
+    #[pallet::storage]
+    #[pallet::getter(fn payout_vesting_periods)]
+    pub type PayoutVestingPeriods<T: Config> =
+        StorageMap<_, Identity, ProjectKey, T::BlockNumber, OptionQuery>;
+
+    /// The block the funds of an approved milestone started vesting on, for projects which stream their payouts.
+    #[pallet::storage]
+    #[pallet::getter(fn milestone_vesting_starts)]
+    pub type MilestoneVestingStarts<T: Config> = StorageDoubleMap<
         _,
-        /// <HB SBP Review:
-        ///
-        /// Unbounded Vec on a storage item. This should be addressed before deploying.
-        ///
-        /// >
-        Vec<(
-            AccountIdOf<T>,
-            ProjectAccountId<T>,
-            BalanceOf<T>,
-            CurrencyId,
-        )>,
+        Identity,
+        ProjectKey,
+        Identity,
+        MilestoneKey,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// The initiator bond reserved for a project, in the project's currency.
+    #[pallet::storage]
+    #[pallet::getter(fn initiator_bonds)]
+    pub type InitiatorBonds<T: Config> =
+        StorageMap<_, Identity, ProjectKey, BalanceOf<T>, OptionQuery>;
+
+    /// The members of a project team, who can act for the initiator in their role and share its payouts.
+    #[pallet::storage]
+    #[pallet::getter(fn project_teams)]
+    pub type ProjectTeams<T: Config> =
+        StorageMap<_, Identity, ProjectKey, BoundedTeam<T>, ValueQuery>;
+
+    /// The merkle root of a project's whitelist of `(account, max_cap)` leaves.
+    /// Contributors prove their spot when contributing rather than every spot being stored.
+    #[pallet::storage]
+    #[pallet::getter(fn whitelist_roots)]
+    pub type WhitelistRoots<T: Config> = StorageMap<_, Identity, ProjectKey, H256, OptionQuery>;
+
+    /// The contribution limits set by initiators for their projects.
+    #[pallet::storage]
+    #[pallet::getter(fn contribution_limits)]
+    pub type ProjectContributionLimits<T: Config> =
+        StorageMap<_, Identity, ProjectKey, ContributionLimits<BalanceOf<T>>, OptionQuery>;
+
+    /// The identity policy set by governance, the least every project must require.
+    #[pallet::storage]
+    #[pallet::getter(fn default_identity_policy)]
+    pub type DefaultIdentityPolicy<T: Config> = StorageValue<_, IdentityPolicy<T>, OptionQuery>;
+
+    /// The identity policies set by initiators for their projects.
+    #[pallet::storage]
+    #[pallet::getter(fn project_identity_policies)]
+    pub type ProjectIdentityPolicies<T: Config> =
+        StorageMap<_, Identity, ProjectKey, IdentityPolicy<T>, OptionQuery>;
+
+    /// The locations of the remote accounts which have contributed through XCM, keyed by their local account.
+    /// Refunds to these accounts are sent back to the chain they contributed from.
+    #[pallet::storage]
+    #[pallet::getter(fn remote_contributors)]
+    pub type RemoteContributors<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, MultiLocation, OptionQuery>;
+
+    /// The refunds which could not be sent back to the chain of a remote contributor, by currency.
+    /// They are kept by the local account until they are sent again with `retry_remote_refund`.
+    #[pallet::storage]
+    #[pallet::getter(fn unsent_remote_refunds)]
+    pub type UnsentRemoteRefunds<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        CurrencyId,
+        BalanceOf<T>,
         ValueQuery,
     >;
 
+    /// The initiator transfers which have been proposed and are waiting to be accepted by the new initiator.
+    #[pallet::storage]
+    #[pallet::getter(fn initiator_transfers)]
+    pub type InitiatorTransfers<T: Config> = StorageMap<
+        _,
+        Identity,
+        ProjectKey,
+        InitiatorTransfer<T::AccountId, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// The votes contributors have cast on a proposed initiator transfer, true to approve it.
+    #[pallet::storage]
+    pub type InitiatorTransferVotes<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        ProjectKey,
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        OptionQuery,
+    >;
+
+    /// The accounts which can be drawn onto an arbitration panel, each has reserved the `JurorBond`.
+    #[pallet::storage]
+    #[pallet::getter(fn jurors)]
+    pub type Jurors<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxJurors>, ValueQuery>;
+
+    /// The milestone decisions which are being disputed, keyed by (project, milestone).
+    #[pallet::storage]
+    #[pallet::getter(fn disputes)]
+    pub type Disputes<T: Config> =
+        StorageDoubleMap<_, Identity, ProjectKey, Identity, MilestoneKey, Dispute<T>, OptionQuery>;
+
+    /// The votes cast by an arbitration panel, true to approve the milestone.
+    #[pallet::storage]
+    #[pallet::getter(fn dispute_votes)]
+    pub type DisputeVotes<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        (ProjectKey, MilestoneKey),
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        OptionQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -232,8 +506,8 @@ pub mod pallet {
         FundingRoundCreated(RoundKey, Vec<ProjectKey>),
         /// A voting round has been created.
         VotingRoundCreated(RoundKey, Vec<ProjectKey>),
-        /// You have submitted a milestone.
-        MilestoneSubmitted(T::AccountId, ProjectKey, MilestoneKey),
+        /// You have submitted a milestone along with the hash of its evidence.
+        MilestoneSubmitted(T::AccountId, ProjectKey, MilestoneKey, H256),
         /// Contribution has succeded.
         ContributeSucceeded(
             T::AccountId,
@@ -244,6 +518,8 @@ pub mod pallet {
         ),
         /// A project has been cancelled.
         ProjectCancelled(RoundKey, ProjectKey),
+        /// A project has been cancelled by its initiator and its contributors refunded.
+        ProjectCancelledByInitiator(T::AccountId, ProjectKey),
         /// Successfully withdrawn funds from the project.
         ProjectFundsWithdrawn(T::AccountId, ProjectKey, BalanceOf<T>, CurrencyId),
         /// A project has been approved.
@@ -258,14 +534,100 @@ pub mod pallet {
         WhitelistAdded(ProjectKey, T::BlockNumber),
         /// A white list has been removed.
         WhitelistRemoved(ProjectKey, T::BlockNumber),
-        /// A project has been added to refund queue.
-        ProjectFundsAddedToRefundQueue(ProjectKey, BalanceOf<T>),
         /// You have created a vote of no confidence.
         NoConfidenceRoundCreated(RoundKey, ProjectKey),
         /// You have voted upon a round of no confidence.
         NoConfidenceRoundVotedUpon(RoundKey, ProjectKey),
         /// You have finalised a vote of no confidence.
         NoConfidenceRoundFinalised(RoundKey, ProjectKey),
+        /// A funding round has reached its end block.
+        FundingRoundEnded(RoundKey),
+        /// Contributors have voted against a milestone, with the (yay, nay) tally of the vote.
+        MilestoneRejected(
+            ProjectKey,
+            MilestoneKey,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            T::BlockNumber,
+        ),
+        /// A vote of no confidence has closed without reaching the required threshold.
+        NoConfidenceRoundFailed(RoundKey, ProjectKey),
+        /// Not enough contributors voted on a milestone before its window closed, it can be submitted again.
+        MilestoneReopened(ProjectKey, MilestoneKey, T::BlockNumber),
+        /// A project did not raise its required funds before the funding round ended, contributors can claim refunds.
+        ProjectFundingFailed(RoundKey, ProjectKey),
+        /// A contribution has been refunded from a cancelled project.
+        ContributionRefunded(T::AccountId, ProjectKey, BalanceOf<T>, CurrencyId),
+        /// A contribution has been reduced or withdrawn while the funding round is open.
+        ContributionWithdrawn(
+            T::AccountId,
+            ProjectKey,
+            BalanceOf<T>,
+            common_types::CurrencyId,
+            T::BlockNumber,
+        ),
+        /// A round's matching pool has been funded.
+        MatchingPoolFunded(RoundKey, T::AccountId, BalanceOf<T>, CurrencyId),
+        /// A project has received its share of a round's matching pool.
+        MatchingFundsDistributed(RoundKey, ProjectKey, BalanceOf<T>),
+        /// A milestone has used all of its submission attempts without being approved.
+        MilestoneFailed(ProjectKey, MilestoneKey),
+        /// A contributor has delegated their vote on a project, (delegator, project, delegate).
+        VoteDelegated(T::AccountId, ProjectKey, T::AccountId),
+        /// A contributor has taken back their vote on a project, (delegator, project, delegate).
+        VoteUndelegated(T::AccountId, ProjectKey, T::AccountId),
+        /// The funding threshold of a project has been set, (project, percentage of required funds).
+        FundingThresholdSet(ProjectKey, u32),
+        /// The Imbue fee from a withdrawal has been shared out, (project, currency, to treasury, burnt, to collators).
+        FeeDistributed(ProjectKey, CurrencyId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// The way the Imbue fee is shared out has been changed.
+        FeeSplitSet(FeeSplit),
+        /// The Imbue fee rate for a funding type has been changed, None means the default is used.
+        FeeRateSet(FundingType, Option<u8>),
+        /// An account has bonded and joined the juror set.
+        JurorAdded(T::AccountId),
+        /// An account has been removed from the juror set, (juror, bond slashed).
+        JurorRemoved(T::AccountId, bool),
+        /// The decision on a milestone has been disputed.
+        DisputeRaised(ProjectKey, MilestoneKey, DisputeRaiser),
+        /// A contributor has bonded to back a dispute.
+        DisputeBacked(T::AccountId, ProjectKey, MilestoneKey),
+        /// A dispute has gone to arbitration before the given panel of jurors.
+        DisputeEscalated(ProjectKey, MilestoneKey, Vec<T::AccountId>),
+        /// A juror has voted on a dispute, true to approve the milestone.
+        DisputeVoteCast(T::AccountId, ProjectKey, MilestoneKey, bool),
+        /// A dispute has been resolved and the milestone approved or rejected.
+        DisputeResolved(ProjectKey, MilestoneKey, bool),
+        /// Not enough contributors backed a dispute in time, their bonds have been sent to the treasury.
+        DisputeExpired(ProjectKey, MilestoneKey),
+        /// No jurors were left to arbitrate a dispute, it has been dropped and the bonds returned.
+        DisputeDropped(ProjectKey, MilestoneKey),
+        /// The period approved milestone funds are streamed over has been set, None means they are paid out in full.
+        PayoutVestingSet(ProjectKey, Option<T::BlockNumber>),
+        /// The initiator has proposed to hand the project over to a new initiator.
+        InitiatorTransferProposed(ProjectKey, T::AccountId),
+        /// A contributor has voted on an initiator transfer, true to approve it.
+        InitiatorTransferVoteCast(T::AccountId, ProjectKey, bool),
+        /// Contributors have approved an initiator transfer, it can now be accepted.
+        InitiatorTransferApproved(ProjectKey),
+        /// Contributors have voted against an initiator transfer and it has been dropped.
+        InitiatorTransferRejected(ProjectKey),
+        /// The project has a new initiator, (project, old initiator, new initiator).
+        InitiatorTransferred(ProjectKey, T::AccountId, T::AccountId),
+        /// The team of a project has been set, an empty team removes it.
+        ProjectTeamSet(ProjectKey, Vec<T::AccountId>),
+        /// The initiator's bond has been slashed to the contributors of a failed project.
+        InitiatorBondSlashed(ProjectKey, BalanceOf<T>),
+        /// The merkle root of a project's whitelist has been set.
+        WhitelistRootSet(ProjectKey, H256),
+        /// The default identity policy has been set or removed.
+        DefaultIdentityPolicySet,
+        /// The identity policy of a project has been set or removed.
+        ProjectIdentityPolicySet(ProjectKey),
+        /// The refund of a remote contributor could not be sent back to their chain and was kept by their local account.
+        RemoteRefundNotSent(T::AccountId, BalanceOf<T>),
+        /// A refund which could not be sent earlier has been sent back to the chain of a remote contributor.
+        RemoteRefundSent(T::AccountId, BalanceOf<T>),
     }
 
     // Errors inform users that something went wrong.
@@ -289,6 +651,8 @@ pub mod pallet {
         ProjectDoesNotExist,
         /// Milestones totals do not add up to 100%.
         MilestonesTotalPercentageMustEqual100,
+        /// A milestone's due block must be in the future.
+        MilestoneDueBlockInPast,
         /// Currently no active round to participate in.
         NoActiveRound,
         /// There was an overflow in pallet_proposals.
@@ -339,10 +703,111 @@ pub mod pallet {
         ImbueRequiredForStorageDep,
         /// White list spot not found
         WhiteListNotFound,
+        /// Too many rounds are already due to close in the given block, try again later.
+        TooManyRoundsExpiringInBlock,
+        /// The voting window for this milestone has closed.
+        MilestoneVotingWindowClosed,
+        /// The milestone is still being voted on.
+        MilestoneVotingWindowOpen,
+        /// Refunds can only be claimed from a cancelled project.
+        ProjectNotRefundable,
+        /// Cannot withdraw more than has been contributed.
+        WithdrawalExceedsContribution,
+        /// Contributions can no longer be withdrawn as the funding threshold has been met.
+        FundingThresholdMet,
+        /// A matching pool can only hold the currency it was first funded with.
+        MatchingPoolCurrencyMismatch,
+        /// The milestone has used all of its submission attempts and can no longer be submitted.
+        MilestoneSubmissionAttemptsExhausted,
+        /// Votes cannot be changed once the milestone has been approved.
+        MilestoneAlreadyApproved,
+        /// A vote cannot be delegated to yourself.
+        CannotDelegateToSelf,
+        /// The vote is already delegated to this account.
+        AlreadyDelegated,
+        /// The delegate already has the maximum number of delegators on this project.
+        TooManyDelegators,
+        /// The vote on this project has not been delegated.
+        NotDelegated,
+        /// The funding threshold must be a percentage between 1 and 100.
+        InvalidFundingThreshold,
+        /// The project has not raised enough funds to meet its funding threshold.
+        FundingThresholdNotMet,
+        /// The shares of the fee split must add up to 100%.
+        InvalidFeeSplit,
+        /// The fee rate must be a percentage below 100.
+        InvalidFeeRate,
+        /// The account is already a juror.
+        AlreadyJuror,
+        /// The account is not a juror.
+        NotJuror,
+        /// The juror set is full.
+        TooManyJurors,
+        /// There are no jurors who can sit on the arbitration panel.
+        NotEnoughJurors,
+        /// Only a rejected milestone can be disputed by the initiator, and an approved milestone which
+        /// has not been withdrawn by contributors.
+        MilestoneNotDisputable,
+        /// The dispute has already been raised or backed.
+        DisputeAlreadyRaised,
+        /// There is no dispute on this milestone.
+        DisputeNotFound,
+        /// The dispute has too many backers.
+        TooManyDisputeBackers,
+        /// Only jurors on the panel of a dispute can vote on it.
+        NotOnDisputePanel,
+        /// The dispute is not open for arbitration votes.
+        DisputeVotingClosed,
+        /// This cannot be done while a milestone of the project is disputed.
+        MilestoneDisputed,
+        /// The vesting period must be at least one block.
+        InvalidVestingPeriod,
+        /// There is no initiator transfer proposed for this project.
+        NoInitiatorTransfer,
+        /// Only the proposed new initiator can accept the transfer.
+        NotNewInitiator,
+        /// The initiator transfer must be approved by contributors before it can be accepted.
+        InitiatorTransferNotApproved,
+        /// The initiator transfer has already been approved.
+        InitiatorTransferAlreadyApproved,
+        /// Team members must be unique and their payout shares must not add up to more than 100.
+        InvalidTeam,
+        /// The initiator cannot reserve the bond required in the project's currency.
+        InitiatorBondRequired,
+        /// The proof does not show the account and cap are in the project's whitelist.
+        InvalidWhitelistProof,
+        /// The identity does not have a field required by the identity policy set.
+        IdentityFieldMissing,
+        /// The minimum contribution exceeds the maximum per contributor or the hard cap.
+        InvalidContributionLimits,
+        /// The contribution is below the minimum accepted by the project.
+        ContributionBelowMinimum,
+        /// The contribution would take the contributor over the maximum per contributor.
+        ContributionAboveMaximum,
+        /// The contribution would take the project over its hard cap.
+        HardCapReached,
+        /// The location the contribution was sent from cannot be converted into an account.
+        UnknownLocation,
+        /// There is no contribution left to refund.
+        NothingToRefund,
+        /// The project has been cancelled and can no longer be contributed to or withdrawn from.
+        ProjectCancelled,
+        /// The project has reached its maximum number of contributors.
+        TooManyContributors,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+        /// Close every round that ends on this block, finalising any outstanding votes.
+        /// The amount of work is bounded by `ExpiringRoundsPerBlock`.
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            for expiring_round in RoundsExpiring::<T>::take(n) {
+                weight = weight.saturating_add(Self::close_expired_round(expiring_round));
+            }
+            weight
+        }
+
         /// <HB SBP Review:
         /// 
         /// I see this hook valid on testnet but if you will deploy this with weights v2 already, you can totally remove this.
@@ -356,8 +821,32 @@ pub mod pallet {
                 weight += migration::v2::migrate::<T>();
                 StorageVersion::<T>::set(Release::V2);
             }
+            if StorageVersion::<T>::get() == Release::V2 {
+                weight += migration::v3::migrate::<T>();
+                StorageVersion::<T>::set(Release::V3);
+            }
+            if StorageVersion::<T>::get() == Release::V3 {
+                weight += migration::v4::migrate::<T>();
+                StorageVersion::<T>::set(Release::V4);
+            }
+            if StorageVersion::<T>::get() == Release::V4 {
+                weight += migration::v5::migrate::<T>();
+                StorageVersion::<T>::set(Release::V5);
+            }
+            if StorageVersion::<T>::get() == Release::V5 {
+                weight += migration::v6::migrate::<T>();
+                StorageVersion::<T>::set(Release::V6);
+            }
             weight
         }
+
+        fn integrity_test() {
+            // The v5 migration merges milestones over the bound into an approved and a pending milestone.
+            assert!(
+                T::MaxMilestonesPerProject::get() >= 2,
+                "projects must be able to hold at least two milestones"
+            );
+        }
     }
 
     #[pallet::call]
@@ -372,6 +861,7 @@ pub mod pallet {
             proposed_milestones: BoundedProposedMilestones<T>,
             required_funds: BalanceOf<T>,
             currency_id: common_types::CurrencyId,
+            contribution_limits: Option<ContributionLimits<BalanceOf<T>>>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
@@ -385,7 +875,7 @@ pub mod pallet {
             // Validation
             let total_percentage = proposed_milestones
                 .iter()
-                .fold(0, |acc: u32, ms: &ProposedMilestone| {
+                .fold(0, |acc: u32, ms: &ProposedMilestone<T::BlockNumber>| {
                     acc.saturating_add(ms.percentage_to_unlock)
                 });
             ensure!(
@@ -406,6 +896,7 @@ pub mod pallet {
                 required_funds,
                 currency_id,
                 FundingType::Proposal,
+                contribution_limits,
             )?;
             Ok(().into())
         }
@@ -419,6 +910,7 @@ pub mod pallet {
             required_funds: BalanceOf<T>,
             currency_id: CurrencyId,
             agreement_hash: H256,
+            contribution_limits: Option<ContributionLimits<BalanceOf<T>>>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
@@ -431,7 +923,7 @@ pub mod pallet {
             /// >
             let total_percentage = proposed_milestones
                 .iter()
-                .fold(0, |acc: u32, ms: &ProposedMilestone| {
+                .fold(0, |acc: u32, ms: &ProposedMilestone<T::BlockNumber>| {
                     acc.saturating_add(ms.percentage_to_unlock)
                 });
 
@@ -448,6 +940,7 @@ pub mod pallet {
                 required_funds,
                 currency_id,
                 agreement_hash,
+                contribution_limits,
             )?;
 
             Self::deposit_event(Event::ProjectUpdated(who, project_key, required_funds));
@@ -467,8 +960,12 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             Self::ensure_initiator(who, project_key)?;
             let mut project_whitelist_spots =
-                WhitelistSpots::<T>::get(project_key).unwrap_or(BTreeMap::new());
-            project_whitelist_spots.extend(new_whitelist_spots);
+                WhitelistSpots::<T>::get(project_key).unwrap_or_default();
+            for (account_id, max_cap) in new_whitelist_spots {
+                project_whitelist_spots
+                    .try_insert(account_id, max_cap)
+                    .map_err(|_| Error::<T>::ParamLimitExceed)?;
+            }
             <WhitelistSpots<T>>::insert(project_key, project_whitelist_spots);
             let now = <frame_system::Pallet<T>>::block_number();
             Self::deposit_event(Event::WhitelistAdded(project_key, now));
@@ -486,6 +983,7 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             Self::ensure_initiator(who, project_key)?;
             <WhitelistSpots<T>>::remove(project_key);
+            <WhitelistRoots<T>>::remove(project_key);
             let now = <frame_system::Pallet<T>>::block_number();
             Self::deposit_event(Event::WhitelistRemoved(project_key, now));
             Ok(().into())
@@ -500,7 +998,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             start: T::BlockNumber,
             end: T::BlockNumber,
-            project_keys: BoundedProjectKeys,
+            project_keys: BoundedProjectKeys<T>,
             round_type: RoundType,
         ) -> DispatchResultWithPostInfo {
             T::AuthorityOrigin::ensure_origin(origin)?;
@@ -540,6 +1038,9 @@ pub mod pallet {
             ensure!(!round.is_canceled, Error::<T>::RoundCanceled);
 
             round.is_canceled = true;
+            for project_key in round.project_keys.iter() {
+                ProjectRounds::<T>::remove(project_key, round_key);
+            }
             <Rounds<T>>::insert(round_key, Some(round));
 
             Self::deposit_event(Event::RoundCancelled(round_key));
@@ -565,7 +1066,7 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let contribution_round_key = round_key.unwrap_or(RoundCount::<T>::get());
-            Self::new_contribution(who, contribution_round_key, project_key, value)
+            Self::new_contribution(who, contribution_round_key, project_key, value, None)
         }
 
         /// Step 4 (ADMIN)
@@ -591,15 +1092,16 @@ pub mod pallet {
             origin: OriginFor<T>,
             project_key: ProjectKey,
             milestone_key: MilestoneKey,
+            evidence_hash: H256,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            Self::new_milestone_submission(who, project_key, milestone_key)
+            Self::new_milestone_submission(who, project_key, milestone_key, evidence_hash)
         }
 
         /// Step 6 (CONTRIBUTOR/FUNDER)
         /// Vote on a milestone
         #[pallet::call_index(9)]
-        #[pallet::weight(<T as Config>::WeightInfo::vote_on_milestone())]
+        #[pallet::weight(<T as Config>::WeightInfo::vote_on_milestone(T::MaxDelegators::get()))]
         pub fn vote_on_milestone(
             origin: OriginFor<T>,
             project_key: ProjectKey,
@@ -620,6 +1122,8 @@ pub mod pallet {
 
         /// Step 7 (INITATOR)
         /// Finalise the voting on a milestone.
+        /// A milestone is approved as soon as the vote allows it, but is only rejected once its
+        /// voting window has closed.
         #[pallet::call_index(10)]
         #[pallet::weight(<T as Config>::WeightInfo::finalise_milestone_voting())]
         pub fn finalise_milestone_voting(
@@ -648,11 +1152,11 @@ pub mod pallet {
         /// This will start a round which each contributor can vote on.
         /// The round will last as long as set in the Config.
         #[pallet::call_index(12)]
-        #[pallet::weight(<T as Config>::WeightInfo::raise_vote_of_no_confidence())]
+        #[pallet::weight(<T as Config>::WeightInfo::raise_vote_of_no_confidence(T::MaxDelegators::get()))]
         pub fn raise_vote_of_no_confidence(
             origin: OriginFor<T>,
             project_key: ProjectKey,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             Self::raise_no_confidence_round(who, project_key)
         }
@@ -661,13 +1165,13 @@ pub mod pallet {
         /// is_yay is FOR the project's continuation.
         /// so is_yay = false == against the project from continuing perhaps should be flipped.
         #[pallet::call_index(13)]
-        #[pallet::weight(<T as Config>::WeightInfo::vote_on_no_confidence_round())]
+        #[pallet::weight(<T as Config>::WeightInfo::vote_on_no_confidence_round(T::MaxDelegators::get()))]
         pub fn vote_on_no_confidence_round(
             origin: OriginFor<T>,
             round_key: Option<RoundKey>,
             project_key: ProjectKey,
             is_yay: bool,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let voting_round_key = round_key.unwrap_or(RoundCount::<T>::get());
             Self::add_vote_no_confidence(who, voting_round_key, project_key, is_yay)
@@ -692,27 +1196,555 @@ pub mod pallet {
                 T::PercentRequiredForVoteToPass::get(),
             )
         }
+
+        /// Claim back a contribution from a project which has been cancelled, for example
+        /// when it did not raise its required funds before the funding round ended.
+        /// The project is removed and the initiator's storage deposit returned once every
+        /// contributor has been refunded.
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_refund())]
+        pub fn claim_refund(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_claim_refund(who, project_key)
+        }
+
+        /// Reduce or fully withdraw a contribution while the contribution round is still open
+        /// and the project has not yet been approved.
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_contribution())]
+        pub fn withdraw_contribution(
+            origin: OriginFor<T>,
+            round_key: Option<RoundKey>,
+            project_key: ProjectKey,
+            value: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let contribution_round_key = round_key.unwrap_or(RoundCount::<T>::get());
+            Self::do_withdraw_contribution(who, contribution_round_key, project_key, value)
+        }
+
+        /// Add funds to the matching pool of an active contribution round.
+        /// Signed origins fund the pool themselves, the AuthorityOrigin funds it from the treasury.
+        /// The pool is shared between the round's projects using quadratic funding when the round ends.
+        /// Projects which have not met their funding threshold from contributions by then are not matched.
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::fund_matching_pool())]
+        pub fn fund_matching_pool(
+            origin: OriginFor<T>,
+            round_key: RoundKey,
+            currency_id: CurrencyId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = match T::AuthorityOrigin::try_origin(origin) {
+                Ok(_) => T::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)?,
+                Err(origin) => ensure_signed(origin)?,
+            };
+            Self::do_fund_matching_pool(who, round_key, currency_id, amount)
+        }
+
+        /// Delegate your contribution weighted vote on a project to another account.
+        /// Votes cast by the delegate are counted for you unless you vote differently yourself.
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::delegate_vote(T::MaxDelegators::get()))]
+        pub fn delegate_vote(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            delegate: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_delegate_vote(who, project_key, delegate)
+        }
+
+        /// Stop delegating your vote on a project.
+        /// Votes already cast on your behalf are kept, you can change them by voting yourself.
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::undelegate_vote())]
+        pub fn undelegate_vote(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_undelegate_vote(who, project_key)
+        }
+
+        /// Set the percentage of the required funds a project must raise by the end of its funding round to proceed.
+        /// Milestone payouts are then taken from the funds actually raised.
+        /// Can only be called by the initiator before the project has been scheduled into a round.
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_funding_threshold())]
+        pub fn set_funding_threshold(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            funding_threshold: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_set_funding_threshold(who, project_key, funding_threshold)
+        }
+
+        /// Set how the Imbue fee taken from withdrawals is shared out between the treasury, burning and the collator pot.
+        #[pallet::call_index(21)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_fee_split())]
+        pub fn set_fee_split(
+            origin: OriginFor<T>,
+            fee_split: FeeSplit,
+        ) -> DispatchResultWithPostInfo {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            Self::do_set_fee_split(fee_split)
+        }
+
+        /// Set the Imbue fee rate in percent for a funding type, None reverts to the default `ImbueFee`.
+        #[pallet::call_index(22)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_fee_rate())]
+        pub fn set_fee_rate(
+            origin: OriginFor<T>,
+            funding_type: FundingType,
+            fee_rate: Option<u8>,
+        ) -> DispatchResultWithPostInfo {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            Self::do_set_fee_rate(funding_type, fee_rate)
+        }
+
+        /// Cancel your project, refunding contributors their share of the funds locked in unapproved milestones.
+        /// Funds unlocked by approved milestones which have not been withdrawn are paid out first.
+        /// The storage deposit is returned and the project is kept as cancelled.
+        /// A project cannot be cancelled while one of its milestones is disputed.
+        #[pallet::call_index(23)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_project(T::MaximumContributorsPerProject::get()))]
+        pub fn cancel_project(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_cancel_project(who, project_key)
+        }
+
+        /// Add an account to the juror set, reserving the `JurorBond` from it.
+        #[pallet::call_index(24)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_juror())]
+        pub fn add_juror(origin: OriginFor<T>, juror: T::AccountId) -> DispatchResultWithPostInfo {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            Self::do_add_juror(juror)
+        }
+
+        /// Remove an account from the juror set, its bond is sent to the treasury if slashed or returned otherwise.
+        #[pallet::call_index(25)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_juror())]
+        pub fn remove_juror(
+            origin: OriginFor<T>,
+            juror: T::AccountId,
+            slash: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            Self::do_remove_juror(juror, slash)
+        }
+
+        /// Dispute the decision on a milestone, reserving the `DisputeBond`.
+        /// The initiator can dispute a rejected milestone and it goes straight to arbitration.
+        /// Contributors can dispute an approved milestone, it goes to arbitration once the contributors
+        /// backing it hold `DisputeThreshold` percent of the raised funds. Calling this on a dispute
+        /// which is waiting for backers backs it. The bonds of a dispute which is not backed in time
+        /// are sent to the treasury. The arbitration panel is drawn in a later block, from randomness
+        /// which was not known when the dispute was escalated.
+        /// Escalated disputes block withdrawals, the funds of a milestone whose dispute is waiting
+        /// for backers are held back.
+        #[pallet::call_index(26)]
+        #[pallet::weight(<T as Config>::WeightInfo::raise_dispute())]
+        pub fn raise_dispute(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            milestone_key: MilestoneKey,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_raise_dispute(who, project_key, milestone_key)
+        }
+
+        /// Vote on a dispute as a juror on its arbitration panel, true to approve the milestone.
+        /// The dispute is resolved once the whole panel has voted or the voting period ends.
+        #[pallet::call_index(27)]
+        #[pallet::weight(<T as Config>::WeightInfo::vote_on_dispute())]
+        pub fn vote_on_dispute(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            milestone_key: MilestoneKey,
+            approve_milestone: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_vote_on_dispute(who, project_key, milestone_key, approve_milestone)
+        }
+
+        /// Stream the funds of each approved milestone to the initiator linearly over the given number of blocks,
+        /// or pay them out in full on approval if None. Funds which have not vested are refunded if the project
+        /// loses a vote of no confidence.
+        /// Can only be called by the initiator before the project has been scheduled into a round.
+        #[pallet::call_index(28)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_payout_vesting())]
+        pub fn set_payout_vesting(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            vesting_period: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_set_payout_vesting(who, project_key, vesting_period)
+        }
+
+        /// Step 1 of handing a project over to a new initiator, called by the current initiator.
+        /// Projects which have met their funding threshold also need the approval of their contributors.
+        /// Proposing again replaces the previous proposal.
+        #[pallet::call_index(29)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_initiator_transfer())]
+        pub fn propose_initiator_transfer(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            new_initiator: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_propose_initiator_transfer(who, project_key, new_initiator)
+        }
+
+        /// Vote on a proposed initiator transfer as a contributor, weighted by contribution.
+        /// The transfer is approved once `PercentRequiredForVoteToPass` of the raised funds vote for it.
+        #[pallet::call_index(30)]
+        #[pallet::weight(<T as Config>::WeightInfo::vote_on_initiator_transfer())]
+        pub fn vote_on_initiator_transfer(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            approve: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_vote_on_initiator_transfer(who, project_key, approve)
+        }
+
+        /// Step 2 of handing a project over, called by the new initiator.
+        /// The storage deposit is reserved from the new initiator and returned to the old one.
+        #[pallet::call_index(31)]
+        #[pallet::weight(<T as Config>::WeightInfo::accept_initiator_transfer())]
+        pub fn accept_initiator_transfer(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_accept_initiator_transfer(who, project_key)
+        }
+
+        /// Set the team of a project, replacing the previous one. Can be called by the initiator or a team admin,
+        /// though only the initiator can change the payout shares.
+        /// Submitters can submit and finalise milestones, treasurers can withdraw and admins can do both.
+        /// On withdrawal each member is paid their percentage share and the rest goes to the initiator.
+        #[pallet::call_index(32)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_project_team())]
+        pub fn set_project_team(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            team: BoundedTeam<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_set_project_team(who, project_key, team)
+        }
+
+        /// Whitelist a project by the merkle root of its `(account, max_cap)` leaves, see `whitelist_leaf`.
+        /// Contributors must then contribute with a proof of their spot.
+        #[pallet::call_index(33)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_project_whitelist_root())]
+        pub fn set_project_whitelist_root(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            root: H256,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_initiator(who, project_key)?;
+            <WhitelistRoots<T>>::insert(project_key, root);
+            Self::deposit_event(Event::WhitelistRootSet(project_key, root));
+            Ok(().into())
+        }
+
+        /// Contribute to a project whitelisted by a merkle root,
+        /// proving the caller's spot and maximum contribution.
+        #[pallet::call_index(34)]
+        #[pallet::weight(<T as Config>::WeightInfo::contribute_with_proof())]
+        #[transactional]
+        pub fn contribute_with_proof(
+            origin: OriginFor<T>,
+            round_key: Option<RoundKey>,
+            project_key: ProjectKey,
+            value: BalanceOf<T>,
+            max_cap: BalanceOf<T>,
+            proof: Proof<H256>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let contribution_round_key = round_key.unwrap_or(RoundCount::<T>::get());
+            Self::new_contribution(
+                who,
+                contribution_round_key,
+                project_key,
+                value,
+                Some((max_cap, proof)),
+            )
+        }
+
+        /// Set or remove the identity policy applied to projects which do not set their own.
+        #[pallet::call_index(35)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_default_identity_policy())]
+        pub fn set_default_identity_policy(
+            origin: OriginFor<T>,
+            policy: Option<IdentityPolicy<T>>,
+        ) -> DispatchResultWithPostInfo {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            DefaultIdentityPolicy::<T>::set(policy);
+            Self::deposit_event(Event::DefaultIdentityPolicySet);
+            Ok(().into())
+        }
+
+        /// Set or remove the identity policy of a project, before it has been approved.
+        /// The project policy can only tighten the default identity policy.
+        #[pallet::call_index(36)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_project_identity_policy())]
+        pub fn set_project_identity_policy(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+            policy: Option<IdentityPolicy<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_set_project_identity_policy(who, project_key, policy)
+        }
+
+        /// Contribute to a project from an account on a sibling parachain.
+        /// Dispatched by XCM `Transact` after the funds have been deposited to the local account
+        /// derived from the remote one, which the contribution is then made from.
+        #[pallet::call_index(37)]
+        #[pallet::weight(<T as Config>::WeightInfo::contribute_via_xcm())]
+        #[transactional]
+        pub fn contribute_via_xcm(
+            origin: OriginFor<T>,
+            round_key: Option<RoundKey>,
+            project_key: ProjectKey,
+            value: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let (who, location) = Self::ensure_remote_account(origin)?;
+            RemoteContributors::<T>::insert(&who, location);
+            let contribution_round_key = round_key.unwrap_or(RoundCount::<T>::get());
+            Self::new_contribution(who, contribution_round_key, project_key, value, None)
+        }
+
+        /// Claim back a contribution made from an account on a sibling parachain, as `claim_refund`.
+        /// The refund is sent back to the chain the contribution was made from.
+        #[pallet::call_index(38)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_refund_via_xcm())]
+        #[transactional]
+        pub fn claim_refund_via_xcm(
+            origin: OriginFor<T>,
+            project_key: ProjectKey,
+        ) -> DispatchResultWithPostInfo {
+            let (who, _) = Self::ensure_remote_account(origin)?;
+            Self::do_claim_refund(who, project_key)
+        }
+
+        /// Reduce or fully withdraw a contribution made from an account on a sibling parachain,
+        /// as `withdraw_contribution`. The funds are sent back to the chain the contribution was made from.
+        #[pallet::call_index(39)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_contribution_via_xcm())]
+        #[transactional]
+        pub fn withdraw_contribution_via_xcm(
+            origin: OriginFor<T>,
+            round_key: Option<RoundKey>,
+            project_key: ProjectKey,
+            value: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let (who, _) = Self::ensure_remote_account(origin)?;
+            let contribution_round_key = round_key.unwrap_or(RoundCount::<T>::get());
+            Self::do_withdraw_contribution(who, contribution_round_key, project_key, value)
+        }
+
+        /// Send a refund which could not be sent back to the chain of a remote contributor again.
+        /// Can be called by anyone as the funds can only go to the remote contributor.
+        #[pallet::call_index(40)]
+        #[pallet::weight(<T as Config>::WeightInfo::retry_remote_refund())]
+        #[transactional]
+        pub fn retry_remote_refund(
+            origin: OriginFor<T>,
+            contributor: T::AccountId,
+            currency_id: CurrencyId,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+            Self::do_retry_remote_refund(contributor, currency_id)
+        }
     }
 }
 
-#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
 pub enum RoundType {
     ContributionRound,
     VotingRound,
     VoteOfNoConfidence,
 }
 
+/// A round which is closed by the hooks once its end block is reached.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+pub enum ExpiringRound {
+    /// A funding round, closed for every project it contains.
+    Contribution(RoundKey),
+    /// The voting round opened when a milestone is submitted.
+    MilestoneVote(RoundKey, ProjectKey, MilestoneKey),
+    /// A vote of no confidence raised against a project.
+    NoConfidence(RoundKey, ProjectKey),
+    /// A dispute waiting for backers or for its arbitration panel to vote.
+    Dispute(ProjectKey, MilestoneKey),
+}
+
+/// The evidence submitted for a milestone and the history of its votes.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+pub struct MilestoneSubmission<BlockNumber, Balance> {
+    /// The hash of the evidence that the milestone has been delivered.
+    pub evidence_hash: H256,
+    /// The voting round opened by the latest submission.
+    pub round_key: RoundKey,
+    pub submitted_at: BlockNumber,
+    /// The number of times the milestone has been submitted.
+    pub attempts: u32,
+    /// The tally of the last vote that rejected the milestone.
+    pub last_rejection: Option<Vote<Balance>>,
+    /// Set once all attempts have been used without the milestone being approved.
+    pub failed: bool,
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Copy, Debug, TypeInfo, MaxEncodedLen)]
+pub enum TeamRole {
+    /// Can manage the team, submit milestones and withdraw.
+    Admin,
+    /// Can submit and finalise milestones.
+    Submitter,
+    /// Can withdraw the project's unlocked funds.
+    Treasurer,
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+pub struct TeamMember<AccountId> {
+    pub account: AccountId,
+    pub role: TeamRole,
+    /// The percentage of each withdrawal paid to the member.
+    pub payout_share: u8,
+}
+
+/// Limits on the contributions a project accepts, zero meaning no limit.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Copy, Debug, Default, TypeInfo, MaxEncodedLen)]
+pub struct ContributionLimits<Balance> {
+    /// The smallest contribution accepted.
+    pub min_contribution: Balance,
+    /// The most a single contributor can contribute in total.
+    pub max_per_contributor: Balance,
+    /// The most the project can raise.
+    pub hard_cap: Balance,
+}
+
+/// The identity an account must hold to act on a project, set by governance or by the project's initiator.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct IdentityPolicy<T: Config> {
+    /// The registrars whose `Reasonable` or `KnownGood` judgements are accepted, any registrar if empty.
+    pub trusted_registrars:
+        BoundedVec<RegistrarIndex, <T as pallet_identity::Config>::MaxRegistrars>,
+    /// The fields which must be set on the identity.
+    pub required_fields: IdentityFields,
+    /// Whether initiators must hold the identity to create or update a project.
+    pub initiators: bool,
+    /// Contributors must hold the identity once their contribution exceeds this amount.
+    pub contributor_threshold: Option<BalanceOf<T>>,
+    /// Whether brief applicants must hold the identity to start work on their brief.
+    pub brief_applicants: bool,
+}
+
+/// A proposal to hand a project over to a new initiator.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+pub struct InitiatorTransfer<AccountId, Balance> {
+    pub new_initiator: AccountId,
+    /// The contributions voting for and against the transfer.
+    pub yay: Balance,
+    pub nay: Balance,
+    pub approved: bool,
+}
+
+/// Who raised a dispute, and so which way they want the milestone decided.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Copy, Debug, TypeInfo, MaxEncodedLen)]
+pub enum DisputeRaiser {
+    /// The initiator disputes the rejection of a milestone.
+    Initiator,
+    /// Contributors dispute the approval of a milestone.
+    Contributors,
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Copy, Debug, TypeInfo, MaxEncodedLen)]
+pub enum DisputeStatus {
+    /// Waiting for enough contributors to back the dispute.
+    Backing,
+    /// Escalated and waiting for the arbitration panel to be drawn.
+    Drawing,
+    /// Being voted on by an arbitration panel.
+    Arbitration,
+}
+
+/// A milestone decision which has been escalated to arbitration.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct Dispute<T: Config> {
+    pub raised_by: DisputeRaiser,
+    pub status: DisputeStatus,
+    /// The accounts which have reserved the `DisputeBond` to raise or back the dispute.
+    pub backers: BoundedVec<AccountIdOf<T>, T::MaximumContributorsPerProject>,
+    /// The sum of the contributions of the backers.
+    pub support: BalanceOf<T>,
+    pub jurors: BoundedVec<AccountIdOf<T>, T::JurorsPerDispute>,
+    /// The block backing or arbitration closes on.
+    pub deadline: T::BlockNumber,
+    pub approvals: u32,
+    pub rejections: u32,
+}
+
+/// A pool of funds matched against the contributions made to the projects in a round.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+pub struct MatchingPool<Balance> {
+    pub currency_id: CurrencyId,
+    pub amount: Balance,
+}
+
+/// How the Imbue fee taken from each withdrawal is shared out.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+pub struct FeeSplit {
+    /// The share sent to the treasury.
+    pub treasury: Perbill,
+    /// The share which is burnt.
+    pub burn: Perbill,
+    /// The share sent to the collator pot.
+    pub collators: Perbill,
+}
+
+impl Default for FeeSplit {
+    /// Send the whole fee to the treasury.
+    fn default() -> Self {
+        Self {
+            treasury: Perbill::one(),
+            burn: Perbill::zero(),
+            collators: Perbill::zero(),
+        }
+    }
+}
+
 /// <HB SBP Review:
 /// 
 /// I suspect this comes from the weights v2 migration?
 /// 
 /// >
-#[derive(Encode, Decode, TypeInfo, PartialEq)]
+#[derive(Encode, Decode, TypeInfo, PartialEq, MaxEncodedLen)]
 #[repr(u32)]
 pub enum Release {
     V0,
     V1,
     V2,
+    V3,
+    V4,
+    V5,
+    V6,
 }
 
 impl Default for Release {
@@ -721,29 +1753,25 @@ impl Default for Release {
     }
 }
 
-/// <HB SBP Review:
-///
-/// This Round struct is storing an unbounded Vec. Please bound all your vecs.
-///
-/// >
 /// The round struct contains all the data associated with a given round.
 /// A round may include multiple projects.
-#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo)]
-pub struct Round<BlockNumber> {
-    start: BlockNumber,
-    end: BlockNumber,
-    project_keys: Vec<ProjectKey>,
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct Round<T: Config> {
+    start: T::BlockNumber,
+    end: T::BlockNumber,
+    project_keys: BoundedProjectKeys<T>,
     round_type: RoundType,
     is_canceled: bool,
 }
 
-impl<BlockNumber: From<u32>> Round<BlockNumber> {
+impl<T: Config> Round<T> {
     fn new(
-        start: BlockNumber,
-        end: BlockNumber,
-        project_keys: Vec<ProjectKey>,
+        start: T::BlockNumber,
+        end: T::BlockNumber,
+        project_keys: BoundedProjectKeys<T>,
         round_type: RoundType,
-    ) -> Round<BlockNumber> {
+    ) -> Round<T> {
         Round {
             start,
             end,
@@ -762,26 +1790,33 @@ impl<BlockNumber: From<u32>> Round<BlockNumber> {
 /// >
 
 /// The milestones provided by the user to define the milestones of a project.
-/// TODO: add ipfs hash like in the grants pallet and
 /// TODO: move these to a common repo (common_types will do)
-#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
-pub struct ProposedMilestone {
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, Default, TypeInfo, MaxEncodedLen)]
+pub struct ProposedMilestone<BlockNumber> {
     pub percentage_to_unlock: u32,
+    /// The hash of the milestone's title.
+    pub title_hash: H256,
+    /// The IPFS hash of the content describing what the milestone delivers.
+    pub content_hash: H256,
+    /// The block the milestone is expected to be delivered by, if any.
+    pub due_block: Option<BlockNumber>,
 }
 
 /// The contribution users made to a project project.
 /// TODO: move these to a common repo (common_types will do)
-/// TODO: add ipfs hash like in the grants pallet and
 #[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
-pub struct Milestone {
+pub struct Milestone<BlockNumber> {
     pub project_key: ProjectKey,
     pub milestone_key: MilestoneKey,
     pub percentage_to_unlock: u32,
     pub is_approved: bool,
+    pub title_hash: H256,
+    pub content_hash: H256,
+    pub due_block: Option<BlockNumber>,
 }
 
 /// The vote struct is used to
-#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
 pub struct Vote<Balance> {
     yay: Balance,
     nay: Balance,
@@ -804,19 +1839,20 @@ impl<Balance: From<u32>> Default for Vote<Balance> {
 }
 
 /// The struct that holds the descriptive properties of a project.
-#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo)]
-pub struct Project<AccountId, Balance, BlockNumber, Timestamp> {
+/// Contributions are held separately in `ProjectContributions`.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct Project<T: Config> {
     pub agreement_hash: H256,
-    // TODO: BOund
-    pub milestones: BTreeMap<MilestoneKey, Milestone>,
-    // TODO: BOund
-    pub contributions: BTreeMap<AccountId, Contribution<Balance, Timestamp>>,
+    pub milestones: BoundedMilestones<T>,
     pub currency_id: common_types::CurrencyId,
-    pub required_funds: Balance,
-    pub withdrawn_funds: Balance,
-    pub raised_funds: Balance,
-    pub initiator: AccountId,
-    pub created_on: BlockNumber,
+    pub required_funds: BalanceOf<T>,
+    /// The percentage of the required funds which must be raised for the project to proceed.
+    pub funding_threshold: u32,
+    pub withdrawn_funds: BalanceOf<T>,
+    pub raised_funds: BalanceOf<T>,
+    pub initiator: AccountIdOf<T>,
+    pub created_on: T::BlockNumber,
     pub approved_for_funding: bool,
     pub funding_threshold_met: bool,
     pub cancelled: bool,
@@ -1,6 +1,7 @@
 use crate::*;
 use frame_support::{pallet_prelude::OptionQuery, storage_alias, traits::Get, weights::Weight};
 pub use pallet::*;
+use sp_runtime::traits::IntegerSquareRoot;
 
 mod v0 {
     use super::*;
@@ -138,10 +139,18 @@ pub mod v1 {
 pub mod v2 {
     use super::*;
 
+    #[derive(Encode, Clone, Decode)]
+    pub struct MilestoneV2 {
+        pub project_key: ProjectKey,
+        pub milestone_key: MilestoneKey,
+        pub percentage_to_unlock: u32,
+        pub is_approved: bool,
+    }
+
     #[derive(Encode, Clone, Decode)]
     pub struct ProjectV2<AccountId, Balance, BlockNumber, Timestamp> {
         pub agreement_hash: H256,
-        pub milestones: BTreeMap<MilestoneKey, Milestone>,
+        pub milestones: BTreeMap<MilestoneKey, MilestoneV2>,
         pub contributions: BTreeMap<AccountId, Contribution<Balance, Timestamp>>,
         pub currency_id: common_types::CurrencyId,
         pub required_funds: Balance,
@@ -155,13 +164,20 @@ pub mod v2 {
         pub funding_type: FundingType,
     }
 
+    pub type ProjectV2Of<T> =
+        ProjectV2<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>, TimestampOf<T>>;
+
+    #[storage_alias]
+    pub type Projects<T: Config> =
+        StorageMap<Pallet<T>, Identity, ProjectKey, ProjectV2Of<T>, OptionQuery>;
+
     pub fn migrate<T: Config>() -> Weight {
         let mut weight = T::DbWeight::get().reads_writes(1, 1);
-        let mut migrated_milestones: BTreeMap<MilestoneKey, Milestone> = BTreeMap::new();
-        Projects::<T>::translate(|_project_key, project: v1::ProjectV1Of<T>| {
+        let mut migrated_milestones: BTreeMap<MilestoneKey, MilestoneV2> = BTreeMap::new();
+        v2::Projects::<T>::translate(|_project_key, project: v1::ProjectV1Of<T>| {
             let _ = project
                 .milestones.into_values().map(|milestone| {
-                    let migrated_milestone = Milestone {
+                    let migrated_milestone = MilestoneV2 {
                         project_key: milestone.project_key,
                         milestone_key: milestone.milestone_key,
                         percentage_to_unlock: milestone.percentage_to_unlock,
@@ -172,12 +188,7 @@ pub mod v2 {
                 .collect::<Vec<_>>();
 
             weight += T::DbWeight::get().reads_writes(1, 1);
-            let migrated_project: Project<
-                T::AccountId,
-                BalanceOf<T>,
-                T::BlockNumber,
-                TimestampOf<T>,
-            > = Project {
+            let migrated_project: ProjectV2Of<T> = ProjectV2 {
                 milestones: migrated_milestones.clone(),
                 contributions: project.contributions,
                 required_funds: project.required_funds,
@@ -198,9 +209,462 @@ pub mod v2 {
     }
 }
 
+pub mod v3 {
+    use super::*;
+
+    #[derive(Encode, Clone, Decode)]
+    pub struct ProjectV3<AccountId, Balance, BlockNumber, Timestamp> {
+        pub agreement_hash: H256,
+        pub milestones: BTreeMap<MilestoneKey, Milestone<BlockNumber>>,
+        pub contributions: BTreeMap<AccountId, Contribution<Balance, Timestamp>>,
+        pub currency_id: common_types::CurrencyId,
+        pub required_funds: Balance,
+        pub withdrawn_funds: Balance,
+        pub raised_funds: Balance,
+        pub initiator: AccountId,
+        pub created_on: BlockNumber,
+        pub approved_for_funding: bool,
+        pub funding_threshold_met: bool,
+        pub cancelled: bool,
+        pub funding_type: FundingType,
+    }
+
+    pub type ProjectV3Of<T> =
+        ProjectV3<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>, TimestampOf<T>>;
+
+    #[storage_alias]
+    pub type Projects<T: Config> =
+        StorageMap<Pallet<T>, Identity, ProjectKey, ProjectV3Of<T>, OptionQuery>;
+
+    /// Milestones now carry a title hash, a content hash and an optional due block.
+    /// Existing milestones have no metadata so they are given empty hashes and no due block.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        v3::Projects::<T>::translate(|_project_key, project: v2::ProjectV2Of<T>| {
+            weight += T::DbWeight::get().reads_writes(1, 1);
+            let migrated_milestones = project
+                .milestones
+                .into_iter()
+                .map(|(milestone_key, milestone)| {
+                    (
+                        milestone_key,
+                        Milestone {
+                            project_key: milestone.project_key,
+                            milestone_key: milestone.milestone_key,
+                            percentage_to_unlock: milestone.percentage_to_unlock,
+                            is_approved: milestone.is_approved,
+                            title_hash: Default::default(),
+                            content_hash: Default::default(),
+                            due_block: None,
+                        },
+                    )
+                })
+                .collect::<BTreeMap<MilestoneKey, Milestone<BlockNumberFor<T>>>>();
+
+            let migrated_project: ProjectV3Of<T> = ProjectV3 {
+                agreement_hash: project.agreement_hash,
+                milestones: migrated_milestones,
+                contributions: project.contributions,
+                currency_id: project.currency_id,
+                required_funds: project.required_funds,
+                withdrawn_funds: project.withdrawn_funds,
+                raised_funds: project.raised_funds,
+                initiator: project.initiator,
+                created_on: project.created_on,
+                approved_for_funding: project.approved_for_funding,
+                funding_threshold_met: project.funding_threshold_met,
+                cancelled: project.cancelled,
+                funding_type: project.funding_type,
+            };
+            Some(migrated_project)
+        });
+        weight
+    }
+}
+
+pub mod v4 {
+    use super::*;
+
+    #[derive(Encode, Clone, Decode)]
+    pub struct ProjectV4<AccountId, Balance, BlockNumber, Timestamp> {
+        pub agreement_hash: H256,
+        pub milestones: BTreeMap<MilestoneKey, Milestone<BlockNumber>>,
+        pub contributions: BTreeMap<AccountId, Contribution<Balance, Timestamp>>,
+        pub currency_id: common_types::CurrencyId,
+        pub required_funds: Balance,
+        pub funding_threshold: u32,
+        pub withdrawn_funds: Balance,
+        pub raised_funds: Balance,
+        pub initiator: AccountId,
+        pub created_on: BlockNumber,
+        pub approved_for_funding: bool,
+        pub funding_threshold_met: bool,
+        pub cancelled: bool,
+        pub funding_type: FundingType,
+    }
+
+    pub type ProjectV4Of<T> =
+        ProjectV4<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>, TimestampOf<T>>;
+
+    #[storage_alias]
+    pub type Projects<T: Config> =
+        StorageMap<Pallet<T>, Identity, ProjectKey, ProjectV4Of<T>, OptionQuery>;
+
+    #[derive(Encode, Clone, Decode)]
+    pub struct RoundV4<BlockNumber> {
+        pub start: BlockNumber,
+        pub end: BlockNumber,
+        pub project_keys: Vec<ProjectKey>,
+        pub round_type: RoundType,
+        pub is_canceled: bool,
+    }
+
+    /// Projects now carry the percentage of their required funds which must be raised for them to proceed.
+    /// Existing projects had to be fully funded so they are given a threshold of 100%.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        v4::Projects::<T>::translate(|_project_key, project: v3::ProjectV3Of<T>| {
+            weight += T::DbWeight::get().reads_writes(1, 1);
+            let migrated_project: ProjectV4Of<T> = ProjectV4 {
+                agreement_hash: project.agreement_hash,
+                milestones: project.milestones,
+                contributions: project.contributions,
+                currency_id: project.currency_id,
+                required_funds: project.required_funds,
+                funding_threshold: MAX_PERCENTAGE,
+                withdrawn_funds: project.withdrawn_funds,
+                raised_funds: project.raised_funds,
+                initiator: project.initiator,
+                created_on: project.created_on,
+                approved_for_funding: project.approved_for_funding,
+                funding_threshold_met: project.funding_threshold_met,
+                cancelled: project.cancelled,
+                funding_type: project.funding_type,
+            };
+            Some(migrated_project)
+        });
+        weight
+    }
+}
+
+pub mod v5 {
+    use super::*;
+
+    /// Contributions are moved out of the project into `ProjectContributions`, and the milestones
+    /// of a project and the project keys of a round are bounded so the pallet has a max encoded length.
+    /// Both were already limited on input. The milestones of a project over the bound are merged,
+    /// the approved ones into one approved milestone and the rest into one pending milestone, so the
+    /// share of the funds which has been unlocked stays the same. Round keys over the bound are dropped.
+    /// Truncations are counted and logged.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        let max_milestones = <T as Config>::MaxMilestonesPerProject::get() as usize;
+
+        let mut truncated_projects: u32 = 0;
+        Projects::<T>::translate(|project_key, project: v4::ProjectV4Of<T>| {
+            weight += T::DbWeight::get().reads_writes(1, 1);
+            for (account_id, contribution) in project.contributions {
+                weight += T::DbWeight::get().writes(1);
+                ProjectContributions::<T>::insert(project_key, account_id, contribution);
+            }
+            let mut milestones = project
+                .milestones
+                .into_iter()
+                .collect::<Vec<(MilestoneKey, Milestone<BlockNumberFor<T>>)>>();
+            if milestones.len() > max_milestones {
+                truncated_projects = truncated_projects.saturating_add(1);
+                // The integrity test ensures there is room for both merged milestones.
+                let merged = milestones.split_off(max_milestones.saturating_sub(2));
+                let (approved, pending): (Vec<_>, Vec<_>) = merged
+                    .into_iter()
+                    .partition(|(_, milestone)| milestone.is_approved);
+                for group in [approved, pending] {
+                    let mut group = group.into_iter();
+                    if let Some((milestone_key, mut first)) = group.next() {
+                        for (_, milestone) in group {
+                            first.percentage_to_unlock = first
+                                .percentage_to_unlock
+                                .saturating_add(milestone.percentage_to_unlock);
+                        }
+                        milestones.push((milestone_key, first));
+                    }
+                }
+            }
+            let milestones: BoundedMilestones<T> = milestones
+                .into_iter()
+                .collect::<BTreeMap<MilestoneKey, Milestone<BlockNumberFor<T>>>>()
+                .try_into()
+                .unwrap_or_default();
+
+            let migrated_project: Project<T> = Project {
+                agreement_hash: project.agreement_hash,
+                milestones,
+                currency_id: project.currency_id,
+                required_funds: project.required_funds,
+                funding_threshold: project.funding_threshold,
+                withdrawn_funds: project.withdrawn_funds,
+                raised_funds: project.raised_funds,
+                initiator: project.initiator,
+                created_on: project.created_on,
+                approved_for_funding: project.approved_for_funding,
+                funding_threshold_met: project.funding_threshold_met,
+                cancelled: project.cancelled,
+                funding_type: project.funding_type,
+            };
+            Some(migrated_project)
+        });
+
+        let max_project_keys = <T as Config>::MaxProjectsPerRound::get() as usize;
+        let mut truncated_rounds: u32 = 0;
+        Rounds::<T>::translate(
+            |_round_key, round: Option<v4::RoundV4<BlockNumberFor<T>>>| {
+                weight += T::DbWeight::get().reads_writes(1, 1);
+                let migrated_round = round.map(|round| {
+                    if round.project_keys.len() > max_project_keys {
+                        truncated_rounds = truncated_rounds.saturating_add(1);
+                    }
+                    Round {
+                        start: round.start,
+                        end: round.end,
+                        project_keys: BoundedVec::truncate_from(round.project_keys),
+                        round_type: round.round_type,
+                        is_canceled: round.is_canceled,
+                    }
+                });
+                Some(migrated_round)
+            },
+        );
+
+        if truncated_projects > 0 || truncated_rounds > 0 {
+            log::warn!(
+                target: "proposals",
+                "migrated to v5, merged the milestones of {:?} projects and dropped project keys from {:?} rounds",
+                truncated_projects,
+                truncated_rounds
+            );
+        }
+        weight
+    }
+}
+
+pub mod v6 {
+    use super::*;
+    use sp_std::collections::btree_set::BTreeSet;
+
+    #[storage_alias]
+    pub type RefundQueue<T: Config> = StorageValue<
+        Pallet<T>,
+        Vec<(
+            AccountIdOf<T>,
+            ProjectAccountId<T>,
+            BalanceOf<T>,
+            CurrencyId,
+        )>,
+        OptionQuery,
+    >;
+
+    /// Refunds are now claimed by the contributors so the refund queue is no longer processed by the hooks.
+    /// Any refunds still queued are paid out here before the queue is removed.
+    /// Rounds are now closed by the hooks and milestone votes have a deadline, so the rounds which are
+    /// still open are scheduled to expire, indexed by project and their milestone votes are given the
+    /// round end as deadline.
+    /// Projects whose funding rounds ended without meeting their threshold are failed here, as their
+    /// rounds are no longer scheduled.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        weight += drain_refund_queue::<T>();
+        weight += backfill_round_expiries::<T>();
+        weight += fail_expired_underfunded_projects::<T>();
+        weight += count_project_contributors::<T>();
+        weight
+    }
+
+    /// Contributors are now counted per project so the number of contributors can be bounded,
+    /// and the square roots of their contributions are summed so matching does not walk them.
+    pub(crate) fn count_project_contributors<T: Config>() -> Weight {
+        let mut weight = Weight::zero();
+        let mut counts: BTreeMap<ProjectKey, u32> = BTreeMap::new();
+        let mut root_sums: BTreeMap<ProjectKey, BalanceOf<T>> = BTreeMap::new();
+        for (project_key, _, contribution) in ProjectContributions::<T>::iter() {
+            weight += T::DbWeight::get().reads(1);
+            let count = counts.entry(project_key).or_default();
+            *count = count.saturating_add(1);
+            let sum = root_sums.entry(project_key).or_default();
+            *sum = sum.saturating_add(contribution.value.integer_sqrt());
+        }
+        for (project_key, sum) in root_sums {
+            weight += T::DbWeight::get().writes(1);
+            ContributionRootSums::<T>::insert(project_key, sum);
+        }
+        for (project_key, count) in counts {
+            if count > <T as Config>::MaximumContributorsPerProject::get() {
+                log::warn!(
+                    target: "proposals",
+                    "project {:?} has {:?} contributors, more than the maximum",
+                    project_key,
+                    count
+                );
+            }
+            weight += T::DbWeight::get().writes(1);
+            ProjectContributorCount::<T>::insert(project_key, count);
+        }
+        weight
+    }
+
+    pub(crate) fn drain_refund_queue<T: Config>() -> Weight {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        let refunds = v6::RefundQueue::<T>::take().unwrap_or_default();
+        for (who, project_account_id, amount, currency_id) in refunds {
+            weight += T::DbWeight::get().reads_writes(2, 2);
+            if let Err(e) =
+                T::MultiCurrency::transfer(currency_id, &project_account_id, &who, amount)
+            {
+                log::warn!(
+                    target: "proposals",
+                    "queued refund of {:?} to {:?} could not be paid: {:?}",
+                    amount,
+                    who,
+                    e
+                );
+            }
+        }
+        weight
+    }
+
+    pub(crate) fn backfill_round_expiries<T: Config>() -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let now = frame_system::Pallet::<T>::block_number();
+
+        let open_votes: Vec<(ProjectKey, MilestoneKey)> = MilestoneVotes::<T>::iter()
+            .filter(|(vote_lookup_key, vote)| {
+                !vote.is_approved && !MilestoneVotingDeadlines::<T>::contains_key(vote_lookup_key)
+            })
+            .map(|(vote_lookup_key, _)| vote_lookup_key)
+            .collect();
+        weight += T::DbWeight::get().reads(open_votes.len() as u64);
+
+        // A milestone may have been submitted more than once, only its latest voting round is kept.
+        let mut vote_deadlines: BTreeMap<
+            (ProjectKey, MilestoneKey),
+            (RoundKey, BlockNumberFor<T>),
+        > = BTreeMap::new();
+        for (round_key, round) in Rounds::<T>::iter() {
+            weight += T::DbWeight::get().reads(1);
+            let round = match round {
+                Some(round) if !round.is_canceled && round.end >= now => round,
+                _ => continue,
+            };
+            for project_key in round.project_keys.iter() {
+                ProjectRounds::<T>::insert(project_key, round_key, ());
+            }
+            weight += T::DbWeight::get().writes(round.project_keys.len() as u64);
+            match round.round_type {
+                RoundType::ContributionRound => {
+                    weight +=
+                        schedule_if_missing::<T>(round.end, ExpiringRound::Contribution(round_key));
+                }
+                RoundType::VoteOfNoConfidence => {
+                    for project_key in round.project_keys.iter() {
+                        weight += schedule_if_missing::<T>(
+                            round.end,
+                            ExpiringRound::NoConfidence(round_key, *project_key),
+                        );
+                    }
+                }
+                RoundType::VotingRound => {
+                    for vote_lookup_key in open_votes
+                        .iter()
+                        .filter(|(project_key, _)| round.project_keys.contains(project_key))
+                    {
+                        let latest = vote_deadlines
+                            .get(vote_lookup_key)
+                            .map_or(true, |(_, end)| *end < round.end);
+                        if latest {
+                            vote_deadlines.insert(*vote_lookup_key, (round_key, round.end));
+                        }
+                    }
+                }
+            }
+        }
+
+        for ((project_key, milestone_key), (round_key, end)) in vote_deadlines {
+            weight += T::DbWeight::get().writes(1);
+            MilestoneVotingDeadlines::<T>::insert((project_key, milestone_key), end);
+            weight += schedule_if_missing::<T>(
+                end,
+                ExpiringRound::MilestoneVote(round_key, project_key, milestone_key),
+            );
+        }
+        weight
+    }
+
+    /// Fail the underfunded projects of contribution rounds which ended before the upgrade,
+    /// unless the project is still being funded in a round which is open.
+    pub(crate) fn fail_expired_underfunded_projects<T: Config>() -> Weight {
+        let mut weight = Weight::zero();
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut expired: Vec<(RoundKey, ProjectKey)> = Vec::new();
+        let mut still_funding: BTreeSet<ProjectKey> = BTreeSet::new();
+        for (round_key, round) in Rounds::<T>::iter() {
+            weight += T::DbWeight::get().reads(1);
+            let round = match round {
+                Some(round)
+                    if !round.is_canceled && round.round_type == RoundType::ContributionRound =>
+                {
+                    round
+                }
+                _ => continue,
+            };
+            for project_key in round.project_keys.iter() {
+                if round.end < now {
+                    expired.push((round_key, *project_key));
+                } else {
+                    still_funding.insert(*project_key);
+                }
+            }
+        }
+
+        for (round_key, project_key) in expired {
+            if still_funding.contains(&project_key) {
+                continue;
+            }
+            weight += T::DbWeight::get().reads_writes(4, 4);
+            if let Err(e) = Pallet::<T>::fail_underfunded_project(round_key, project_key) {
+                log::warn!(
+                    target: "proposals",
+                    "project {:?} could not be failed: {:?}",
+                    project_key,
+                    e
+                );
+            }
+        }
+        weight
+    }
+
+    fn schedule_if_missing<T: Config>(
+        at: BlockNumberFor<T>,
+        expiring_round: ExpiringRound,
+    ) -> Weight {
+        if RoundsExpiring::<T>::get(at).contains(&expiring_round) {
+            return T::DbWeight::get().reads(1);
+        }
+        if let Err(e) = Pallet::<T>::schedule_round_expiry(at, expiring_round.clone()) {
+            log::warn!(
+                target: "proposals",
+                "{:?} could not be scheduled to expire at {:?}: {:?}",
+                expiring_round,
+                at,
+                e
+            );
+        }
+        T::DbWeight::get().reads_writes(2, 1)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use frame_support::{assert_ok, bounded_vec};
     use mock::*;
 
     use v0::{ContributionV0, MilestoneV0, ProjectV0};
@@ -338,7 +802,7 @@ mod test {
             };
             v1::Projects::<Test>::insert(project_key, &old_project);
             let _ = v2::migrate::<Test>();
-            let migrated_project = Projects::<Test>::get(project_key).unwrap();
+            let migrated_project = v2::Projects::<Test>::get(project_key).unwrap();
 
             assert_eq!(old_project.create_block_number, migrated_project.created_on);
 
@@ -351,4 +815,474 @@ mod test {
             assert_eq!(FundingType::Proposal, migrated_project.funding_type);
         })
     }
+
+    #[test]
+    fn migrate_v2_to_v3() {
+        build_test_externality().execute_with(|| {
+            let project_key = 1;
+            let mut milestones: BTreeMap<MilestoneKey, v2::MilestoneV2> = BTreeMap::new();
+            milestones.insert(
+                0,
+                v2::MilestoneV2 {
+                    project_key,
+                    milestone_key: 0,
+                    percentage_to_unlock: 40,
+                    is_approved: true,
+                },
+            );
+            milestones.insert(
+                1,
+                v2::MilestoneV2 {
+                    project_key,
+                    milestone_key: 1,
+                    percentage_to_unlock: 60,
+                    is_approved: false,
+                },
+            );
+
+            let old_project = v2::ProjectV2 {
+                agreement_hash: H256::from([1u8; 32]),
+                milestones,
+                contributions: BTreeMap::new(),
+                currency_id: CurrencyId::KSM,
+                required_funds: (100_000_000u32).into(),
+                withdrawn_funds: (0u32).into(),
+                raised_funds: (100_000_000u32).into(),
+                initiator: *ALICE,
+                created_on: 100u64,
+                approved_for_funding: true,
+                funding_threshold_met: true,
+                cancelled: false,
+                funding_type: FundingType::Brief,
+            };
+            v2::Projects::<Test>::insert(project_key, &old_project);
+            let _ = v3::migrate::<Test>();
+            let migrated_project = v3::Projects::<Test>::get(project_key).unwrap();
+
+            assert_eq!(old_project.agreement_hash, migrated_project.agreement_hash);
+            assert_eq!(old_project.funding_type, migrated_project.funding_type);
+            assert_eq!(old_project.created_on, migrated_project.created_on);
+            assert_eq!(
+                migrated_project.milestones.get(&1).unwrap(),
+                &Milestone {
+                    project_key,
+                    milestone_key: 1,
+                    percentage_to_unlock: 60,
+                    is_approved: false,
+                    title_hash: H256::default(),
+                    content_hash: H256::default(),
+                    due_block: None,
+                }
+            );
+            assert!(migrated_project.milestones.get(&0).unwrap().is_approved);
+        })
+    }
+
+    #[test]
+    fn migrate_v3_to_v4() {
+        build_test_externality().execute_with(|| {
+            let project_key = 1;
+            let mut milestones: BTreeMap<MilestoneKey, Milestone<BlockNumber>> = BTreeMap::new();
+            milestones.insert(
+                0,
+                Milestone {
+                    project_key,
+                    milestone_key: 0,
+                    percentage_to_unlock: 100,
+                    is_approved: false,
+                    title_hash: H256::from([2u8; 32]),
+                    content_hash: H256::from([3u8; 32]),
+                    due_block: Some(200u64),
+                },
+            );
+
+            let old_project = v3::ProjectV3 {
+                agreement_hash: H256::from([1u8; 32]),
+                milestones: milestones.clone(),
+                contributions: BTreeMap::new(),
+                currency_id: CurrencyId::KSM,
+                required_funds: (100_000_000u32).into(),
+                withdrawn_funds: (0u32).into(),
+                raised_funds: (100_000_000u32).into(),
+                initiator: *ALICE,
+                created_on: 100u64,
+                approved_for_funding: true,
+                funding_threshold_met: true,
+                cancelled: false,
+                funding_type: FundingType::Proposal,
+            };
+            v3::Projects::<Test>::insert(project_key, &old_project);
+            let _ = v4::migrate::<Test>();
+            let migrated_project = v4::Projects::<Test>::get(project_key).unwrap();
+
+            assert_eq!(migrated_project.funding_threshold, MAX_PERCENTAGE);
+            assert_eq!(migrated_project.milestones, milestones);
+            assert_eq!(old_project.required_funds, migrated_project.required_funds);
+            assert_eq!(old_project.initiator, migrated_project.initiator);
+        })
+    }
+
+    #[test]
+    fn migrate_v4_to_v5() {
+        build_test_externality().execute_with(|| {
+            let project_key = 1;
+            let mut milestones: BTreeMap<MilestoneKey, Milestone<BlockNumber>> = BTreeMap::new();
+            milestones.insert(
+                0,
+                Milestone {
+                    project_key,
+                    milestone_key: 0,
+                    percentage_to_unlock: 100,
+                    is_approved: false,
+                    title_hash: H256::from([2u8; 32]),
+                    content_hash: H256::from([3u8; 32]),
+                    due_block: None,
+                },
+            );
+            let mut contributions: BTreeMap<AccountId, Contribution<Balance, Moment>> =
+                BTreeMap::new();
+            contributions.insert(
+                *BOB,
+                Contribution {
+                    value: 60_000_000u64,
+                    timestamp: 1,
+                },
+            );
+            contributions.insert(
+                *CHARLIE,
+                Contribution {
+                    value: 40_000_000u64,
+                    timestamp: 2,
+                },
+            );
+
+            let old_project = v4::ProjectV4 {
+                agreement_hash: H256::from([1u8; 32]),
+                milestones: milestones.clone(),
+                contributions: contributions.clone(),
+                currency_id: CurrencyId::KSM,
+                required_funds: (100_000_000u32).into(),
+                funding_threshold: 80,
+                withdrawn_funds: (0u32).into(),
+                raised_funds: (100_000_000u32).into(),
+                initiator: *ALICE,
+                created_on: 100u64,
+                approved_for_funding: true,
+                funding_threshold_met: true,
+                cancelled: false,
+                funding_type: FundingType::Proposal,
+            };
+            v4::Projects::<Test>::insert(project_key, &old_project);
+
+            let round_key = 1;
+            let old_round = v4::RoundV4 {
+                start: 10u64,
+                end: 20u64,
+                project_keys: vec![project_key],
+                round_type: RoundType::ContributionRound,
+                is_canceled: false,
+            };
+            frame_support::storage::unhashed::put(
+                &Rounds::<Test>::hashed_key_for(round_key),
+                &Some(old_round),
+            );
+
+            let _ = v5::migrate::<Test>();
+            let migrated_project = Projects::<Test>::get(project_key).unwrap();
+
+            assert_eq!(migrated_project.milestones.into_inner(), milestones);
+            assert_eq!(migrated_project.funding_threshold, 80);
+            assert_eq!(old_project.raised_funds, migrated_project.raised_funds);
+            assert_eq!(old_project.initiator, migrated_project.initiator);
+            assert_eq!(
+                ProjectContributions::<Test>::iter_prefix(project_key)
+                    .collect::<BTreeMap<AccountId, Contribution<Balance, Moment>>>(),
+                contributions
+            );
+
+            let migrated_round = Rounds::<Test>::get(round_key).unwrap();
+            assert_eq!(migrated_round.project_keys.into_inner(), vec![project_key]);
+            assert_eq!(migrated_round.end, 20u64);
+        })
+    }
+
+    fn v4_project_with_milestones(
+        milestone_count: u32,
+        raised_funds: Balance,
+    ) -> v4::ProjectV4<AccountId, Balance, BlockNumber, Moment> {
+        // Every milestone unlocks 1% apart from the last which unlocks the rest.
+        let milestones = (0..milestone_count)
+            .map(|milestone_key| {
+                let percentage_to_unlock = if milestone_key + 1 == milestone_count {
+                    100 - (milestone_count - 1)
+                } else {
+                    1
+                };
+                (
+                    milestone_key,
+                    Milestone {
+                        project_key: 1,
+                        milestone_key,
+                        percentage_to_unlock,
+                        is_approved: false,
+                        title_hash: H256::default(),
+                        content_hash: H256::default(),
+                        due_block: None,
+                    },
+                )
+            })
+            .collect();
+        v4::ProjectV4 {
+            agreement_hash: H256::from([1u8; 32]),
+            milestones,
+            contributions: BTreeMap::new(),
+            currency_id: CurrencyId::KSM,
+            required_funds: (100_000_000u32).into(),
+            funding_threshold: 100,
+            withdrawn_funds: (0u32).into(),
+            raised_funds,
+            initiator: *ALICE,
+            created_on: 100u64,
+            approved_for_funding: false,
+            funding_threshold_met: false,
+            cancelled: false,
+            funding_type: FundingType::Proposal,
+        }
+    }
+
+    #[test]
+    fn migrate_v4_to_v5_merges_milestones_over_the_bound() {
+        build_test_externality().execute_with(|| {
+            let max_milestones = <Test as Config>::MaxMilestonesPerProject::get();
+            v4::Projects::<Test>::insert(1, v4_project_with_milestones(max_milestones + 1, 0));
+
+            let _ = v5::migrate::<Test>();
+
+            // None of the milestones are approved so they are merged into a single pending milestone.
+            let migrated_project = Projects::<Test>::get(1).unwrap();
+            assert_eq!(migrated_project.milestones.len() as u32, max_milestones - 1);
+            assert_eq!(
+                migrated_project
+                    .milestones
+                    .values()
+                    .map(|milestone| milestone.percentage_to_unlock)
+                    .sum::<u32>(),
+                100
+            );
+        })
+    }
+
+    #[test]
+    fn migrate_v4_to_v5_keeps_the_unlocked_share_of_funded_projects() {
+        build_test_externality().execute_with(|| {
+            let max_milestones = <Test as Config>::MaxMilestonesPerProject::get();
+            let mut old_project = v4_project_with_milestones(max_milestones + 1, 1_000_000u64);
+            old_project.withdrawn_funds = 10_000u64;
+            // One approved milestone is kept as it is and another is merged.
+            for milestone_key in [0, max_milestones - 1] {
+                old_project
+                    .milestones
+                    .get_mut(&milestone_key)
+                    .unwrap()
+                    .is_approved = true;
+            }
+            v4::Projects::<Test>::insert(1, &old_project);
+
+            let _ = v5::migrate::<Test>();
+
+            let migrated_project = Projects::<Test>::get(1).unwrap();
+            assert_eq!(migrated_project.milestones.len() as u32, max_milestones);
+            let unlocked = |approved: bool| {
+                migrated_project
+                    .milestones
+                    .values()
+                    .filter(|milestone| milestone.is_approved == approved)
+                    .map(|milestone| milestone.percentage_to_unlock)
+                    .sum::<u32>()
+            };
+            assert_eq!(unlocked(true), 2);
+            assert_eq!(unlocked(true) + unlocked(false), 100);
+            assert_eq!(migrated_project.withdrawn_funds, 10_000u64);
+            assert_eq!(migrated_project.raised_funds, 1_000_000u64);
+        })
+    }
+
+    #[test]
+    fn migrate_v5_to_v6_pays_queued_refunds() {
+        build_test_externality().execute_with(|| {
+            let project_account_id: AccountId = Proposals::project_account_id(1);
+            let refund = 1_000_000u64;
+            let _ = Tokens::deposit(CurrencyId::Native, &project_account_id, refund);
+            let bob_balance = Tokens::free_balance(CurrencyId::Native, &*BOB);
+            v6::RefundQueue::<Test>::put(vec![(
+                *BOB,
+                project_account_id,
+                refund,
+                CurrencyId::Native,
+            )]);
+
+            let _ = v6::migrate::<Test>();
+
+            assert!(v6::RefundQueue::<Test>::get().is_none());
+            assert_eq!(
+                Tokens::free_balance(CurrencyId::Native, &*BOB),
+                bob_balance + refund
+            );
+            assert_eq!(
+                Tokens::free_balance(CurrencyId::Native, &project_account_id),
+                0
+            );
+        })
+    }
+
+    #[test]
+    fn migrate_v5_to_v6_counts_contributors() {
+        build_test_externality().execute_with(|| {
+            let contribution = Contribution {
+                value: 1_000_000u64,
+                timestamp: 1,
+            };
+            ProjectContributions::<Test>::insert(1, *BOB, contribution.clone());
+            ProjectContributions::<Test>::insert(1, *CHARLIE, contribution.clone());
+            ProjectContributions::<Test>::insert(2, *BOB, contribution);
+
+            let _ = v6::migrate::<Test>();
+
+            assert_eq!(ProjectContributorCount::<Test>::get(1), 2);
+            assert_eq!(ProjectContributorCount::<Test>::get(2), 1);
+            assert_eq!(ProjectContributorCount::<Test>::get(3), 0);
+            assert_eq!(ContributionRootSums::<Test>::get(1), 2_000);
+            assert_eq!(ContributionRootSums::<Test>::get(2), 1_000);
+        })
+    }
+
+    #[test]
+    fn migrate_v5_to_v6_schedules_open_rounds() {
+        build_test_externality().execute_with(|| {
+            let project_key = 1;
+            let now = frame_system::Pallet::<Test>::block_number();
+            let contribution_end = now + 10;
+            let vote_end = now + 20;
+            Rounds::<Test>::insert(
+                1,
+                Some(Round::<Test>::new(
+                    now,
+                    contribution_end,
+                    bounded_vec![project_key],
+                    RoundType::ContributionRound,
+                )),
+            );
+            Rounds::<Test>::insert(
+                2,
+                Some(Round::<Test>::new(
+                    now,
+                    vote_end,
+                    bounded_vec![project_key],
+                    RoundType::VotingRound,
+                )),
+            );
+            // A round which ended before the upgrade is left as it is.
+            Rounds::<Test>::insert(
+                3,
+                Some(Round::<Test>::new(
+                    0,
+                    0,
+                    bounded_vec![project_key],
+                    RoundType::VotingRound,
+                )),
+            );
+            MilestoneVotes::<Test>::insert(
+                (project_key, 0),
+                Vote {
+                    yay: 0,
+                    nay: 0,
+                    is_approved: false,
+                },
+            );
+
+            let _ = v6::migrate::<Test>();
+
+            assert_eq!(
+                MilestoneVotingDeadlines::<Test>::get((project_key, 0)),
+                Some(vote_end)
+            );
+            assert_eq!(
+                RoundsExpiring::<Test>::get(contribution_end).into_inner(),
+                vec![ExpiringRound::Contribution(1)]
+            );
+            assert_eq!(
+                RoundsExpiring::<Test>::get(vote_end).into_inner(),
+                vec![ExpiringRound::MilestoneVote(2, project_key, 0)]
+            );
+
+            assert!(ProjectRounds::<Test>::contains_key(project_key, 1));
+            assert!(ProjectRounds::<Test>::contains_key(project_key, 2));
+            assert!(!ProjectRounds::<Test>::contains_key(project_key, 3));
+
+            // Running the migration again does not schedule the rounds twice.
+            let _ = v6::backfill_round_expiries::<Test>();
+            assert_eq!(RoundsExpiring::<Test>::get(contribution_end).len(), 1);
+            assert_eq!(RoundsExpiring::<Test>::get(vote_end).len(), 1);
+        })
+    }
+
+    #[test]
+    fn migrate_v5_to_v6_fails_projects_whose_funding_round_ended() {
+        build_test_externality().execute_with(|| {
+            let mut project_keys = vec![];
+            for _ in 0..2 {
+                project_keys.push(ProjectCount::<Test>::get());
+                assert_ok!(Proposals::create_project(
+                    RuntimeOrigin::signed(*ALICE),
+                    H256::from([1u8; 32]),
+                    bounded_vec![ProposedMilestone {
+                        percentage_to_unlock: 100,
+                        ..Default::default()
+                    }],
+                    1_000_000u64,
+                    CurrencyId::Native,
+                    None,
+                ));
+                ProjectContributions::<Test>::insert(
+                    project_keys.last().unwrap(),
+                    *BOB,
+                    Contribution {
+                        value: 100_000u64,
+                        timestamp: 1,
+                    },
+                );
+            }
+            let alice_reserved = Tokens::reserved_balance(CurrencyId::Native, &ALICE);
+            frame_system::Pallet::<Test>::set_block_number(10);
+            Rounds::<Test>::insert(
+                1,
+                Some(Round::<Test>::new(
+                    0,
+                    5,
+                    project_keys.clone().try_into().unwrap(),
+                    RoundType::ContributionRound,
+                )),
+            );
+            // The second project is still being funded in a round which is open.
+            Rounds::<Test>::insert(
+                2,
+                Some(Round::<Test>::new(
+                    5,
+                    20,
+                    bounded_vec![project_keys[1]],
+                    RoundType::ContributionRound,
+                )),
+            );
+
+            let _ = v6::migrate::<Test>();
+
+            assert!(Projects::<Test>::get(project_keys[0]).unwrap().cancelled);
+            assert!(!Projects::<Test>::get(project_keys[1]).unwrap().cancelled);
+            // The storage deposit is held until the refunds have been claimed.
+            assert_eq!(
+                Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+                alice_reserved
+            );
+        })
+    }
 }
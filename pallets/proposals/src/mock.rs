@@ -70,6 +70,24 @@ orml_traits::parameter_type_with_key! {
     };
 }
 
+orml_traits::parameter_type_with_key! {
+    pub MinimumContribution: |currency_id: CurrencyId| -> Balance {
+        match currency_id {
+            CurrencyId::KSM => 1_000,
+            _ => 0,
+        }
+    };
+}
+
+orml_traits::parameter_type_with_key! {
+    pub InitiatorBond: |currency_id: CurrencyId| -> Balance {
+        match currency_id {
+            CurrencyId::KSM => 10_000,
+            _ => 0,
+        }
+    };
+}
+
 parameter_types! {
     pub DustAccount: AccountId = PalletId(*b"orml/dst").into_account_truncating();
     pub MaxLocks: u32 = 2;
@@ -187,12 +205,22 @@ parameter_types! {
     pub NoConfidenceTimeLimit: BlockNumber = 100800u32.into();
     pub PercentRequiredForVoteToPass: u8 = 75u8;
     pub MaximumContributorsPerProject: u32 = 5000;
-    pub RefundsPerBlock: u8 = 2;
     pub IsIdentityRequired: bool = false;
     pub MilestoneVotingWindow: BlockNumber  =  100800u64;
     pub MaxMilestonesPerProject: u32 = 50;
     pub ProjectStorageDeposit: Balance = 100;
     pub ImbueFee: u8 = 5;
+    pub ExpiringRoundsPerBlock: u32 = 100;
+    pub MaxMilestoneSubmissionAttempts: u32 = 3;
+    pub CollatorPot: AccountId = PalletId(*b"PotStake").into_account_truncating();
+    pub DisputeBond: Balance = 1000;
+    pub DisputeThreshold: u8 = 50;
+    pub DisputeVotingPeriod: BlockNumber = 100;
+    pub JurorBond: Balance = 1000;
+    pub MaxJurors: u32 = 10;
+    pub JurorsPerDispute: u32 = 3;
+    pub MaxTeamMembers: u32 = 10;
+    pub MaxDelegators: u32 = 50;
 }
 
 impl pallet_proposals::Config for Test {
@@ -207,12 +235,28 @@ impl pallet_proposals::Config for Test {
     type NoConfidenceTimeLimit = NoConfidenceTimeLimit;
     type PercentRequiredForVoteToPass = PercentRequiredForVoteToPass;
     type MaximumContributorsPerProject = MaximumContributorsPerProject;
-    type RefundsPerBlock = RefundsPerBlock;
     type IsIdentityRequired = IsIdentityRequired;
     type MilestoneVotingWindow = MilestoneVotingWindow;
     type RefundHandler = pallet_proposals::traits::MockRefundHandler<Test>;
     type MaxMilestonesPerProject = MaxMilestonesPerProject;
     type ImbueFee = ImbueFee;
+    type ExpiringRoundsPerBlock = ExpiringRoundsPerBlock;
+    type MaxMilestoneSubmissionAttempts = MaxMilestoneSubmissionAttempts;
+    type CollatorPot = CollatorPot;
+    type DisputeBond = DisputeBond;
+    type DisputeThreshold = DisputeThreshold;
+    type DisputeVotingPeriod = DisputeVotingPeriod;
+    type JurorBond = JurorBond;
+    type MaxJurors = MaxJurors;
+    type JurorsPerDispute = JurorsPerDispute;
+    type MaxTeamMembers = MaxTeamMembers;
+    type MaxDelegators = MaxDelegators;
+    type InitiatorBond = InitiatorBond;
+    type MinimumContribution = MinimumContribution;
+    type ContributionReceipts = pallet_proposals::traits::MockContributionReceipts<Test>;
+    type XcmOrigin = pallet_proposals::traits::MockXcmOrigin<Test>;
+    type LocationToAccountId = pallet_proposals::traits::MockLocationToAccountId<Test>;
+    type Randomness = pallet_proposals::traits::MockRandomness<Test>;
     type ProjectStorageDeposit = ProjectStorageDeposit;
 }
 
@@ -0,0 +1,35 @@
+use crate::{Contribution, MilestoneKey, ProjectKey, RoundKey, Vote};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait QuadraticFundingApi<Balance>
+    where
+        Balance: codec::Codec,
+    {
+        /// The share of a round's matching pool each of its projects would receive if the round ended now.
+        fn matching_estimate(round_key: RoundKey) -> Vec<(ProjectKey, Balance)>;
+    }
+
+    pub trait ProposalsApi<AccountId, Balance, Timestamp, Project>
+    where
+        AccountId: codec::Codec,
+        Balance: codec::Codec,
+        Timestamp: codec::Codec,
+        Project: codec::Codec,
+    {
+        /// The details of a project.
+        fn project(project_key: ProjectKey) -> Option<Project>;
+
+        /// The amount the initiator of a project can currently withdraw, before the Imbue fee is taken.
+        fn withdrawable_funds(project_key: ProjectKey) -> Balance;
+
+        /// The vote tallies of each of a project's milestones which have been submitted.
+        fn milestone_votes(project_key: ProjectKey) -> Vec<(MilestoneKey, Vote<Balance>)>;
+
+        /// The rounds a project belongs to which have not yet ended.
+        fn active_rounds(project_key: ProjectKey) -> Vec<RoundKey>;
+
+        /// The contribution an account has made to a project.
+        fn contribution(project_key: ProjectKey, account_id: AccountId) -> Option<Contribution<Balance, Timestamp>>;
+    }
+}
@@ -1,14 +1,17 @@
 use crate as proposals;
 use crate::mock::*;
 
+use crate::traits::{IntoProposal, MockContributionReceipts, MockXcmOrigin, WhitelistVerifier};
 use crate::*;
 use common_types::CurrencyId;
 use frame_support::{
     assert_noop, assert_ok, bounded_btree_map, bounded_vec,
     dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo},
 };
+use pallet_identity::{Data, IdentityField, IdentityFields, IdentityInfo, Judgement};
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
-use sp_core::H256;
+use sp_core::{sr25519, H256};
 
 use sp_std::vec::Vec;
 
@@ -27,11 +30,13 @@ fn create_a_test_project_with_less_than_100_percent() {
                 RuntimeOrigin::signed(*ALICE),
                 gen_hash(1),
                 bounded_vec![ProposedMilestone {
-                    percentage_to_unlock: 99
+                    percentage_to_unlock: 99,
+                    ..Default::default()
                 }],
                 //funds required
                 1000000u64,
-                CurrencyId::Native
+                CurrencyId::Native,
+                None
             ),
             DispatchErrorWithPostInfo {
                 post_info: PostDispatchInfo {
@@ -199,7 +204,7 @@ fn cancel_round_no_active_round() {
 
 #[test]
 fn test_funding_round_is_created_on_schedule_round() {
-    let project_keys: BoundedProjectKeys = bounded_vec![0u32];
+    let project_keys: BoundedProjectKeys<Test> = bounded_vec![0u32];
 
     //create_project extrinsic
     build_test_externality().execute_with(|| {
@@ -235,7 +240,7 @@ fn cancel_round() {
     build_test_externality().execute_with(|| {
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         //schedule_round extrinsic
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -270,7 +275,7 @@ fn test_cancelling_started_round() {
     build_test_externality().execute_with(|| {
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -302,7 +307,7 @@ fn test_cancelling_round_without_root_privilege() {
     build_test_externality().execute_with(|| {
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -332,7 +337,7 @@ fn create_a_test_project_and_schedule_round_and_contribute() {
         //create_project extrinsic
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let project_key: u32 = 0;
         let contribution_amount = 2000u64;
 
@@ -397,7 +402,7 @@ fn create_a_test_project_and_schedule_round_and_add_whitelist_with_cap_and_contr
         //create_project extrinsic
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let project_key: u32 = 0;
         let contribution_amount = 2000u64;
         let max_cap = 1000000u64;
@@ -454,7 +459,7 @@ fn create_a_test_project_and_schedule_round_and_add_whitelist_with_unlimited_cap
         //create_project extrinsic
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let project_key: u32 = 0;
         let contribution_amount = 2000u64;
         let max_cap = 0u64;
@@ -511,7 +516,7 @@ fn create_a_test_project_and_schedule_round_and_add_whitelist_and_contribute_ove
         //create_project extrinsic
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let project_key: u32 = 0;
         let contribution_amount = 60_000u64;
         let max_cap = 100_000u64;
@@ -565,7 +570,7 @@ fn create_a_test_project_and_schedule_round_and_contribute_and_approve() {
         //create_project extrinsic
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let project_key = 0;
         let contribution_amount = 1000000u64;
 
@@ -615,7 +620,7 @@ fn create_a_test_project_and_schedule_round_and_contribute_and_approvefail() {
         //create_project extrinsic
         assert_ok!(create_project());
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let project_key = 0;
         let contribution_amount = 100000u64;
 
@@ -664,7 +669,7 @@ fn test_submit_milestone() {
         assert_ok!(create_project());
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -675,7 +680,7 @@ fn test_submit_milestone() {
         )
         .unwrap();
 
-        let value = 100u64;
+        let value = 1_000_000u64;
         Proposals::contribute(RuntimeOrigin::signed(*BOB), None, project_key, value).unwrap();
 
         let mut milestone_index: BoundedMilestoneKeys<Test> = bounded_vec![];
@@ -688,7 +693,8 @@ fn test_submit_milestone() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            0
+            0,
+            gen_hash(1)
         ));
 
         let latest_event = <frame_system::Pallet<Test>>::events()
@@ -712,7 +718,7 @@ fn test_submit_milestone_without_approval() {
         assert_ok!(create_project());
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -723,7 +729,7 @@ fn test_submit_milestone_without_approval() {
         )
         .unwrap();
 
-        let value = 100u64;
+        let value = 1_000_000u64;
         assert_ok!(Proposals::contribute(
             RuntimeOrigin::signed(*BOB),
             None,
@@ -737,7 +743,7 @@ fn test_submit_milestone_without_approval() {
         run_to_block(3);
 
         assert_noop!(
-            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0),
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1)),
             DispatchErrorWithPostInfo {
                 post_info: PostDispatchInfo {
                     actual_weight: None,
@@ -756,7 +762,7 @@ fn test_voting_on_a_milestone() {
         assert_ok!(create_project());
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -767,7 +773,7 @@ fn test_voting_on_a_milestone() {
         )
         .unwrap();
 
-        let value = 100u64;
+        let value = 1_000_000u64;
         Proposals::contribute(RuntimeOrigin::signed(*BOB), None, project_key, value).unwrap();
 
         let mut milestone_index: BoundedMilestoneKeys<Test> = bounded_vec![];
@@ -785,7 +791,8 @@ fn test_voting_on_a_milestone() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            0
+            0,
+            gen_hash(1)
         ));
 
         run_to_block(5);
@@ -837,7 +844,7 @@ fn test_voting_on_a_milestone_not_approved_as_milestone_not_met() {
         assert_ok!(create_project());
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -848,8 +855,14 @@ fn test_voting_on_a_milestone_not_approved_as_milestone_not_met() {
         )
         .unwrap();
 
-        Proposals::contribute(RuntimeOrigin::signed(*BOB), None, project_key, 40u64).unwrap();
-        Proposals::contribute(RuntimeOrigin::signed(*CHARLIE), None, project_key, 60u64).unwrap();
+        Proposals::contribute(RuntimeOrigin::signed(*BOB), None, project_key, 400_000u64).unwrap();
+        Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            600_000u64,
+        )
+        .unwrap();
 
         let mut milestone_index: BoundedMilestoneKeys<Test> = bounded_vec![];
         let _ = milestone_index.try_push(0);
@@ -866,7 +879,8 @@ fn test_voting_on_a_milestone_not_approved_as_milestone_not_met() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            0
+            0,
+            gen_hash(1)
         ));
 
         run_to_block(5);
@@ -918,7 +932,7 @@ fn test_voting_on_a_cancelled_round() {
         assert_ok!(create_project());
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![project_key];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![project_key];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -972,16 +986,19 @@ fn test_voting_on_a_cancelled_round() {
 fn test_finalize_a_milestone_without_voting() {
     let milestone1_key = 0;
     let milestone2_key = 1;
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 20,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
-    let milestone3: ProposedMilestone = ProposedMilestone {
+    let milestone3 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
@@ -991,7 +1008,7 @@ fn test_finalize_a_milestone_without_voting() {
         assert_ok!(create_project_multiple_milestones(proposed_milestones));
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -1002,7 +1019,7 @@ fn test_finalize_a_milestone_without_voting() {
         )
         .unwrap();
 
-        let value = 100u64;
+        let value = 1_000_000u64;
         Proposals::contribute(RuntimeOrigin::signed(*BOB), None, project_key, value).unwrap();
 
         let mut milestone_index: BoundedMilestoneKeys<Test> = bounded_vec![];
@@ -1023,13 +1040,15 @@ fn test_finalize_a_milestone_without_voting() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            milestone1_key
+            milestone1_key,
+            gen_hash(1)
         ));
 
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            milestone2_key
+            milestone2_key,
+            gen_hash(1)
         ));
 
         run_to_block(5);
@@ -1063,17 +1082,20 @@ fn test_finalize_a_milestone_without_voting() {
 
 #[test]
 fn test_project_initiator_cannot_withdraw_if_majority_vote_against() {
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
 
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 20,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
-    let milestone3: ProposedMilestone = ProposedMilestone {
+    let milestone3 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
@@ -1083,7 +1105,7 @@ fn test_project_initiator_cannot_withdraw_if_majority_vote_against() {
         assert_ok!(create_project_multiple_milestones(proposed_milestones));
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -1135,7 +1157,8 @@ fn test_project_initiator_cannot_withdraw_if_majority_vote_against() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            0
+            0,
+            gen_hash(1)
         ));
 
         run_to_block(5);
@@ -1160,11 +1183,10 @@ fn test_project_initiator_cannot_withdraw_if_majority_vote_against() {
         )
         .ok();
 
-        assert_ok!(Proposals::finalise_milestone_voting(
-            RuntimeOrigin::signed(*ALICE),
-            project_key,
-            0
-        ));
+        assert_noop!(
+            Proposals::finalise_milestone_voting(RuntimeOrigin::signed(*ALICE), project_key, 0),
+            Error::<Test>::MilestoneVotingWindowOpen
+        );
 
         assert_noop!(
             Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
@@ -1187,17 +1209,20 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
     let milestone2_key = 1;
     let milestone3_key = 2;
 
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
 
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 20,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
-    let milestone3: ProposedMilestone = ProposedMilestone {
+    let milestone3 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
@@ -1208,7 +1233,7 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
         assert_ok!(create_project_multiple_milestones(proposed_milestones));
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -1232,11 +1257,21 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
 
         Proposals::approve(RuntimeOrigin::root(), None, project_key, None).unwrap();
 
-        Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, milestone1_key)
-            .unwrap();
+        Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            milestone1_key,
+            gen_hash(1),
+        )
+        .unwrap();
 
-        Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, milestone2_key)
-            .ok();
+        Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            milestone2_key,
+            gen_hash(1),
+        )
+        .ok();
 
         run_to_block(5);
         //Bob voting on the submitted milestones
@@ -1287,8 +1322,10 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
             milestone2_key
         ));
 
-        let pallet_account = <proposals::Pallet<Test>>::account_id();
-        let account_balance = Tokens::free_balance(CurrencyId::Native, &pallet_account);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let account_balance = Tokens::free_balance(CurrencyId::Native, &treasury_account);
 
         assert_ok!(<proposals::Pallet<Test>>::withdraw(
             RuntimeOrigin::signed(*ALICE),
@@ -1317,7 +1354,7 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
         );
 
         assert_eq!(
-            Tokens::free_balance(CurrencyId::Native, &pallet_account),
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
             account_balance.saturating_add(calc_imbue_fee(available))
         );
 
@@ -1325,7 +1362,8 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            2
+            2,
+            gen_hash(1)
         ));
         run_to_block(10);
         //Bob voting on the submitted milestone
@@ -1374,7 +1412,7 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
         );
 
         assert_eq!(
-            Tokens::free_balance(CurrencyId::Native, &pallet_account),
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
             account_balance.saturating_add(calc_imbue_fee(required_funds))
         );
 
@@ -1387,17 +1425,20 @@ fn test_project_initiator_can_withdraw_only_the_percentage_milestone_completed()
 
 #[test]
 fn test_project_initiator_can_withdraw_only_the_percentage_after_force_milestone_completed() {
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
 
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 20,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
-    let milestone3: ProposedMilestone = ProposedMilestone {
+    let milestone3 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
@@ -1410,7 +1451,7 @@ fn test_project_initiator_can_withdraw_only_the_percentage_after_force_milestone
         assert_ok!(create_project_multiple_milestones(proposed_milestones));
 
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -1449,8 +1490,10 @@ fn test_project_initiator_can_withdraw_only_the_percentage_after_force_milestone
         )
         .unwrap();
 
-        let pallet_account = <proposals::Pallet<Test>>::account_id();
-        let account_balance = Tokens::free_balance(CurrencyId::Native, &pallet_account);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let account_balance = Tokens::free_balance(CurrencyId::Native, &treasury_account);
 
         assert_ok!(<proposals::Pallet<Test>>::withdraw(
             RuntimeOrigin::signed(*ALICE),
@@ -1493,7 +1536,7 @@ fn test_project_initiator_can_withdraw_only_the_percentage_after_force_milestone
         );
 
         assert_eq!(
-            Tokens::free_balance(CurrencyId::Native, &pallet_account),
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
             account_balance.saturating_add(calc_imbue_fee(contribution_value))
         );
 
@@ -1522,7 +1565,7 @@ fn test_withdraw_upon_project_approval_and_finalised_voting() {
         let initial_balance = Tokens::free_balance(CurrencyId::Native, &ALICE);
         assert_ok!(create_project());
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         Proposals::schedule_round(
             RuntimeOrigin::root(),
             System::block_number(),
@@ -1532,7 +1575,7 @@ fn test_withdraw_upon_project_approval_and_finalised_voting() {
         )
         .unwrap();
 
-        let required_funds = 100u64;
+        let required_funds = 1_000_000u64;
         Proposals::contribute(
             RuntimeOrigin::signed(*BOB),
             None,
@@ -1548,7 +1591,8 @@ fn test_withdraw_upon_project_approval_and_finalised_voting() {
 
         Proposals::approve(RuntimeOrigin::root(), None, project_key, None).unwrap();
 
-        Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0).unwrap();
+        Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1))
+            .unwrap();
 
         run_to_block(5);
         Proposals::vote_on_milestone(
@@ -1567,8 +1611,10 @@ fn test_withdraw_upon_project_approval_and_finalised_voting() {
         )
         .unwrap();
 
-        let pallet_account = <proposals::Pallet<Test>>::account_id();
-        let account_balance = Tokens::free_balance(CurrencyId::Native, &pallet_account);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let account_balance = Tokens::free_balance(CurrencyId::Native, &treasury_account);
 
         assert_ok!(Proposals::withdraw(
             RuntimeOrigin::signed(*ALICE),
@@ -1577,7 +1623,7 @@ fn test_withdraw_upon_project_approval_and_finalised_voting() {
 
         let withdrawn = deduct_imbue_fee(required_funds);
         assert_eq!(
-            Tokens::free_balance(CurrencyId::Native, &pallet_account),
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
             account_balance.saturating_add(calc_imbue_fee(required_funds))
         );
 
@@ -1608,7 +1654,7 @@ fn test_withdraw_upon_project_auto_approval_and_based_on_threshold_met_during_vo
         let initial_balance = Tokens::free_balance(CurrencyId::Native, &ALICE);
         assert_ok!(create_project());
         let project_key = 0;
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
         Proposals::schedule_round(
             RuntimeOrigin::root(),
@@ -1619,9 +1665,9 @@ fn test_withdraw_upon_project_auto_approval_and_based_on_threshold_met_during_vo
         )
         .unwrap();
 
-        let required_funds = 100u64;
-        let funds_contributed_by_bob = 76u64;
-        let funds_contributed_by_charlie = 24u64;
+        let required_funds = 1_000_000u64;
+        let funds_contributed_by_bob = 760_000u64;
+        let funds_contributed_by_charlie = 240_000u64;
         Proposals::contribute(
             RuntimeOrigin::signed(*BOB),
             None,
@@ -1645,7 +1691,8 @@ fn test_withdraw_upon_project_auto_approval_and_based_on_threshold_met_during_vo
 
         Proposals::approve(RuntimeOrigin::root(), None, project_key, None).unwrap();
 
-        Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0).unwrap();
+        Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1))
+            .unwrap();
 
         run_to_block(5);
         Proposals::vote_on_milestone(
@@ -1657,8 +1704,10 @@ fn test_withdraw_upon_project_auto_approval_and_based_on_threshold_met_during_vo
         )
         .unwrap();
 
-        let pallet_account = <proposals::Pallet<Test>>::account_id();
-        let account_balance = Tokens::free_balance(CurrencyId::Native, &pallet_account);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let account_balance = Tokens::free_balance(CurrencyId::Native, &treasury_account);
 
         assert_ok!(Proposals::withdraw(
             RuntimeOrigin::signed(*ALICE),
@@ -1667,7 +1716,7 @@ fn test_withdraw_upon_project_auto_approval_and_based_on_threshold_met_during_vo
 
         let withdrawn = deduct_imbue_fee(required_funds);
         assert_eq!(
-            Tokens::free_balance(CurrencyId::Native, &pallet_account),
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
             account_balance.saturating_add(calc_imbue_fee(required_funds))
         );
 
@@ -1716,17 +1765,19 @@ fn test_withdraw_from_non_initiator_account() {
 fn submit_multiple_milestones() {
     let voting_round1_key = 2;
     let voting_round2_key = 3;
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
 
-    let project_keys: BoundedProjectKeys = bounded_vec![0];
+    let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
 
     build_test_externality().execute_with(|| {
         assert_ok!(create_project_multiple_milestones(proposed_milestones));
@@ -1744,7 +1795,7 @@ fn submit_multiple_milestones() {
         )
         .unwrap();
 
-        let value = 100u64;
+        let value = 1_000_000u64;
         Proposals::contribute(RuntimeOrigin::signed(*BOB), None, project_key, value).unwrap();
 
         let mut milestone_index: BoundedMilestoneKeys<Test> = bounded_vec![];
@@ -1759,6 +1810,7 @@ fn submit_multiple_milestones() {
             RuntimeOrigin::signed(*ALICE),
             project_key,
             milestone_index_1,
+            gen_hash(1),
         )
         .unwrap();
 
@@ -1779,7 +1831,8 @@ fn submit_multiple_milestones() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            milestone_index_2
+            milestone_index_2,
+            gen_hash(1)
         ));
 
         let voting_round_event_2 = <frame_system::Pallet<Test>>::events()
@@ -1802,17 +1855,20 @@ fn withdraw_percentage_milestone_completed_refund_locked_milestone() {
     let required_funds = 1000000u64;
     let project_key = 0;
 
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
 
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 20,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
-    let milestone3: ProposedMilestone = ProposedMilestone {
+    let milestone3 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
@@ -1823,7 +1879,7 @@ fn withdraw_percentage_milestone_completed_refund_locked_milestone() {
         let initial_balance = Tokens::free_balance(CurrencyId::Native, &ALICE);
         assert_ok!(create_project_multiple_milestones(proposed_milestones));
 
-        let project_keys: BoundedProjectKeys = bounded_vec![0];
+        let project_keys: BoundedProjectKeys<Test> = bounded_vec![0];
         let milestone1_key = 0;
         let milestone2_key = 1;
 
@@ -1878,13 +1934,15 @@ fn withdraw_percentage_milestone_completed_refund_locked_milestone() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            milestone1_key
+            milestone1_key,
+            gen_hash(1)
         ));
 
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            milestone2_key
+            milestone2_key,
+            gen_hash(1)
         ));
 
         run_to_block(5);
@@ -1914,8 +1972,10 @@ fn withdraw_percentage_milestone_completed_refund_locked_milestone() {
             0
         ));
 
-        let pallet_account = <proposals::Pallet<Test>>::account_id();
-        let account_balance = Tokens::free_balance(CurrencyId::Native, &pallet_account);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let account_balance = Tokens::free_balance(CurrencyId::Native, &treasury_account);
 
         assert_ok!(<proposals::Pallet<Test>>::withdraw(
             RuntimeOrigin::signed(*ALICE),
@@ -1945,7 +2005,7 @@ fn withdraw_percentage_milestone_completed_refund_locked_milestone() {
         );
 
         assert_eq!(
-            Tokens::free_balance(CurrencyId::Native, &pallet_account),
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
             account_balance.saturating_add(calc_imbue_fee(available))
         );
 
@@ -2065,7 +2125,7 @@ fn test_raising_a_vote_of_no_confidence() {
 
         // Assert that storage has been mutated correctly.
         assert!(vote.nay == 1_000_000u64 && vote.yay == 0u64);
-        assert!(UserVotes::<Test>::get((*BOB, project_key, 0, round_count)) == Some(true));
+        assert!(UserVotes::<Test>::get((*BOB, project_key, 0, round_count)) == Some(false));
         assert!(round_count == 2u32);
         assert!(NoConfidenceVotes::<Test>::contains_key(project_key));
 
@@ -2146,15 +2206,6 @@ fn test_adding_vote_of_no_confidence() {
             ),
             Error::<Test>::VoteAlreadyExists
         );
-        assert_noop!(
-            Proposals::vote_on_no_confidence_round(
-                RuntimeOrigin::signed(*BOB),
-                None,
-                project_key,
-                false
-            ),
-            Error::<Test>::VoteAlreadyExists
-        );
 
         // Assert the state of the system is as it should be.
         let vote = NoConfidenceVotes::<Test>::get(project_key).unwrap();
@@ -2162,7 +2213,7 @@ fn test_adding_vote_of_no_confidence() {
 
         // Assert that storage has been mutated correctly.
         assert!(vote.nay == 500_000u64 && vote.yay == 500_000u64);
-        assert!(UserVotes::<Test>::get((*CHARLIE, project_key, 0, round_count)) == Some(true));
+        assert!(UserVotes::<Test>::get((*CHARLIE, project_key, 0, round_count)) == Some(false));
         assert!(UserVotes::<Test>::get((*BOB, project_key, 0, round_count)) == Some(true));
 
         assert!(round_count == 2u32);
@@ -2326,6 +2377,7 @@ fn test_finalise_vote_of_no_confidence_refunds_contributors() {
 
     build_test_externality().execute_with(|| {
         let initial_balance = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let alice_initial = Tokens::free_balance(CurrencyId::Native, &ALICE);
         let project_key = 0u32;
         // Create a project for both ALICE and BOB.
         assert_ok!(create_project());
@@ -2442,7 +2494,8 @@ fn test_finalise_milestone_is_ok_on_threshold_vote() {
         // Assert that threshold has been met
         let _ = Proposals::approve(RuntimeOrigin::root(), Some(1), 0, None).unwrap();
 
-        let _ = Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), 0, 0).unwrap();
+        let _ =
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), 0, 0, gen_hash(1)).unwrap();
 
         run_to_block(System::block_number() + 1);
 
@@ -2464,27 +2517,32 @@ fn test_finalise_milestone_is_ok_on_threshold_vote() {
 fn update_an_existing_project() {
     let updated_required_funds = 2_500_000u64;
     let updated_agreement_hash = gen_hash(200);
-    let mut proposed_milestones: Vec<ProposedMilestone> = Vec::new();
-    let milestone1: ProposedMilestone = ProposedMilestone {
+    let mut proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
+    let milestone1 = ProposedMilestone {
         percentage_to_unlock: 20,
+        ..Default::default()
     };
-    let milestone2: ProposedMilestone = ProposedMilestone {
+    let milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
-    let milestone3: ProposedMilestone = ProposedMilestone {
+    let milestone3 = ProposedMilestone {
         percentage_to_unlock: 50,
+        ..Default::default()
     };
     proposed_milestones.push(milestone1);
     proposed_milestones.push(milestone2);
     proposed_milestones.push(milestone3);
 
-    let mut updated_proposed_milestones: Vec<ProposedMilestone> = Vec::new();
-    let updated_milestone1: ProposedMilestone = ProposedMilestone {
+    let mut updated_proposed_milestones: Vec<ProposedMilestone<BlockNumber>> = Vec::new();
+    let updated_milestone1 = ProposedMilestone {
         percentage_to_unlock: 70,
+        ..Default::default()
     };
-    let updated_milestone2: ProposedMilestone = ProposedMilestone {
+    let updated_milestone2 = ProposedMilestone {
         percentage_to_unlock: 30,
+        ..Default::default()
     };
 
     updated_proposed_milestones.push(updated_milestone1);
@@ -2503,7 +2561,8 @@ fn update_an_existing_project() {
                 .expect("Invalid proposed milestones"),
             updated_required_funds,
             CurrencyId::Native,
-            gen_hash(200)
+            gen_hash(200),
+            None
         ));
 
         let latest_event = <frame_system::Pallet<Test>>::events()
@@ -2532,12 +2591,14 @@ fn only_the_initiator_can_update_project() {
         assert_ok!(create_project());
         let project_key = 0;
         let updated_funds = 1_000;
-        let updated_milestone1: ProposedMilestone = ProposedMilestone {
+        let updated_milestone1 = ProposedMilestone {
             percentage_to_unlock: 70,
+            ..Default::default()
         };
 
-        let updated_milestone2: ProposedMilestone = ProposedMilestone {
+        let updated_milestone2 = ProposedMilestone {
             percentage_to_unlock: 30,
+            ..Default::default()
         };
 
         assert_noop!(
@@ -2548,6 +2609,7 @@ fn only_the_initiator_can_update_project() {
                 updated_funds,
                 CurrencyId::Native,
                 gen_hash(1),
+                None,
             ),
             Error::<Test>::MilestonesTotalPercentageMustEqual100
         );
@@ -2562,6 +2624,7 @@ fn only_the_initiator_can_update_project() {
                 updated_funds,
                 CurrencyId::Native,
                 gen_hash(1),
+                None,
             ),
             Error::<Test>::UserIsNotInitiator
         );
@@ -2607,7 +2670,8 @@ fn project_is_deleted_on_final_withdraw() {
         assert_ok!(Proposals::submit_milestone(
             RuntimeOrigin::signed(*ALICE),
             project_key,
-            0
+            0,
+            gen_hash(1)
         ));
         run_to_block(System::block_number() + 1);
         assert_ok!(Proposals::vote_on_milestone(
@@ -2674,66 +2738,4278 @@ fn project_is_deleted_after_no_confidence_call() {
     })
 }
 
-//common helper methods
-pub fn create_project() -> DispatchResultWithPostInfo {
-    Proposals::create_project(
-        RuntimeOrigin::signed(*ALICE),
-        gen_hash(1),
-        bounded_vec![ProposedMilestone {
-            percentage_to_unlock: 100
-        }],
-        //funds required
-        1_000_000u64,
-        CurrencyId::Native,
-    )
+#[test]
+fn funding_round_ends_automatically() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(create_project());
+        let end = System::block_number() + 10;
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            end,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+        assert_eq!(RoundsExpiring::<Test>::get(end).len(), 1);
+        run_to_block(end);
+        assert!(RoundsExpiring::<Test>::get(end).is_empty());
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::FundingRoundEnded(1))
+        );
+    })
 }
 
-pub fn create_project_multiple_milestones(
-    proposed_milestones: Vec<ProposedMilestone>,
-) -> DispatchResultWithPostInfo {
-    Proposals::create_project(
-        RuntimeOrigin::signed(*ALICE),
-        gen_hash(1),
-        proposed_milestones
-            .try_into()
-            .expect("proposed milestones are too long"),
-        //funds required
-        1_000_000u64,
-        CurrencyId::Native,
-    )
-}
+#[test]
+fn milestone_vote_is_approved_automatically_when_window_closes() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let end = System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        assert!(!Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
 
-fn run_to_block(n: u64) {
-    while System::block_number() < n {
-        System::set_block_number(System::block_number() + 1);
-        System::on_initialize(System::block_number());
-        Proposals::on_initialize(System::block_number());
-        //Bad case scenario is that we have little space. all tests must still pass.
-        if n % 2 == 0 {
-            Proposals::on_idle(System::block_number(), Weight::MAX / 90);
-        } else {
-            Proposals::on_idle(System::block_number(), Weight::MAX / 2);
-        }
-    }
+        run_to_block(end);
+
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert!(
+            MilestoneVotes::<Test>::get((project_key, 0))
+                .unwrap()
+                .is_approved
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::MilestoneApproved(
+                *ALICE,
+                project_key,
+                0,
+                end
+            ))
+        );
+    })
 }
 
-fn _run_to_block_with_no_idle_space(n: u64) {
-    while System::block_number() < n {
-        System::set_block_number(System::block_number() + 1);
-        System::on_initialize(System::block_number());
-        Proposals::on_initialize(System::block_number());
-        Proposals::on_idle(System::block_number(), Weight::zero());
-    }
+#[test]
+fn milestone_vote_is_rejected_automatically_when_window_closes() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let end = System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+
+        run_to_block(end);
+
+        assert!(!Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert!(MilestoneVotes::<Test>::get((project_key, 0)).is_none());
+        assert_eq!(
+            MilestoneSubmissions::<Test>::get((project_key, 0))
+                .unwrap()
+                .last_rejection,
+            Some(Vote {
+                yay: 400_000u64,
+                nay: 600_000u64,
+                is_approved: false,
+            })
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::MilestoneRejected(
+                project_key,
+                0,
+                400_000u64,
+                600_000u64,
+                end
+            ))
+        );
+    })
 }
 
-fn gen_hash(seed: u8) -> AgreementHash {
-    H256::from([seed; 32])
+#[test]
+fn milestone_is_reopened_when_too_few_contributors_vote() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let end = System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+
+        run_to_block(end);
+
+        assert!(!Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert!(MilestoneVotes::<Test>::get((project_key, 0)).is_none());
+        assert!(MilestoneVotingDeadlines::<Test>::get((project_key, 0)).is_none());
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::MilestoneReopened(project_key, 0, end))
+        );
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+    })
 }
 
-fn deduct_imbue_fee(fund: u64) -> u64 {
-    fund.saturating_sub(calc_imbue_fee(fund))
+#[test]
+fn milestone_cannot_be_resubmitted_while_voting_window_is_open() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        assert_eq!(
+            MilestoneVotingDeadlines::<Test>::get((project_key, 0)),
+            Some(System::block_number() + <Test as Config>::MilestoneVotingWindow::get())
+        );
+        assert_noop!(
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1)),
+            Error::<Test>::MilestoneVotingWindowOpen
+        );
+    })
 }
 
-fn calc_imbue_fee(fund: u64) -> u64 {
-    fund.saturating_mul(<Test as Config>::ImbueFee::get() as u64) / 100
+#[test]
+fn cannot_vote_on_milestone_after_voting_window_closes() {
+    build_test_externality().execute_with(|| {
+        let project_key = ProjectCount::<Test>::get();
+        assert_ok!(create_project_multiple_milestones(vec![
+            ProposedMilestone {
+                percentage_to_unlock: 50,
+                ..Default::default()
+            },
+            ProposedMilestone {
+                percentage_to_unlock: 50,
+                ..Default::default()
+            },
+        ]));
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 1,
+            bounded_vec![project_key],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            1_000_000u64
+        ));
+        run_to_block(System::block_number() + 2);
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            None,
+            project_key,
+            None
+        ));
+
+        let first_window_end =
+            System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 10);
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            1,
+            gen_hash(1)
+        ));
+        let second_round_key = RoundCount::<Test>::get();
+
+        run_to_block(first_window_end);
+
+        assert_noop!(
+            Proposals::vote_on_milestone(
+                RuntimeOrigin::signed(*BOB),
+                project_key,
+                0,
+                Some(second_round_key),
+                true
+            ),
+            Error::<Test>::MilestoneVotingWindowClosed
+        );
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            1,
+            Some(second_round_key),
+            true
+        ));
+    })
+}
+
+#[test]
+fn vote_of_no_confidence_is_finalised_automatically() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let end = System::block_number() + <Test as Config>::NoConfidenceTimeLimit::get();
+        assert_ok!(Proposals::raise_vote_of_no_confidence(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert_ok!(Proposals::vote_on_no_confidence_round(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            false
+        ));
+
+        run_to_block(end);
+
+        assert!(Projects::<Test>::get(project_key).is_none());
+        assert!(NoConfidenceVotes::<Test>::get(project_key).is_none());
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::NoConfidenceRoundFinalised(2, project_key))
+        );
+    })
+}
+
+#[test]
+fn vote_of_no_confidence_fails_automatically_below_threshold() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let end = System::block_number() + <Test as Config>::NoConfidenceTimeLimit::get();
+        assert_ok!(Proposals::raise_vote_of_no_confidence(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert_ok!(Proposals::vote_on_no_confidence_round(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            true
+        ));
+
+        run_to_block(end);
+
+        assert!(Projects::<Test>::get(project_key).is_some());
+        assert!(NoConfidenceVotes::<Test>::get(project_key).is_none());
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::NoConfidenceRoundFailed(2, project_key))
+        );
+    })
+}
+
+#[test]
+fn project_fails_when_funding_round_ends_underfunded() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_underfunded_project(100_000, 200_000);
+
+        let project = Projects::<Test>::get(project_key).unwrap();
+        assert!(project.cancelled);
+        assert!(System::events().iter().any(|record| record.event
+            == mock::RuntimeEvent::from(proposals::Event::ProjectFundingFailed(1, project_key))));
+        assert_noop!(
+            Proposals::approve(RuntimeOrigin::root(), None, project_key, None),
+            Error::<Test>::ProjectWithdrawn
+        );
+    })
+}
+
+#[test]
+fn project_without_contributions_is_removed_when_funding_fails() {
+    build_test_externality().execute_with(|| {
+        let alice_initial = Tokens::free_balance(CurrencyId::Native, &ALICE);
+        assert_ok!(create_project());
+        let end = System::block_number() + 1;
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            end,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+
+        run_to_block(end);
+
+        assert!(Projects::<Test>::get(0).is_none());
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &ALICE),
+            alice_initial
+        );
+    })
+}
+
+#[test]
+fn contributors_can_claim_refunds_from_failed_project() {
+    build_test_externality().execute_with(|| {
+        let initial_balance = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let alice_initial = Tokens::free_balance(CurrencyId::Native, &ALICE);
+        let project_key = create_underfunded_project(100_000, 200_000);
+
+        assert_ok!(Proposals::claim_refund(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            initial_balance
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::ContributionRefunded(
+                *BOB,
+                project_key,
+                100_000,
+                CurrencyId::Native
+            ))
+        );
+        assert_noop!(
+            Proposals::claim_refund(RuntimeOrigin::signed(*BOB), project_key),
+            Error::<Test>::NothingToRefund
+        );
+        assert!(Projects::<Test>::get(project_key).is_some());
+
+        assert_ok!(Proposals::claim_refund(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key
+        ));
+        assert!(Projects::<Test>::get(project_key).is_none());
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::project_account_id(project_key)
+            ),
+            0
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &ALICE),
+            alice_initial
+        );
+    })
+}
+
+#[test]
+fn failed_project_returns_its_storage_deposit_once() {
+    build_test_externality().execute_with(|| {
+        // The deposit of a second project must stay reserved.
+        let _ = create_project_key();
+        let alice_reserved = Tokens::reserved_balance(CurrencyId::Native, &ALICE);
+        let project_key = create_underfunded_project(100_000, 200_000);
+        // The deposit is held until the last refund removes the project.
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved + <Test as Config>::ProjectStorageDeposit::get()
+        );
+
+        assert_ok!(Proposals::claim_refund(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved + <Test as Config>::ProjectStorageDeposit::get()
+        );
+        assert_ok!(Proposals::claim_refund(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key
+        ));
+        assert!(Projects::<Test>::get(project_key).is_none());
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved
+        );
+    })
+}
+
+#[test]
+fn contributors_are_limited_per_project() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000u64
+        ));
+        assert_eq!(Proposals::project_contributor_count(project_key), 1);
+
+        ProjectContributorCount::<Test>::insert(
+            project_key,
+            <Test as Config>::MaximumContributorsPerProject::get(),
+        );
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*CHARLIE),
+                Some(round_key),
+                project_key,
+                100_000u64
+            ),
+            Error::<Test>::TooManyContributors
+        );
+        // Existing contributors can still add to their contribution.
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000u64
+        ));
+
+        ProjectContributorCount::<Test>::insert(project_key, 1);
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            200_000u64
+        ));
+        assert_eq!(Proposals::project_contributor_count(project_key), 0);
+    })
+}
+
+#[test]
+fn cannot_claim_refund_from_funded_project() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_noop!(
+            Proposals::claim_refund(RuntimeOrigin::signed(*BOB), project_key),
+            Error::<Test>::ProjectNotRefundable
+        );
+    })
+}
+
+#[test]
+fn contributor_can_reduce_contribution_while_round_is_open() {
+    build_test_externality().execute_with(|| {
+        let initial_balance = Tokens::free_balance(CurrencyId::Native, &BOB);
+        assert_ok!(create_project());
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 10,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            500_000u64
+        ));
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            200_000u64
+        ));
+
+        let project = Projects::<Test>::get(0).unwrap();
+        assert_eq!(project.raised_funds, 300_000u64);
+        assert_eq!(
+            Proposals::project_contributions(0, *BOB).unwrap().value,
+            300_000u64
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            initial_balance - 300_000u64
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::ContributionWithdrawn(
+                *BOB,
+                0,
+                200_000u64,
+                CurrencyId::Native,
+                1
+            ))
+        );
+
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            300_000u64
+        ));
+        let project = Projects::<Test>::get(0).unwrap();
+        assert!(Proposals::project_contributions(0, *BOB).is_none());
+        assert_eq!(project.raised_funds, 0u64);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            initial_balance
+        );
+    })
+}
+
+#[test]
+fn cannot_withdraw_more_than_contributed() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(create_project());
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 10,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            500_000u64
+        ));
+        assert_noop!(
+            Proposals::withdraw_contribution(RuntimeOrigin::signed(*BOB), None, 0, 500_001u64),
+            Error::<Test>::WithdrawalExceedsContribution
+        );
+        assert_noop!(
+            Proposals::withdraw_contribution(RuntimeOrigin::signed(*CHARLIE), None, 0, 1u64),
+            Error::<Test>::OnlyContributorsCanVote
+        );
+    })
+}
+
+#[test]
+fn cannot_withdraw_contribution_after_round_ends() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(create_project());
+        let end = System::block_number() + 10;
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            end,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            1_000_000u64
+        ));
+        // The round is closed by the hooks on its end block.
+        run_to_block(end);
+        assert_noop!(
+            Proposals::contribute(RuntimeOrigin::signed(*CHARLIE), None, 0, 1_000_000u64),
+            Error::<Test>::EndBlockNumberInvalid
+        );
+        assert_noop!(
+            Proposals::withdraw_contribution(RuntimeOrigin::signed(*BOB), None, 0, 1u64),
+            Error::<Test>::EndBlockNumberInvalid
+        );
+    })
+}
+
+#[test]
+fn cannot_withdraw_contribution_once_funding_threshold_met() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(create_project());
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 10,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            1_000_000u64
+        ));
+        assert_ok!(Proposals::approve(RuntimeOrigin::root(), None, 0, None));
+        assert_noop!(
+            Proposals::withdraw_contribution(RuntimeOrigin::signed(*BOB), None, 0, 1u64),
+            Error::<Test>::FundingThresholdMet
+        );
+    })
+}
+
+#[test]
+fn whitelist_cap_is_freed_when_contribution_is_withdrawn() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(create_project());
+        let whitelist = bounded_btree_map!(*BOB => 100_000u64);
+        assert_ok!(Proposals::add_project_whitelist(
+            RuntimeOrigin::signed(*ALICE),
+            0,
+            whitelist
+        ));
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 10,
+            bounded_vec![0u32],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            100_000u64
+        ));
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            40_000u64
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            0,
+            40_000u64
+        ));
+        assert_eq!(
+            Proposals::project_contributions(0, *BOB).unwrap().value,
+            100_000u64
+        );
+    })
+}
+
+#[test]
+fn sponsor_can_fund_matching_pool() {
+    build_test_externality().execute_with(|| {
+        let round_key = schedule_matching_round(vec![create_project_key()]);
+        let initial_balance = Tokens::free_balance(CurrencyId::Native, &CHARLIE);
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*CHARLIE),
+            round_key,
+            CurrencyId::Native,
+            100_000u64
+        ));
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*CHARLIE),
+            round_key,
+            CurrencyId::Native,
+            50_000u64
+        ));
+
+        assert_eq!(
+            MatchingPools::<Test>::get(round_key),
+            Some(MatchingPool {
+                currency_id: CurrencyId::Native,
+                amount: 150_000u64
+            })
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &CHARLIE),
+            initial_balance - 150_000u64
+        );
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::matching_pool_account_id(round_key)
+            ),
+            150_000u64
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::MatchingPoolFunded(
+                round_key,
+                *CHARLIE,
+                50_000u64,
+                CurrencyId::Native
+            ))
+        );
+    })
+}
+
+#[test]
+fn matching_pool_only_holds_one_currency() {
+    build_test_externality().execute_with(|| {
+        let round_key = schedule_matching_round(vec![create_project_key()]);
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*CHARLIE),
+            round_key,
+            CurrencyId::Native,
+            100_000u64
+        ));
+        assert_noop!(
+            Proposals::fund_matching_pool(
+                RuntimeOrigin::signed(*CHARLIE),
+                round_key,
+                CurrencyId::KSM,
+                100_000u64
+            ),
+            Error::<Test>::MatchingPoolCurrencyMismatch
+        );
+    })
+}
+
+#[test]
+fn authority_funds_matching_pool_from_treasury() {
+    build_test_externality().execute_with(|| {
+        let treasury =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let _ = Tokens::deposit(CurrencyId::Native, &treasury, 1_000_000u64);
+        let round_key = schedule_matching_round(vec![create_project_key()]);
+
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::root(),
+            round_key,
+            CurrencyId::Native,
+            400_000u64
+        ));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury),
+            600_000u64
+        );
+        assert_eq!(
+            MatchingPools::<Test>::get(round_key).unwrap().amount,
+            400_000u64
+        );
+    })
+}
+
+#[test]
+fn matching_pool_is_distributed_quadratically_when_round_ends() {
+    build_test_externality().execute_with(|| {
+        let broad_project = create_project_key();
+        let narrow_project = create_project_key();
+        let round_key = schedule_matching_round(vec![broad_project, narrow_project]);
+        let end = System::block_number() + 10;
+        let pool = 100_000u64;
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*ALICE),
+            round_key,
+            CurrencyId::Native,
+            pool
+        ));
+        for (project_key, bob_contribution, charlie_contribution) in [
+            (broad_project, 500_000u64, 500_000u64),
+            (narrow_project, 900_000u64, 100_000u64),
+        ] {
+            assert_ok!(Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                None,
+                project_key,
+                bob_contribution
+            ));
+            assert_ok!(Proposals::contribute(
+                RuntimeOrigin::signed(*CHARLIE),
+                None,
+                project_key,
+                charlie_contribution
+            ));
+        }
+
+        let estimate = Proposals::matching_estimate(round_key);
+        assert_eq!(estimate.len(), 2);
+        let broad_estimate = estimate[0].1;
+        let narrow_estimate = estimate[1].1;
+        assert!(broad_estimate > narrow_estimate);
+        assert!(broad_estimate + narrow_estimate <= pool);
+
+        run_to_block(end);
+
+        assert!(MatchingPools::<Test>::get(round_key).is_none());
+        assert_eq!(MatchedFunds::<Test>::get(broad_project), broad_estimate);
+        assert_eq!(MatchedFunds::<Test>::get(narrow_project), narrow_estimate);
+        assert_eq!(
+            Projects::<Test>::get(broad_project).unwrap().raised_funds,
+            1_000_000u64 + broad_estimate
+        );
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::project_account_id(narrow_project)
+            ),
+            1_000_000u64 + narrow_estimate
+        );
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::matching_pool_account_id(round_key)
+            ),
+            0
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == mock::RuntimeEvent::from(proposals::Event::MatchingFundsDistributed(
+                round_key,
+                broad_project,
+                broad_estimate
+            ))));
+    })
+}
+
+#[test]
+fn matched_funds_do_not_count_towards_vote_thresholds() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        let end = System::block_number() + 10;
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*ALICE),
+            round_key,
+            CurrencyId::Native,
+            1_000_000u64
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            600_000u64
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            400_000u64
+        ));
+        run_to_block(end);
+
+        // The project is matched as much as it raised, so the contributors hold half of its funds.
+        let project = Projects::<Test>::get(project_key).unwrap();
+        assert_eq!(MatchedFunds::<Test>::get(project_key), 1_000_000u64);
+        assert_eq!(project.raised_funds, 2_000_000u64);
+        assert_eq!(
+            Proposals::contributed_funds(project_key, &project),
+            1_000_000u64
+        );
+
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            None,
+            project_key,
+            None
+        ));
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        for contributor in [*BOB, *CHARLIE] {
+            assert_ok!(Proposals::vote_on_milestone(
+                RuntimeOrigin::signed(contributor),
+                project_key,
+                0,
+                None,
+                true
+            ));
+        }
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+    })
+}
+
+#[test]
+fn matching_pool_without_matches_is_sent_to_treasury() {
+    build_test_externality().execute_with(|| {
+        let treasury =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*ALICE),
+            round_key,
+            CurrencyId::Native,
+            100_000u64
+        ));
+        // A single contributor gives a quadratic score of zero.
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            1_000_000u64
+        ));
+
+        run_to_block(System::block_number() + 10);
+
+        assert_eq!(MatchedFunds::<Test>::get(project_key), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury),
+            100_000u64
+        );
+    })
+}
+
+#[test]
+fn underfunded_projects_are_failed_before_matching() {
+    build_test_externality().execute_with(|| {
+        let treasury =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::fund_matching_pool(
+            RuntimeOrigin::signed(*ALICE),
+            round_key,
+            CurrencyId::Native,
+            1_000_000u64
+        ));
+        // The pool would cover the shortfall but matching does not count towards the threshold.
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            400_000u64
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            400_000u64
+        ));
+
+        run_to_block(System::block_number() + 10);
+
+        assert!(Projects::<Test>::get(project_key).unwrap().cancelled);
+        assert_eq!(MatchedFunds::<Test>::get(project_key), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury),
+            1_000_000u64
+        );
+    })
+}
+
+#[test]
+fn contribution_root_sums_follow_withdrawals() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let _ = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            250_000u64
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            40_000u64
+        ));
+        assert_eq!(ContributionRootSums::<Test>::get(project_key), 700);
+
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            90_000u64
+        ));
+        assert_eq!(ContributionRootSums::<Test>::get(project_key), 600);
+
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            40_000u64
+        ));
+        assert_eq!(ContributionRootSums::<Test>::get(project_key), 400);
+    })
+}
+
+#[test]
+fn create_project_stores_milestone_metadata() {
+    build_test_externality().execute_with(|| {
+        let milestone1 = ProposedMilestone {
+            percentage_to_unlock: 40,
+            title_hash: gen_hash(10),
+            content_hash: gen_hash(11),
+            due_block: Some(1_000),
+        };
+        let milestone2 = ProposedMilestone {
+            percentage_to_unlock: 60,
+            title_hash: gen_hash(20),
+            content_hash: gen_hash(21),
+            due_block: None,
+        };
+        assert_ok!(create_project_multiple_milestones(vec![
+            milestone1.clone(),
+            milestone2.clone()
+        ]));
+
+        let project = Projects::<Test>::get(0).unwrap();
+        let stored_milestone1 = project.milestones.get(&0).unwrap();
+        assert_eq!(stored_milestone1.title_hash, milestone1.title_hash);
+        assert_eq!(stored_milestone1.content_hash, milestone1.content_hash);
+        assert_eq!(stored_milestone1.due_block, Some(1_000));
+        let stored_milestone2 = project.milestones.get(&1).unwrap();
+        assert_eq!(stored_milestone2.content_hash, milestone2.content_hash);
+        assert_eq!(stored_milestone2.due_block, None);
+    });
+}
+
+#[test]
+fn update_project_replaces_milestone_metadata() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(create_project());
+        let updated_milestone = ProposedMilestone {
+            percentage_to_unlock: 100,
+            title_hash: gen_hash(30),
+            content_hash: gen_hash(31),
+            due_block: Some(500),
+        };
+        assert_ok!(Proposals::update_project(
+            RuntimeOrigin::signed(*ALICE),
+            0,
+            vec![updated_milestone].try_into().expect("qed"),
+            1_000_000u64,
+            CurrencyId::Native,
+            gen_hash(1),
+            None
+        ));
+
+        let milestone = Projects::<Test>::get(0).unwrap().milestones[&0].clone();
+        assert_eq!(milestone.title_hash, gen_hash(30));
+        assert_eq!(milestone.content_hash, gen_hash(31));
+        assert_eq!(milestone.due_block, Some(500));
+    });
+}
+
+#[test]
+fn milestone_due_block_must_be_in_the_future() {
+    build_test_externality().execute_with(|| {
+        run_to_block(10);
+        let overdue_milestone = ProposedMilestone {
+            percentage_to_unlock: 100,
+            due_block: Some(10),
+            ..Default::default()
+        };
+        assert_noop!(
+            create_project_multiple_milestones(vec![overdue_milestone.clone()]),
+            Error::<Test>::MilestoneDueBlockInPast
+        );
+
+        assert_ok!(create_project());
+        assert_noop!(
+            Proposals::update_project(
+                RuntimeOrigin::signed(*ALICE),
+                0,
+                vec![overdue_milestone].try_into().expect("qed"),
+                1_000_000u64,
+                CurrencyId::Native,
+                gen_hash(1),
+                None
+            ),
+            Error::<Test>::MilestoneDueBlockInPast
+        );
+    });
+}
+
+#[test]
+fn submit_milestone_records_evidence() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let now = System::block_number();
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(42)
+        ));
+
+        assert_eq!(
+            MilestoneSubmissions::<Test>::get((project_key, 0)),
+            Some(MilestoneSubmission {
+                evidence_hash: gen_hash(42),
+                round_key: RoundCount::<Test>::get(),
+                submitted_at: now,
+                attempts: 1,
+                last_rejection: None,
+                failed: false,
+            })
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == mock::RuntimeEvent::from(proposals::Event::MilestoneSubmitted(
+                *ALICE,
+                project_key,
+                0,
+                gen_hash(42)
+            ))));
+    })
+}
+
+#[test]
+fn rejected_milestone_can_be_resubmitted_for_a_fresh_vote() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let first_end = System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        let first_round_key = RoundCount::<Test>::get();
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        // The rejection only counts once the window has closed.
+        assert_noop!(
+            Proposals::finalise_milestone_voting(RuntimeOrigin::signed(*ALICE), project_key, 0),
+            Error::<Test>::MilestoneVotingWindowOpen
+        );
+        run_to_block(first_end);
+        assert!(MilestoneVotingDeadlines::<Test>::get((project_key, 0)).is_none());
+
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(2)
+        ));
+        let submission = MilestoneSubmissions::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(submission.evidence_hash, gen_hash(2));
+        assert_eq!(submission.attempts, 2);
+        assert_eq!(
+            submission.last_rejection,
+            Some(Vote {
+                yay: 400_000u64,
+                nay: 600_000u64,
+                is_approved: false,
+            })
+        );
+        // Votes on the first submission stay with its round.
+        assert_eq!(
+            UserVotes::<Test>::get((*BOB, project_key, 0, first_round_key)),
+            Some(false)
+        );
+        assert!(UserVotes::<Test>::get((*BOB, project_key, 0, submission.round_key)).is_none());
+
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+    })
+}
+
+#[test]
+fn milestone_fails_after_max_submission_attempts() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let max_attempts = <Test as Config>::MaxMilestoneSubmissionAttempts::get();
+        for _ in 0..max_attempts {
+            let end = System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+            assert_ok!(Proposals::submit_milestone(
+                RuntimeOrigin::signed(*ALICE),
+                project_key,
+                0,
+                gen_hash(1)
+            ));
+            run_to_block(System::block_number() + 1);
+            assert_ok!(Proposals::vote_on_milestone(
+                RuntimeOrigin::signed(*BOB),
+                project_key,
+                0,
+                None,
+                false
+            ));
+            assert_ok!(Proposals::vote_on_milestone(
+                RuntimeOrigin::signed(*CHARLIE),
+                project_key,
+                0,
+                None,
+                false
+            ));
+            run_to_block(end);
+        }
+
+        let submission = MilestoneSubmissions::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(submission.attempts, max_attempts);
+        assert!(submission.failed);
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::MilestoneFailed(project_key, 0))
+        );
+        assert_noop!(
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1)),
+            Error::<Test>::MilestoneSubmissionAttemptsExhausted
+        );
+    })
+}
+
+#[test]
+fn contributor_can_change_milestone_vote() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        let round_key = RoundCount::<Test>::get();
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        assert_noop!(
+            Proposals::vote_on_milestone(RuntimeOrigin::signed(*BOB), project_key, 0, None, false),
+            Error::<Test>::VoteAlreadyExists
+        );
+
+        // Bob changes their vote once more evidence is posted.
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.yay, 600_000u64);
+        assert_eq!(vote.nay, 0u64);
+        assert_eq!(
+            UserVotes::<Test>::get((*BOB, project_key, 0, round_key)),
+            Some(true)
+        );
+
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert_noop!(
+            Proposals::vote_on_milestone(RuntimeOrigin::signed(*BOB), project_key, 0, None, false),
+            Error::<Test>::MilestoneAlreadyApproved
+        );
+    })
+}
+
+#[test]
+fn contributor_can_change_no_confidence_vote() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::raise_vote_of_no_confidence(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key
+        ));
+        assert_ok!(Proposals::vote_on_no_confidence_round(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            true
+        ));
+
+        // Charlie raised the vote but now supports the project continuing.
+        assert_ok!(Proposals::vote_on_no_confidence_round(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            true
+        ));
+        let vote = NoConfidenceVotes::<Test>::get(project_key).unwrap();
+        assert_eq!(vote.yay, 1_000_000u64);
+        assert_eq!(vote.nay, 0u64);
+
+        assert_ok!(Proposals::vote_on_no_confidence_round(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            false
+        ));
+        let vote = NoConfidenceVotes::<Test>::get(project_key).unwrap();
+        assert_eq!(vote.yay, 400_000u64);
+        assert_eq!(vote.nay, 600_000u64);
+        assert_eq!(
+            UserVotes::<Test>::get((*BOB, project_key, 0, RoundCount::<Test>::get())),
+            Some(false)
+        );
+    })
+}
+
+#[test]
+fn delegated_vote_is_counted_with_the_delegates_vote() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::delegate_vote(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            *BOB
+        ));
+        assert_eq!(Proposals::delegations(project_key, *CHARLIE), Some(*BOB));
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        let round_key = RoundCount::<Test>::get();
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.nay, 1_000_000u64);
+        assert_eq!(
+            UserVotes::<Test>::get((*CHARLIE, project_key, 0, round_key)),
+            Some(false)
+        );
+
+        // Charlie overrides the delegate with their own vote.
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.yay, 400_000u64);
+        assert_eq!(vote.nay, 600_000u64);
+
+        // The delegate changing their vote no longer moves Charlie's.
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.yay, 1_000_000u64);
+        assert_eq!(vote.nay, 0u64);
+    })
+}
+
+#[test]
+fn delegators_who_voted_with_their_delegate_keep_their_own_vote() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::delegate_vote(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            *BOB
+        ));
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        let round_key = RoundCount::<Test>::get();
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            false
+        ));
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.nay, 1_000_000u64);
+
+        // Charlie voted themselves so the delegate changing sides does not move their vote.
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.yay, 600_000u64);
+        assert_eq!(vote.nay, 400_000u64);
+        assert_eq!(
+            UserVotes::<Test>::get((*CHARLIE, project_key, 0, round_key)),
+            Some(false)
+        );
+    })
+}
+
+#[test]
+fn undelegated_vote_is_not_counted() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::delegate_vote(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            *BOB
+        ));
+        assert_ok!(Proposals::undelegate_vote(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key
+        ));
+        assert_eq!(Proposals::delegations(project_key, *CHARLIE), None);
+        let event = <frame_system::Pallet<Test>>::events()
+            .pop()
+            .expect("Expected at least one EventRecord to be found")
+            .event;
+        assert_eq!(
+            event,
+            mock::RuntimeEvent::from(proposals::Event::VoteUndelegated(
+                *CHARLIE,
+                project_key,
+                *BOB
+            ))
+        );
+        assert_noop!(
+            Proposals::undelegate_vote(RuntimeOrigin::signed(*CHARLIE), project_key),
+            Error::<Test>::NotDelegated
+        );
+
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        let round_key = RoundCount::<Test>::get();
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        let vote = MilestoneVotes::<Test>::get((project_key, 0)).unwrap();
+        assert_eq!(vote.yay, 600_000u64);
+        assert_eq!(
+            UserVotes::<Test>::get((*CHARLIE, project_key, 0, round_key)),
+            None
+        );
+    })
+}
+
+#[test]
+fn delegate_vote_is_validated() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_noop!(
+            Proposals::delegate_vote(RuntimeOrigin::signed(*CHARLIE), project_key, *CHARLIE),
+            Error::<Test>::CannotDelegateToSelf
+        );
+        assert_noop!(
+            Proposals::delegate_vote(RuntimeOrigin::signed(*ALICE), project_key, *BOB),
+            Error::<Test>::OnlyContributorsCanVote
+        );
+        assert_ok!(Proposals::delegate_vote(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            *BOB
+        ));
+        assert_noop!(
+            Proposals::delegate_vote(RuntimeOrigin::signed(*CHARLIE), project_key, *BOB),
+            Error::<Test>::AlreadyDelegated
+        );
+    })
+}
+
+#[test]
+fn delegated_vote_is_counted_in_no_confidence_round() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::delegate_vote(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            *BOB
+        ));
+        assert_ok!(Proposals::raise_vote_of_no_confidence(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        let vote = NoConfidenceVotes::<Test>::get(project_key).unwrap();
+        assert_eq!(vote.nay, 1_000_000u64);
+        assert_eq!(
+            UserVotes::<Test>::get((*CHARLIE, project_key, 0, RoundCount::<Test>::get())),
+            Some(false)
+        );
+    })
+}
+
+#[test]
+fn partially_funded_project_above_threshold_proceeds_with_raised_funds() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        assert_ok!(Proposals::set_funding_threshold(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            50
+        ));
+        assert_eq!(
+            Projects::<Test>::get(project_key)
+                .unwrap()
+                .funding_threshold,
+            50
+        );
+        let end = System::block_number() + 1;
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            end,
+            bounded_vec![project_key],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            400_000
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            200_000
+        ));
+        run_to_block(end + 1);
+        assert!(!Projects::<Test>::get(project_key).unwrap().cancelled);
+
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            None,
+            project_key,
+            Some(bounded_vec![0])
+        ));
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::ProjectFundsWithdrawn(
+                *ALICE,
+                project_key,
+                deduct_imbue_fee(600_000u64),
+                CurrencyId::Native
+            ))
+        );
+    })
+}
+
+#[test]
+fn project_below_funding_threshold_fails() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        assert_ok!(Proposals::set_funding_threshold(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            50
+        ));
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::FundingThresholdNotMet
+        );
+        let end = System::block_number() + 1;
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            end,
+            bounded_vec![project_key],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            400_000
+        ));
+        run_to_block(end);
+
+        assert!(Projects::<Test>::get(project_key).unwrap().cancelled);
+        assert_ok!(Proposals::claim_refund(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+    })
+}
+
+#[test]
+fn set_funding_threshold_is_validated() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        assert_noop!(
+            Proposals::set_funding_threshold(RuntimeOrigin::signed(*ALICE), project_key, 0),
+            Error::<Test>::InvalidFundingThreshold
+        );
+        assert_noop!(
+            Proposals::set_funding_threshold(RuntimeOrigin::signed(*ALICE), project_key, 101),
+            Error::<Test>::InvalidFundingThreshold
+        );
+        assert_noop!(
+            Proposals::set_funding_threshold(RuntimeOrigin::signed(*BOB), project_key, 50),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 1,
+            bounded_vec![project_key],
+            RoundType::ContributionRound,
+        ));
+        assert_noop!(
+            Proposals::set_funding_threshold(RuntimeOrigin::signed(*ALICE), project_key, 50),
+            Error::<Test>::ProjectAlreadyApproved
+        );
+    })
+}
+
+#[test]
+fn withdrawal_fee_is_split_between_treasury_burn_and_collators() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(Proposals::set_fee_split(
+            RuntimeOrigin::root(),
+            FeeSplit {
+                treasury: Perbill::from_percent(50),
+                burn: Perbill::from_percent(25),
+                collators: Perbill::from_percent(25),
+            }
+        ));
+        let project_key = create_funded_and_approved_project(500_000, 500_000);
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let treasury_balance = Tokens::free_balance(CurrencyId::Native, &treasury_account);
+        let total_issuance = Tokens::total_issuance(CurrencyId::Native);
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        let fee = calc_imbue_fee(1_000_000u64);
+        assert!(System::events().iter().any(|record| record.event
+            == mock::RuntimeEvent::from(proposals::Event::FeeDistributed(
+                project_key,
+                CurrencyId::Native,
+                fee / 2,
+                fee / 4,
+                fee / 4
+            ))));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
+            treasury_balance + fee / 2
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &CollatorPot::get()),
+            fee / 4
+        );
+        assert_eq!(
+            Tokens::total_issuance(CurrencyId::Native),
+            total_issuance - fee / 4
+        );
+    })
+}
+
+#[test]
+fn withdrawal_fee_rate_is_set_per_funding_type() {
+    build_test_externality().execute_with(|| {
+        assert_ok!(Proposals::set_fee_rate(
+            RuntimeOrigin::root(),
+            FundingType::Proposal,
+            Some(2)
+        ));
+        assert_eq!(Proposals::fee_rates(FundingType::Proposal), Some(2));
+        let project_key = create_funded_and_approved_project(500_000, 500_000);
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::ProjectFundsWithdrawn(
+                *ALICE,
+                project_key,
+                980_000u64,
+                CurrencyId::Native
+            ))
+        );
+
+        assert_ok!(Proposals::set_fee_rate(
+            RuntimeOrigin::root(),
+            FundingType::Proposal,
+            None
+        ));
+        assert_eq!(Proposals::fee_rates(FundingType::Proposal), None);
+    })
+}
+
+#[test]
+fn fee_schedule_is_validated() {
+    build_test_externality().execute_with(|| {
+        let fee_split = FeeSplit {
+            treasury: Perbill::from_percent(50),
+            burn: Perbill::from_percent(25),
+            collators: Perbill::from_percent(20),
+        };
+        assert_noop!(
+            Proposals::set_fee_split(RuntimeOrigin::root(), fee_split),
+            Error::<Test>::InvalidFeeSplit
+        );
+        assert_noop!(
+            Proposals::set_fee_split(RuntimeOrigin::signed(*ALICE), FeeSplit::default()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Proposals::set_fee_rate(RuntimeOrigin::root(), FundingType::Brief, Some(100)),
+            Error::<Test>::InvalidFeeRate
+        );
+        assert_noop!(
+            Proposals::set_fee_rate(RuntimeOrigin::signed(*ALICE), FundingType::Brief, Some(1)),
+            DispatchError::BadOrigin
+        );
+    })
+}
+
+#[test]
+fn runtime_api_queries_project_state() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_eq!(
+            Proposals::project_active_rounds(project_key),
+            vec![round_key]
+        );
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            1_000_000
+        ));
+        assert_eq!(
+            Proposals::project_contribution(project_key, *BOB).map(|c| c.value),
+            Some(1_000_000u64)
+        );
+        assert_eq!(Proposals::project_contribution(project_key, *CHARLIE), None);
+        assert_eq!(Proposals::withdrawable_funds(project_key), 0u64);
+
+        run_to_block(System::block_number() + 11);
+        assert!(Proposals::project_active_rounds(project_key).is_empty());
+        assert!(!ProjectRounds::<Test>::contains_key(project_key, round_key));
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            Some(round_key),
+            project_key,
+            None
+        ));
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        let voting_round_key = RoundCount::<Test>::get();
+        assert_eq!(
+            Proposals::project_active_rounds(project_key),
+            vec![voting_round_key]
+        );
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+
+        assert_eq!(
+            Proposals::project_milestone_votes(project_key),
+            vec![(0, MilestoneVotes::<Test>::get((project_key, 0)).unwrap())]
+        );
+        assert_eq!(Proposals::withdrawable_funds(project_key), 1_000_000u64);
+    })
+}
+
+#[test]
+fn initiator_can_cancel_project_and_contributors_are_refunded() {
+    build_test_externality().execute_with(|| {
+        let alice_initial = Tokens::free_balance(CurrencyId::Native, &ALICE);
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let charlie_initial = Tokens::free_balance(CurrencyId::Native, &CHARLIE);
+        let project_key = create_funded_and_approved_project(600_000u64, 400_000u64);
+
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        assert_eq!(Tokens::free_balance(CurrencyId::Native, &BOB), bob_initial);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &CHARLIE),
+            charlie_initial
+        );
+        // The storage deposit has been returned.
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &ALICE),
+            alice_initial
+        );
+        assert!(Projects::<Test>::get(project_key).unwrap().cancelled);
+        assert!(Proposals::project_contributions(project_key, *BOB).is_none());
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::ProjectCancelledByInitiator(
+                *ALICE,
+                project_key
+            ))
+        );
+
+        assert_noop!(
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1)),
+            Error::<Test>::ProjectWithdrawn
+        );
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::ProjectCancelled
+        );
+    })
+}
+
+#[test]
+fn cancelling_project_pays_out_approved_milestones_to_initiator() {
+    build_test_externality().execute_with(|| {
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let project_key = ProjectCount::<Test>::get();
+        assert_ok!(create_project_multiple_milestones(vec![
+            ProposedMilestone {
+                percentage_to_unlock: 40,
+                ..Default::default()
+            },
+            ProposedMilestone {
+                percentage_to_unlock: 60,
+                ..Default::default()
+            },
+        ]));
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            1_000_000u64
+        ));
+        run_to_block(System::block_number() + 11);
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            Some(round_key),
+            project_key,
+            Some(bounded_vec![0])
+        ));
+
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        // The first milestone is paid to the initiator less the fee, the rest is refunded.
+        assert!(System::events().iter().any(|record| record.event
+            == mock::RuntimeEvent::from(proposals::Event::ProjectFundsWithdrawn(
+                *ALICE,
+                project_key,
+                380_000u64,
+                CurrencyId::Native
+            ))));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_initial - 400_000u64
+        );
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::project_account_id(project_key)
+            ),
+            0
+        );
+        assert_eq!(
+            Projects::<Test>::get(project_key).unwrap().withdrawn_funds,
+            400_000u64
+        );
+    })
+}
+
+#[test]
+fn cancelled_project_is_removed_from_its_rounds_and_cannot_be_contributed_to() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000u64
+        ));
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        assert!(Proposals::rounds(round_key)
+            .unwrap()
+            .project_keys
+            .is_empty());
+        assert!(Proposals::project_active_rounds(project_key).is_empty());
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*CHARLIE),
+                Some(round_key),
+                project_key,
+                100_000u64
+            ),
+            Error::<Test>::ProjectNotInRound
+        );
+
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*CHARLIE),
+                Some(round_key),
+                project_key,
+                100_000u64
+            ),
+            Error::<Test>::ProjectCancelled
+        );
+        assert_noop!(
+            Proposals::withdraw_contribution(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                1u64
+            ),
+            Error::<Test>::ProjectCancelled
+        );
+    })
+}
+
+#[test]
+fn cancelling_a_project_only_refunds_funds_locked_in_unapproved_milestones() {
+    build_test_externality().execute_with(|| {
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let project_key = ProjectCount::<Test>::get();
+        assert_ok!(create_project_multiple_milestones(vec![
+            ProposedMilestone {
+                percentage_to_unlock: 40,
+                ..Default::default()
+            },
+            ProposedMilestone {
+                percentage_to_unlock: 60,
+                ..Default::default()
+            },
+        ]));
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            1_000_000u64
+        ));
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            Some(round_key),
+            project_key,
+            None
+        ));
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0,
+            None,
+            true
+        ));
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_initial - 400_000u64
+        );
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::project_account_id(project_key)
+            ),
+            0u64
+        );
+    })
+}
+
+#[test]
+fn cancel_project_is_validated() {
+    build_test_externality().execute_with(|| {
+        assert_noop!(
+            Proposals::cancel_project(RuntimeOrigin::signed(*ALICE), 0),
+            Error::<Test>::ProjectDoesNotExist
+        );
+        let project_key = create_project_key();
+        assert_noop!(
+            Proposals::cancel_project(RuntimeOrigin::signed(*BOB), project_key),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_noop!(
+            Proposals::cancel_project(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::ProjectWithdrawn
+        );
+    })
+}
+
+#[test]
+fn add_and_remove_jurors() {
+    build_test_externality().execute_with(|| {
+        let juror = *ALICE;
+        assert_noop!(
+            Proposals::add_juror(RuntimeOrigin::signed(*BOB), juror),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Proposals::add_juror(RuntimeOrigin::root(), juror));
+        assert_eq!(Proposals::jurors().into_inner(), vec![juror]);
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &juror),
+            <Test as Config>::JurorBond::get()
+        );
+        assert_noop!(
+            Proposals::add_juror(RuntimeOrigin::root(), juror),
+            Error::<Test>::AlreadyJuror
+        );
+
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let treasury_before = Tokens::free_balance(CurrencyId::Native, &treasury_account);
+        assert_ok!(Proposals::remove_juror(RuntimeOrigin::root(), juror, true));
+        assert!(Proposals::jurors().is_empty());
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &juror), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
+            treasury_before + <Test as Config>::JurorBond::get()
+        );
+        assert_noop!(
+            Proposals::remove_juror(RuntimeOrigin::root(), juror, false),
+            Error::<Test>::NotJuror
+        );
+    })
+}
+
+#[test]
+fn juror_set_is_bounded() {
+    build_test_externality().execute_with(|| {
+        let _ = create_jurors(<Test as Config>::MaxJurors::get() as u8);
+        let juror = create_funded_account(100);
+        assert_noop!(
+            Proposals::add_juror(RuntimeOrigin::root(), juror),
+            Error::<Test>::TooManyJurors
+        );
+    })
+}
+
+#[test]
+fn initiator_can_dispute_a_rejected_milestone_and_win() {
+    build_test_externality().execute_with(|| {
+        let jurors = create_jurors(3);
+        let project_key = create_rejected_milestone();
+        let alice_reserved = Tokens::reserved_balance(CurrencyId::Native, &ALICE);
+
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0
+        ));
+        assert_eq!(
+            Proposals::disputes(project_key, 0).unwrap().status,
+            DisputeStatus::Drawing
+        );
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved + <Test as Config>::DisputeBond::get()
+        );
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::MilestoneDisputed
+        );
+        assert_noop!(
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(2)),
+            Error::<Test>::MilestoneDisputed
+        );
+        assert_noop!(
+            Proposals::vote_on_dispute(RuntimeOrigin::signed(jurors[0]), project_key, 0, true),
+            Error::<Test>::DisputeVotingClosed
+        );
+
+        let dispute = draw_dispute_panel(project_key, 0);
+        assert_eq!(dispute.status, DisputeStatus::Arbitration);
+        assert_eq!(dispute.jurors.len(), 3);
+
+        for (i, juror) in jurors.iter().enumerate() {
+            assert_ok!(Proposals::vote_on_dispute(
+                RuntimeOrigin::signed(*juror),
+                project_key,
+                0,
+                i != 0
+            ));
+        }
+
+        assert!(Proposals::disputes(project_key, 0).is_none());
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::DisputeResolved(project_key, 0, true))
+        );
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+    })
+}
+
+#[test]
+fn contributors_dispute_escalates_once_threshold_backs_it() {
+    build_test_externality().execute_with(|| {
+        let jurors = create_jurors(3);
+        let project_key = create_approved_milestone(400_000, 600_000);
+
+        // BOB holds 40% of the raised funds, below the 50% threshold.
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0
+        ));
+        let dispute = Proposals::disputes(project_key, 0).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Backing);
+        assert_eq!(dispute.support, 400_000);
+        assert_noop!(
+            Proposals::raise_dispute(RuntimeOrigin::signed(*BOB), project_key, 0),
+            Error::<Test>::DisputeAlreadyRaised
+        );
+        assert_noop!(
+            Proposals::vote_on_dispute(RuntimeOrigin::signed(jurors[0]), project_key, 0, false),
+            Error::<Test>::DisputeVotingClosed
+        );
+
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            0
+        ));
+        let dispute = draw_dispute_panel(project_key, 0);
+        assert_eq!(dispute.status, DisputeStatus::Arbitration);
+        assert_eq!(dispute.backers.into_inner(), vec![*BOB, *CHARLIE]);
+
+        // The panel rejects the milestone before the deadline, the rest of the panel need not vote.
+        for juror in jurors.iter().take(2) {
+            assert_ok!(Proposals::vote_on_dispute(
+                RuntimeOrigin::signed(*juror),
+                project_key,
+                0,
+                false
+            ));
+        }
+        run_to_block(dispute.deadline);
+
+        assert!(Proposals::disputes(project_key, 0).is_none());
+        assert!(!Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert!(MilestoneVotes::<Test>::get((project_key, 0)).is_none());
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &BOB), 0);
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &CHARLIE), 0);
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::NoAvailableFundsToWithdraw
+        );
+    })
+}
+
+#[test]
+fn losing_a_dispute_slashes_the_bonds() {
+    build_test_externality().execute_with(|| {
+        let jurors = create_jurors(3);
+        let project_key = create_approved_milestone(600_000, 400_000);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let treasury_before = Tokens::free_balance(CurrencyId::Native, &treasury_account);
+        let bob_before = Tokens::free_balance(CurrencyId::Native, &BOB);
+
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0
+        ));
+        let _ = draw_dispute_panel(project_key, 0);
+        // A tie keeps the milestone approved.
+        assert_ok!(Proposals::vote_on_dispute(
+            RuntimeOrigin::signed(jurors[0]),
+            project_key,
+            0,
+            false
+        ));
+        assert_ok!(Proposals::vote_on_dispute(
+            RuntimeOrigin::signed(jurors[1]),
+            project_key,
+            0,
+            true
+        ));
+        run_to_block(Proposals::disputes(project_key, 0).unwrap().deadline);
+
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_before - <Test as Config>::DisputeBond::get()
+        );
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &BOB), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
+            treasury_before + <Test as Config>::DisputeBond::get()
+        );
+    })
+}
+
+#[test]
+fn unbacked_dispute_expires_and_slashes_bonds() {
+    build_test_externality().execute_with(|| {
+        let _ = create_jurors(3);
+        let project_key = create_approved_milestone(400_000, 600_000);
+        let treasury_account =
+            <Test as Config>::RefundHandler::get_treasury_account_id(TreasuryOrigin::Imbue)
+                .unwrap();
+        let treasury_before = Tokens::free_balance(CurrencyId::Native, &treasury_account);
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0
+        ));
+        run_to_block(System::block_number() + <Test as Config>::DisputeVotingPeriod::get());
+
+        assert!(Proposals::disputes(project_key, 0).is_none());
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &BOB), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasury_account),
+            treasury_before + <Test as Config>::DisputeBond::get()
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::DisputeExpired(project_key, 0))
+        );
+        assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+    })
+}
+
+#[test]
+fn dispute_is_dropped_when_no_jurors_are_left_to_draw() {
+    build_test_externality().execute_with(|| {
+        let jurors = create_jurors(1);
+        let project_key = create_rejected_milestone();
+        let alice_reserved = Tokens::reserved_balance(CurrencyId::Native, &ALICE);
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0
+        ));
+        assert_ok!(Proposals::remove_juror(
+            RuntimeOrigin::root(),
+            jurors[0],
+            false
+        ));
+
+        run_to_block(Proposals::disputes(project_key, 0).unwrap().deadline);
+        assert!(Proposals::disputes(project_key, 0).is_none());
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::DisputeDropped(project_key, 0))
+        );
+    })
+}
+
+#[test]
+fn dispute_waiting_for_backers_holds_back_the_disputed_milestone() {
+    build_test_externality().execute_with(|| {
+        let _ = create_jurors(3);
+        let project_key = create_approved_milestone(400_000, 600_000);
+        assert_eq!(Proposals::withdrawable_funds(project_key), 1_000_000u64);
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0
+        ));
+        let dispute = Proposals::disputes(project_key, 0).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Backing);
+        assert_eq!(Proposals::withdrawable_funds(project_key), 0);
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::NoAvailableFundsToWithdraw
+        );
+
+        // The funds are released once the dispute expires without enough backing.
+        run_to_block(dispute.deadline);
+        assert!(Proposals::disputes(project_key, 0).is_none());
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+    })
+}
+
+#[test]
+fn raise_dispute_is_validated() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_approved_milestone(600_000, 400_000);
+        // The milestone is approved so the initiator has nothing to dispute.
+        assert_noop!(
+            Proposals::raise_dispute(RuntimeOrigin::signed(*ALICE), project_key, 0),
+            Error::<Test>::MilestoneNotDisputable
+        );
+        assert_noop!(
+            Proposals::raise_dispute(RuntimeOrigin::signed(*BOB), project_key, 1),
+            Error::<Test>::MilestoneDoesNotExist
+        );
+        // Jurors with a stake in the project cannot sit on its panel.
+        assert_ok!(Proposals::add_juror(RuntimeOrigin::root(), *CHARLIE));
+        assert_noop!(
+            Proposals::raise_dispute(RuntimeOrigin::signed(*BOB), project_key, 0),
+            Error::<Test>::NotEnoughJurors
+        );
+        let jurors = create_jurors(1);
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            0
+        ));
+        assert_eq!(
+            draw_dispute_panel(project_key, 0).jurors.into_inner(),
+            jurors
+        );
+        assert_noop!(
+            Proposals::vote_on_dispute(RuntimeOrigin::signed(*CHARLIE), project_key, 0, true),
+            Error::<Test>::NotOnDisputePanel
+        );
+
+        // Once all of its funds have been withdrawn the project is removed.
+        let project_key = create_approved_milestone(600_000, 400_000);
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_noop!(
+            Proposals::raise_dispute(RuntimeOrigin::signed(*BOB), project_key, 0),
+            Error::<Test>::ProjectDoesNotExist
+        );
+    })
+}
+
+#[test]
+fn cannot_cancel_a_project_while_a_milestone_is_disputed() {
+    build_test_externality().execute_with(|| {
+        let _ = create_jurors(3);
+        let project_key = create_rejected_milestone();
+        let alice_reserved = Tokens::reserved_balance(CurrencyId::Native, &ALICE);
+        assert_ok!(Proposals::raise_dispute(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0
+        ));
+        let dispute = draw_dispute_panel(project_key, 0);
+        assert_eq!(dispute.status, DisputeStatus::Arbitration);
+        assert_noop!(
+            Proposals::cancel_project(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::MilestoneDisputed
+        );
+
+        // Nobody on the panel votes so the rejection stands and the bond is slashed.
+        run_to_block(dispute.deadline);
+        assert!(Proposals::disputes(project_key, 0).is_none());
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            alice_reserved - <Test as Config>::ProjectStorageDeposit::get()
+        );
+    })
+}
+
+#[test]
+fn approved_milestone_funds_are_streamed_to_the_initiator() {
+    build_test_externality().execute_with(|| {
+        let (project_key, approved_at) = create_vesting_project(100);
+        let alice_before = Tokens::free_balance(CurrencyId::Native, &ALICE);
+        assert_eq!(
+            MilestoneVestingStarts::<Test>::get(project_key, 0),
+            Some(approved_at)
+        );
+
+        run_to_block(approved_at + 50);
+        assert_eq!(Proposals::withdrawable_funds(project_key), 500_000u64);
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &ALICE),
+            alice_before + deduct_imbue_fee(500_000u64)
+        );
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(*ALICE), project_key),
+            Error::<Test>::NoAvailableFundsToWithdraw
+        );
+
+        // Nothing more vests once the period is over.
+        run_to_block(approved_at + 200);
+        assert_eq!(Proposals::withdrawable_funds(project_key), 500_000u64);
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &ALICE),
+            alice_before + deduct_imbue_fee(1_000_000u64)
+        );
+        assert!(Projects::<Test>::get(project_key).is_none());
+        assert!(PayoutVestingPeriods::<Test>::get(project_key).is_none());
+        assert!(MilestoneVestingStarts::<Test>::get(project_key, 0).is_none());
+    })
+}
+
+#[test]
+fn no_confidence_refunds_the_unvested_funds_of_approved_milestones() {
+    build_test_externality().execute_with(|| {
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let (project_key, approved_at) = create_vesting_project(100);
+
+        run_to_block(approved_at + 25);
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_ok!(Proposals::raise_vote_of_no_confidence(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert_ok!(Proposals::finalise_no_confidence_round(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key
+        ));
+
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_initial - 250_000u64
+        );
+        assert_eq!(
+            Tokens::free_balance(
+                CurrencyId::Native,
+                &Proposals::project_account_id(project_key)
+            ),
+            0u64
+        );
+    })
+}
+
+#[test]
+fn set_payout_vesting_is_validated() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        assert_noop!(
+            Proposals::set_payout_vesting(RuntimeOrigin::signed(*BOB), project_key, Some(10)),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_noop!(
+            Proposals::set_payout_vesting(RuntimeOrigin::signed(*ALICE), project_key, Some(0)),
+            Error::<Test>::InvalidVestingPeriod
+        );
+        assert_ok!(Proposals::set_payout_vesting(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            Some(10)
+        ));
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::PayoutVestingSet(project_key, Some(10)))
+        );
+        assert_ok!(Proposals::set_payout_vesting(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            None
+        ));
+        assert!(PayoutVestingPeriods::<Test>::get(project_key).is_none());
+
+        schedule_matching_round(vec![project_key]);
+        assert_noop!(
+            Proposals::set_payout_vesting(RuntimeOrigin::signed(*ALICE), project_key, Some(10)),
+            Error::<Test>::ProjectAlreadyApproved
+        );
+    })
+}
+
+#[test]
+fn initiator_transfer_moves_the_storage_deposit() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let deposit = <Test as Config>::ProjectStorageDeposit::get();
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::Native, &ALICE),
+            deposit
+        );
+
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            *BOB
+        ));
+        assert!(
+            Proposals::initiator_transfers(project_key)
+                .unwrap()
+                .approved
+        );
+        assert_noop!(
+            Proposals::accept_initiator_transfer(RuntimeOrigin::signed(*CHARLIE), project_key),
+            Error::<Test>::NotNewInitiator
+        );
+        assert_ok!(Proposals::accept_initiator_transfer(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+
+        assert_eq!(Projects::<Test>::get(project_key).unwrap().initiator, *BOB);
+        assert!(Proposals::initiator_transfers(project_key).is_none());
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &ALICE), 0);
+        assert_eq!(Tokens::reserved_balance(CurrencyId::Native, &BOB), deposit);
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::InitiatorTransferred(
+                project_key,
+                *ALICE,
+                *BOB
+            ))
+        );
+        assert_noop!(
+            Proposals::propose_initiator_transfer(
+                RuntimeOrigin::signed(*ALICE),
+                project_key,
+                *CHARLIE
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+    })
+}
+
+#[test]
+fn funded_project_transfer_needs_contributor_approval() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let new_initiator = create_funded_account(50);
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            new_initiator
+        ));
+        assert_noop!(
+            Proposals::accept_initiator_transfer(RuntimeOrigin::signed(new_initiator), project_key),
+            Error::<Test>::InitiatorTransferNotApproved
+        );
+
+        // BOB's 60% of the raised funds is below the 75% required.
+        assert_ok!(Proposals::vote_on_initiator_transfer(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            true
+        ));
+        assert!(
+            !Proposals::initiator_transfers(project_key)
+                .unwrap()
+                .approved
+        );
+        assert_noop!(
+            Proposals::vote_on_initiator_transfer(RuntimeOrigin::signed(*BOB), project_key, true),
+            Error::<Test>::VoteAlreadyExists
+        );
+        assert_ok!(Proposals::vote_on_initiator_transfer(
+            RuntimeOrigin::signed(*CHARLIE),
+            project_key,
+            true
+        ));
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::InitiatorTransferApproved(project_key))
+        );
+
+        assert_ok!(Proposals::accept_initiator_transfer(
+            RuntimeOrigin::signed(new_initiator),
+            project_key
+        ));
+        assert_noop!(
+            Proposals::submit_milestone(RuntimeOrigin::signed(*ALICE), project_key, 0, gen_hash(1)),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(new_initiator),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+    })
+}
+
+#[test]
+fn transfer_proposed_before_funding_needs_contributor_approval() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let new_initiator = create_funded_account(50);
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            new_initiator
+        ));
+        assert!(
+            Proposals::initiator_transfers(project_key)
+                .unwrap()
+                .approved
+        );
+
+        assert_ok!(Proposals::schedule_round(
+            RuntimeOrigin::root(),
+            System::block_number(),
+            System::block_number() + 1,
+            bounded_vec![project_key],
+            RoundType::ContributionRound,
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key,
+            1_000_000u64
+        ));
+        run_to_block(System::block_number() + 2);
+        assert_ok!(Proposals::approve(
+            RuntimeOrigin::root(),
+            None,
+            project_key,
+            None
+        ));
+        assert_noop!(
+            Proposals::accept_initiator_transfer(RuntimeOrigin::signed(new_initiator), project_key),
+            Error::<Test>::InitiatorTransferNotApproved
+        );
+
+        assert_ok!(Proposals::vote_on_initiator_transfer(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            true
+        ));
+        assert_ok!(Proposals::accept_initiator_transfer(
+            RuntimeOrigin::signed(new_initiator),
+            project_key
+        ));
+        assert_eq!(
+            Projects::<Test>::get(project_key).unwrap().initiator,
+            new_initiator
+        );
+    })
+}
+
+#[test]
+fn contributors_can_reject_an_initiator_transfer() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            *CHARLIE
+        ));
+        assert_ok!(Proposals::vote_on_initiator_transfer(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            false
+        ));
+        assert!(Proposals::initiator_transfers(project_key).is_none());
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::InitiatorTransferRejected(project_key))
+        );
+        assert_noop!(
+            Proposals::accept_initiator_transfer(RuntimeOrigin::signed(*CHARLIE), project_key),
+            Error::<Test>::NoInitiatorTransfer
+        );
+
+        // A new proposal starts a fresh vote.
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            *CHARLIE
+        ));
+        assert_ok!(Proposals::vote_on_initiator_transfer(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            true
+        ));
+    })
+}
+
+#[test]
+fn initiator_transfer_is_validated() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        assert_noop!(
+            Proposals::propose_initiator_transfer(RuntimeOrigin::signed(*BOB), project_key, *BOB),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_noop!(
+            Proposals::propose_initiator_transfer(
+                RuntimeOrigin::signed(*ALICE),
+                project_key,
+                *ALICE
+            ),
+            Error::<Test>::InvalidAccount
+        );
+        assert_noop!(
+            Proposals::vote_on_initiator_transfer(RuntimeOrigin::signed(*BOB), project_key, true),
+            Error::<Test>::NoInitiatorTransfer
+        );
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            *BOB
+        ));
+        assert_noop!(
+            Proposals::vote_on_initiator_transfer(RuntimeOrigin::signed(*BOB), project_key, true),
+            Error::<Test>::InitiatorTransferAlreadyApproved
+        );
+
+        // The new initiator must be able to reserve the storage deposit.
+        let broke = sr25519::Public::from_raw([60u8; 32]);
+        assert_ok!(Proposals::propose_initiator_transfer(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            broke
+        ));
+        assert_noop!(
+            Proposals::accept_initiator_transfer(RuntimeOrigin::signed(broke), project_key),
+            Error::<Test>::ImbueRequiredForStorageDep
+        );
+    })
+}
+
+#[test]
+fn team_members_act_in_their_roles_and_share_payouts() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        let submitter = create_funded_account(70);
+        let treasurer = create_funded_account(71);
+        assert_ok!(Proposals::set_project_team(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            bounded_vec![
+                TeamMember {
+                    account: submitter,
+                    role: TeamRole::Submitter,
+                    payout_share: 20,
+                },
+                TeamMember {
+                    account: treasurer,
+                    role: TeamRole::Treasurer,
+                    payout_share: 30,
+                },
+            ]
+        ));
+
+        assert_noop!(
+            Proposals::submit_milestone(
+                RuntimeOrigin::signed(treasurer),
+                project_key,
+                0,
+                gen_hash(1)
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(submitter),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        for contributor in [*BOB, *CHARLIE] {
+            assert_ok!(Proposals::vote_on_milestone(
+                RuntimeOrigin::signed(contributor),
+                project_key,
+                0,
+                None,
+                true
+            ));
+        }
+
+        assert_noop!(
+            Proposals::withdraw(RuntimeOrigin::signed(submitter), project_key),
+            Error::<Test>::InvalidAccount
+        );
+        let alice_before = Tokens::free_balance(CurrencyId::Native, &ALICE);
+        let submitter_before = Tokens::free_balance(CurrencyId::Native, &submitter);
+        let treasurer_before = Tokens::free_balance(CurrencyId::Native, &treasurer);
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(treasurer),
+            project_key
+        ));
+
+        let withdrawn = deduct_imbue_fee(1_000_000u64);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &submitter),
+            submitter_before + withdrawn * 20 / 100
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &treasurer),
+            treasurer_before + withdrawn * 30 / 100
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &ALICE),
+            alice_before + withdrawn * 50 / 100
+        );
+        assert!(Proposals::project_teams(project_key).is_empty());
+    })
+}
+
+#[test]
+fn set_project_team_is_validated() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let member = |account: AccountId, role: TeamRole, payout_share: u8| TeamMember {
+            account,
+            role,
+            payout_share,
+        };
+        assert_noop!(
+            Proposals::set_project_team(
+                RuntimeOrigin::signed(*BOB),
+                project_key,
+                bounded_vec![member(*BOB, TeamRole::Admin, 0)]
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_noop!(
+            Proposals::set_project_team(
+                RuntimeOrigin::signed(*ALICE),
+                project_key,
+                bounded_vec![
+                    member(*BOB, TeamRole::Admin, 10),
+                    member(*BOB, TeamRole::Treasurer, 10)
+                ]
+            ),
+            Error::<Test>::InvalidTeam
+        );
+        assert_noop!(
+            Proposals::set_project_team(
+                RuntimeOrigin::signed(*ALICE),
+                project_key,
+                bounded_vec![
+                    member(*BOB, TeamRole::Admin, 60),
+                    member(*CHARLIE, TeamRole::Treasurer, 41)
+                ]
+            ),
+            Error::<Test>::InvalidTeam
+        );
+
+        assert_ok!(Proposals::set_project_team(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            bounded_vec![member(*BOB, TeamRole::Admin, 60)]
+        ));
+        // Admins can manage the team, but not the payout shares.
+        assert_noop!(
+            Proposals::set_project_team(
+                RuntimeOrigin::signed(*BOB),
+                project_key,
+                bounded_vec![
+                    member(*BOB, TeamRole::Admin, 60),
+                    member(*CHARLIE, TeamRole::Submitter, 40)
+                ]
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_noop!(
+            Proposals::set_project_team(
+                RuntimeOrigin::signed(*BOB),
+                project_key,
+                bounded_vec![member(*BOB, TeamRole::Admin, 100)]
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::set_project_team(
+            RuntimeOrigin::signed(*BOB),
+            project_key,
+            bounded_vec![
+                member(*BOB, TeamRole::Admin, 60),
+                member(*CHARLIE, TeamRole::Submitter, 0)
+            ]
+        ));
+        assert_noop!(
+            Proposals::set_project_team(
+                RuntimeOrigin::signed(*CHARLIE),
+                project_key,
+                bounded_vec![]
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::set_project_team(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            bounded_vec![]
+        ));
+        assert!(!ProjectTeams::<Test>::contains_key(project_key));
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::ProjectTeamSet(project_key, vec![]))
+        );
+    })
+}
+
+#[test]
+fn initiator_bond_is_returned_once_the_project_completes() {
+    build_test_externality().execute_with(|| {
+        let bond = <Test as Config>::InitiatorBond::get(&CurrencyId::KSM);
+        let project_key = create_bonded_project(600_000, 400_000);
+        assert_eq!(Proposals::initiator_bonds(project_key), Some(bond));
+        assert_eq!(Tokens::reserved_balance(CurrencyId::KSM, &ALICE), bond);
+
+        assert_ok!(Proposals::submit_milestone(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            0,
+            gen_hash(1)
+        ));
+        run_to_block(System::block_number() + 1);
+        for contributor in [*BOB, *CHARLIE] {
+            assert_ok!(Proposals::vote_on_milestone(
+                RuntimeOrigin::signed(contributor),
+                project_key,
+                0,
+                None,
+                true
+            ));
+        }
+        assert_ok!(Proposals::withdraw(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        assert!(Proposals::initiator_bonds(project_key).is_none());
+        assert_eq!(Tokens::reserved_balance(CurrencyId::KSM, &ALICE), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::KSM, &ALICE),
+            10_000_000u64 + deduct_imbue_fee(1_000_000u64)
+        );
+    })
+}
+
+#[test]
+fn initiator_bond_is_slashed_to_contributors_on_no_confidence() {
+    build_test_externality().execute_with(|| {
+        let bond = <Test as Config>::InitiatorBond::get(&CurrencyId::KSM);
+        let project_key = create_bonded_project(600_000, 400_000);
+        assert_ok!(Proposals::raise_vote_of_no_confidence(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert_ok!(Proposals::vote_on_no_confidence_round(
+            RuntimeOrigin::signed(*CHARLIE),
+            None,
+            project_key,
+            false
+        ));
+        assert_ok!(Proposals::finalise_no_confidence_round(
+            RuntimeOrigin::signed(*BOB),
+            None,
+            project_key
+        ));
+
+        assert!(Proposals::initiator_bonds(project_key).is_none());
+        assert_eq!(Tokens::reserved_balance(CurrencyId::KSM, &ALICE), 0);
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::KSM, &ALICE),
+            10_000_000u64 - bond
+        );
+        // Contributors are refunded and share the bond.
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::KSM, &BOB),
+            10_000_000u64 + bond * 60 / 100
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::KSM, &CHARLIE),
+            10_000_000u64 + bond * 40 / 100
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == mock::RuntimeEvent::from(proposals::Event::InitiatorBondSlashed(
+                project_key,
+                bond
+            ))));
+    })
+}
+
+#[test]
+fn initiator_bond_is_required_and_returned_when_cancelling_an_unfunded_project() {
+    build_test_externality().execute_with(|| {
+        let bond = <Test as Config>::InitiatorBond::get(&CurrencyId::KSM);
+        assert_noop!(
+            Proposals::create_project(
+                RuntimeOrigin::signed(*ALICE),
+                gen_hash(1),
+                bounded_vec![ProposedMilestone {
+                    percentage_to_unlock: 100,
+                    ..Default::default()
+                }],
+                1_000_000u64,
+                CurrencyId::KSM,
+                None
+            ),
+            Error::<Test>::InitiatorBondRequired
+        );
+
+        // No bond is set for the native currency.
+        let project_key = create_project_key();
+        assert!(Proposals::initiator_bonds(project_key).is_none());
+
+        let _ = Tokens::deposit(CurrencyId::KSM, &ALICE, bond);
+        let project_key = ProjectCount::<Test>::get();
+        assert_ok!(Proposals::create_project(
+            RuntimeOrigin::signed(*ALICE),
+            gen_hash(1),
+            bounded_vec![ProposedMilestone {
+                percentage_to_unlock: 100,
+                ..Default::default()
+            }],
+            1_000_000u64,
+            CurrencyId::KSM,
+            None
+        ));
+        assert_eq!(Tokens::reserved_balance(CurrencyId::KSM, &ALICE), bond);
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert_eq!(Tokens::reserved_balance(CurrencyId::KSM, &ALICE), 0);
+        assert_eq!(Tokens::free_balance(CurrencyId::KSM, &ALICE), bond);
+    })
+}
+
+#[test]
+fn converted_proposals_reserve_the_initiator_bond() {
+    build_test_externality().execute_with(|| {
+        let bond = <Test as Config>::InitiatorBond::get(&CurrencyId::KSM);
+        let storage_deposit = <Test as Config>::ProjectStorageDeposit::get();
+        let mut contributions = BTreeMap::new();
+        contributions.insert(
+            *BOB,
+            Contribution {
+                value: 100_000u64,
+                timestamp: 0u64,
+            },
+        );
+        let milestones = vec![ProposedMilestone {
+            percentage_to_unlock: 100,
+            ..Default::default()
+        }];
+
+        let _ = Tokens::deposit(CurrencyId::KSM, &ALICE, storage_deposit);
+        assert_eq!(
+            Proposals::convert_to_proposal(
+                CurrencyId::KSM,
+                contributions.clone(),
+                gen_hash(1),
+                *ALICE,
+                milestones.clone(),
+                FundingType::Treasury(TreasuryOrigin::Imbue),
+            ),
+            Err(Error::<Test>::InitiatorBondRequired.into())
+        );
+
+        let _ = Tokens::deposit(CurrencyId::KSM, &ALICE, bond);
+        assert_ok!(Proposals::convert_to_proposal(
+            CurrencyId::KSM,
+            contributions,
+            gen_hash(1),
+            *ALICE,
+            milestones,
+            FundingType::Treasury(TreasuryOrigin::Imbue),
+        ));
+        let project_key = ProjectCount::<Test>::get();
+        assert_eq!(Proposals::initiator_bonds(project_key), Some(bond));
+        assert_eq!(
+            Tokens::reserved_balance(CurrencyId::KSM, &ALICE),
+            storage_deposit + bond
+        );
+    })
+}
+
+#[test]
+fn contribution_receipts_track_top_ups_and_withdrawals() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        let started = System::block_number();
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000
+        ));
+        assert_eq!(
+            MockContributionReceipts::<Test>::receipt(project_key, &BOB),
+            Some((100_000, CurrencyId::Native, started))
+        );
+
+        run_to_block(started + 1);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            50_000
+        ));
+        assert_eq!(
+            MockContributionReceipts::<Test>::receipt(project_key, &BOB),
+            Some((150_000, CurrencyId::Native, started + 1))
+        );
+
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            50_000
+        ));
+        assert_eq!(
+            MockContributionReceipts::<Test>::receipt(project_key, &BOB),
+            Some((100_000, CurrencyId::Native, started + 1))
+        );
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000
+        ));
+        assert!(MockContributionReceipts::<Test>::receipt(project_key, &BOB).is_none());
+    })
+}
+
+#[test]
+fn refunded_contributors_have_their_receipts_burned() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_underfunded_project(100_000, 200_000);
+        assert_ok!(Proposals::claim_refund(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+        assert!(MockContributionReceipts::<Test>::receipt(project_key, &BOB).is_none());
+        assert!(MockContributionReceipts::<Test>::receipt(project_key, &CHARLIE).is_some());
+
+        let project_key = create_funded_and_approved_project(600_000, 400_000);
+        assert!(MockContributionReceipts::<Test>::receipt(project_key, &BOB).is_some());
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+        assert!(MockContributionReceipts::<Test>::receipt(project_key, &BOB).is_none());
+        assert!(MockContributionReceipts::<Test>::receipt(project_key, &CHARLIE).is_none());
+    })
+}
+
+#[test]
+fn contributors_prove_their_spot_in_a_merkle_whitelist() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let bob_leaf = Proposals::whitelist_leaf(&BOB, 150_000);
+        let charlie_leaf = Proposals::whitelist_leaf(&CHARLIE, 0);
+        let root = proofs::hashing::sort_hash_of::<WhitelistVerifier>(bob_leaf, charlie_leaf);
+        assert_noop!(
+            Proposals::set_project_whitelist_root(RuntimeOrigin::signed(*BOB), project_key, root),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::set_project_whitelist_root(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            root
+        ));
+        let round_key = schedule_matching_round(vec![project_key]);
+
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                100_000
+            ),
+            Error::<Test>::OnlyWhitelistedAccountsCanContribute
+        );
+        // A proof of someone else's spot, or of a higher cap, is rejected.
+        assert_noop!(
+            Proposals::contribute_with_proof(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                100_000,
+                0,
+                Proof::new(charlie_leaf, vec![bob_leaf])
+            ),
+            Error::<Test>::InvalidWhitelistProof
+        );
+        assert_noop!(
+            Proposals::contribute_with_proof(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                100_000,
+                200_000,
+                Proof::new(bob_leaf, vec![charlie_leaf])
+            ),
+            Error::<Test>::InvalidWhitelistProof
+        );
+        assert_noop!(
+            Proposals::contribute_with_proof(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                200_000,
+                150_000,
+                Proof::new(bob_leaf, vec![charlie_leaf])
+            ),
+            Error::<Test>::ContributionMustBeLowerThanMaxCap
+        );
+
+        assert_ok!(Proposals::contribute_with_proof(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            150_000,
+            150_000,
+            Proof::new(bob_leaf, vec![charlie_leaf])
+        ));
+        assert_ok!(Proposals::contribute_with_proof(
+            RuntimeOrigin::signed(*CHARLIE),
+            Some(round_key),
+            project_key,
+            500_000,
+            0,
+            Proof::new(charlie_leaf, vec![bob_leaf])
+        ));
+        assert_eq!(
+            Projects::<Test>::get(project_key).unwrap().raised_funds,
+            650_000
+        );
+    })
+}
+
+#[test]
+fn identity_policies_gate_large_contributors() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let policy = IdentityPolicy {
+            trusted_registrars: bounded_vec![0],
+            required_fields: IdentityFields(IdentityField::Display | IdentityField::Email),
+            initiators: false,
+            contributor_threshold: Some(100_000),
+            brief_applicants: false,
+        };
+        assert_noop!(
+            Proposals::set_project_identity_policy(
+                RuntimeOrigin::signed(*BOB),
+                project_key,
+                Some(policy.clone())
+            ),
+            Error::<Test>::UserIsNotInitiator
+        );
+        assert_ok!(Proposals::set_project_identity_policy(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            Some(policy)
+        ));
+        let round_key = schedule_matching_round(vec![project_key]);
+        let registrar = create_funded_account(200);
+
+        // Contributions up to the threshold do not need an identity.
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000
+        ));
+        assert_noop!(
+            Proposals::contribute(RuntimeOrigin::signed(*BOB), Some(round_key), project_key, 1),
+            Error::<Test>::IdentityNeeded
+        );
+
+        set_identity(*BOB, false);
+        assert_noop!(
+            Proposals::contribute(RuntimeOrigin::signed(*BOB), Some(round_key), project_key, 1),
+            Error::<Test>::InvalidAccount
+        );
+        judge_identity(registrar, *BOB);
+        assert_noop!(
+            Proposals::contribute(RuntimeOrigin::signed(*BOB), Some(round_key), project_key, 1),
+            Error::<Test>::IdentityFieldMissing
+        );
+
+        set_identity(*BOB, true);
+        judge_identity(registrar, *BOB);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            1
+        ));
+    })
+}
+
+#[test]
+fn project_identity_policies_cannot_loosen_the_default() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        assert_ok!(Proposals::set_default_identity_policy(
+            RuntimeOrigin::root(),
+            Some(IdentityPolicy {
+                trusted_registrars: bounded_vec![],
+                required_fields: IdentityFields(IdentityField::Display.into()),
+                initiators: false,
+                contributor_threshold: Some(50_000),
+                brief_applicants: false,
+            })
+        ));
+        assert_ok!(Proposals::set_project_identity_policy(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            Some(IdentityPolicy {
+                trusted_registrars: bounded_vec![0],
+                required_fields: IdentityFields(IdentityField::Email.into()),
+                initiators: false,
+                contributor_threshold: Some(100_000),
+                brief_applicants: false,
+            })
+        ));
+        assert_eq!(
+            Proposals::identity_policy_of(project_key),
+            Some(IdentityPolicy {
+                trusted_registrars: bounded_vec![0],
+                required_fields: IdentityFields(IdentityField::Display | IdentityField::Email),
+                initiators: false,
+                contributor_threshold: Some(50_000),
+                brief_applicants: false,
+            })
+        );
+
+        let round_key = schedule_matching_round(vec![project_key]);
+        let registrar = create_funded_account(200);
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                60_000
+            ),
+            Error::<Test>::IdentityNeeded
+        );
+        set_identity(*BOB, true);
+        judge_identity(registrar, *BOB);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            60_000
+        ));
+    })
+}
+
+#[test]
+fn default_identity_policy_applies_to_initiators() {
+    build_test_externality().execute_with(|| {
+        let policy = IdentityPolicy {
+            trusted_registrars: bounded_vec![],
+            required_fields: IdentityFields(IdentityField::Display.into()),
+            initiators: true,
+            contributor_threshold: None,
+            brief_applicants: false,
+        };
+        assert_noop!(
+            Proposals::set_default_identity_policy(
+                RuntimeOrigin::signed(*ALICE),
+                Some(policy.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Proposals::set_default_identity_policy(
+            RuntimeOrigin::root(),
+            Some(policy)
+        ));
+        assert_noop!(create_project(), Error::<Test>::IdentityNeeded);
+
+        set_identity(*ALICE, false);
+        judge_identity(create_funded_account(200), *ALICE);
+        assert_ok!(create_project());
+    })
+}
+
+#[test]
+fn contributions_are_checked_against_the_project_limits() {
+    build_test_externality().execute_with(|| {
+        let limits = ContributionLimits {
+            min_contribution: 10_000,
+            max_per_contributor: 400_000,
+            hard_cap: 600_000,
+        };
+        let project_key = ProjectCount::<Test>::get();
+        assert_noop!(
+            Proposals::create_project(
+                RuntimeOrigin::signed(*ALICE),
+                gen_hash(1),
+                bounded_vec![ProposedMilestone {
+                    percentage_to_unlock: 100,
+                    ..Default::default()
+                }],
+                1_000_000u64,
+                CurrencyId::Native,
+                Some(ContributionLimits {
+                    min_contribution: 500_000,
+                    ..limits
+                })
+            ),
+            Error::<Test>::InvalidContributionLimits
+        );
+        assert_ok!(Proposals::create_project(
+            RuntimeOrigin::signed(*ALICE),
+            gen_hash(1),
+            bounded_vec![ProposedMilestone {
+                percentage_to_unlock: 100,
+                ..Default::default()
+            }],
+            1_000_000u64,
+            CurrencyId::Native,
+            Some(limits)
+        ));
+        assert_eq!(Proposals::contribution_limits(project_key), Some(limits));
+        let round_key = schedule_matching_round(vec![project_key]);
+
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                9_999
+            ),
+            Error::<Test>::ContributionBelowMinimum
+        );
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            300_000
+        ));
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                100_001
+            ),
+            Error::<Test>::ContributionAboveMaximum
+        );
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            Some(round_key),
+            project_key,
+            300_000
+        ));
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                10_000
+            ),
+            Error::<Test>::HardCapReached
+        );
+    })
+}
+
+#[test]
+fn contribution_limits_can_be_updated_before_approval() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let limits = ContributionLimits {
+            min_contribution: 10_000,
+            ..Default::default()
+        };
+        assert_ok!(Proposals::update_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            bounded_vec![ProposedMilestone {
+                percentage_to_unlock: 100,
+                ..Default::default()
+            }],
+            1_000_000u64,
+            CurrencyId::Native,
+            gen_hash(1),
+            Some(limits)
+        ));
+        assert_eq!(Proposals::contribution_limits(project_key), Some(limits));
+        assert_ok!(Proposals::update_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key,
+            bounded_vec![ProposedMilestone {
+                percentage_to_unlock: 100,
+                ..Default::default()
+            }],
+            1_000_000u64,
+            CurrencyId::Native,
+            gen_hash(1),
+            None
+        ));
+        assert_eq!(Proposals::contribution_limits(project_key), None);
+    })
+}
+
+#[test]
+fn dust_contributions_in_foreign_currencies_are_rejected() {
+    build_test_externality().execute_with(|| {
+        let _ = Tokens::deposit(CurrencyId::KSM, &ALICE, 10_000_000u64);
+        let _ = Tokens::deposit(CurrencyId::KSM, &BOB, 10_000_000u64);
+        let project_key = ProjectCount::<Test>::get();
+        assert_ok!(Proposals::create_project(
+            RuntimeOrigin::signed(*ALICE),
+            gen_hash(1),
+            bounded_vec![ProposedMilestone {
+                percentage_to_unlock: 100,
+                ..Default::default()
+            }],
+            1_000_000u64,
+            CurrencyId::KSM,
+            None
+        ));
+        let round_key = schedule_matching_round(vec![project_key]);
+        let minimum = <Test as Config>::MinimumContribution::get(&CurrencyId::KSM);
+        assert_noop!(
+            Proposals::contribute(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                minimum - 1
+            ),
+            Error::<Test>::ContributionBelowMinimum
+        );
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            minimum
+        ));
+    })
+}
+
+#[test]
+fn withdrawals_cannot_leave_dust_contributions() {
+    build_test_externality().execute_with(|| {
+        let _ = Tokens::deposit(CurrencyId::KSM, &BOB, 10_000_000u64);
+        let project_key = ProjectCount::<Test>::get();
+        assert_ok!(Proposals::create_project(
+            RuntimeOrigin::signed(*ALICE),
+            gen_hash(1),
+            bounded_vec![ProposedMilestone {
+                percentage_to_unlock: 100,
+                ..Default::default()
+            }],
+            1_000_000u64,
+            CurrencyId::KSM,
+            None
+        ));
+        let round_key = schedule_matching_round(vec![project_key]);
+        let minimum = <Test as Config>::MinimumContribution::get(&CurrencyId::KSM);
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            minimum * 3
+        ));
+        assert_noop!(
+            Proposals::withdraw_contribution(
+                RuntimeOrigin::signed(*BOB),
+                Some(round_key),
+                project_key,
+                minimum * 2 + 1
+            ),
+            Error::<Test>::ContributionBelowMinimum
+        );
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            minimum * 2
+        ));
+        assert_eq!(
+            Proposals::project_contributions(project_key, *BOB)
+                .unwrap()
+                .value,
+            minimum
+        );
+        // Withdrawing everything is always allowed.
+        assert_ok!(Proposals::withdraw_contribution(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            minimum
+        ));
+        assert!(Proposals::project_contributions(project_key, *BOB).is_none());
+    })
+}
+
+#[test]
+fn sibling_accounts_contribute_through_xcm() {
+    build_test_externality().execute_with(|| {
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_noop!(
+            Proposals::contribute_via_xcm(
+                RuntimeOrigin::root(),
+                Some(round_key),
+                project_key,
+                300_000
+            ),
+            DispatchError::BadOrigin
+        );
+        // The mock treats BOB's signed origin as BOB's account on a sibling parachain.
+        assert_ok!(Proposals::contribute_via_xcm(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            300_000
+        ));
+        assert_eq!(
+            Proposals::project_contributions(project_key, *BOB)
+                .expect("contribution should be made from the derived account")
+                .value,
+            300_000
+        );
+        assert_eq!(
+            Proposals::remote_contributors(*BOB),
+            Some(MockXcmOrigin::<Test>::location_of(&BOB))
+        );
+    })
+}
+
+#[test]
+fn refunds_to_remote_contributors_are_sent_back_to_their_chain() {
+    build_test_externality().execute_with(|| {
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let charlie_initial = Tokens::free_balance(CurrencyId::Native, &CHARLIE);
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute_via_xcm(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            300_000
+        ));
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(*CHARLIE),
+            Some(round_key),
+            project_key,
+            200_000
+        ));
+
+        assert_ok!(Proposals::cancel_project(
+            RuntimeOrigin::signed(*ALICE),
+            project_key
+        ));
+
+        // The mock refund handler withdraws what it sends to another chain.
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_initial - 300_000
+        );
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &CHARLIE),
+            charlie_initial
+        );
+    })
+}
+
+#[test]
+fn remote_contributors_withdraw_and_claim_refunds_through_xcm() {
+    build_test_externality().execute_with(|| {
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        let project_key = create_project_key();
+        let round_key = schedule_matching_round(vec![project_key]);
+        assert_ok!(Proposals::contribute_via_xcm(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            300_000
+        ));
+        assert_noop!(
+            Proposals::withdraw_contribution_via_xcm(
+                RuntimeOrigin::root(),
+                Some(round_key),
+                project_key,
+                100_000
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Proposals::withdraw_contribution_via_xcm(
+            RuntimeOrigin::signed(*BOB),
+            Some(round_key),
+            project_key,
+            100_000
+        ));
+        assert_eq!(
+            Proposals::project_contributions(project_key, *BOB)
+                .unwrap()
+                .value,
+            200_000
+        );
+
+        // The project fails to raise its required funds.
+        run_to_block(System::block_number() + 11);
+        assert!(Projects::<Test>::get(project_key).unwrap().cancelled);
+        assert_noop!(
+            Proposals::claim_refund_via_xcm(RuntimeOrigin::root(), project_key),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Proposals::claim_refund_via_xcm(
+            RuntimeOrigin::signed(*BOB),
+            project_key
+        ));
+
+        // The mock refund handler withdraws what it sends to another chain.
+        assert!(Proposals::project_contributions(project_key, *BOB).is_none());
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_initial - 300_000
+        );
+    })
+}
+
+#[test]
+fn unsent_remote_refunds_can_be_sent_again() {
+    build_test_externality().execute_with(|| {
+        let bob_initial = Tokens::free_balance(CurrencyId::Native, &BOB);
+        RemoteContributors::<Test>::insert(*BOB, MockXcmOrigin::<Test>::location_of(&BOB));
+        UnsentRemoteRefunds::<Test>::insert(*BOB, CurrencyId::Native, 100_000);
+        assert_noop!(
+            Proposals::retry_remote_refund(
+                RuntimeOrigin::signed(*CHARLIE),
+                *CHARLIE,
+                CurrencyId::Native
+            ),
+            Error::<Test>::NothingToRefund
+        );
+
+        assert_ok!(Proposals::retry_remote_refund(
+            RuntimeOrigin::signed(*CHARLIE),
+            *BOB,
+            CurrencyId::Native
+        ));
+        assert_eq!(
+            Tokens::free_balance(CurrencyId::Native, &BOB),
+            bob_initial - 100_000
+        );
+        assert_eq!(
+            Proposals::unsent_remote_refunds(*BOB, CurrencyId::Native),
+            0
+        );
+        assert_eq!(
+            System::events().pop().expect("expected an event").event,
+            mock::RuntimeEvent::from(proposals::Event::RemoteRefundSent(*BOB, 100_000))
+        );
+    })
+}
+
+//common helper methods
+pub fn create_project() -> DispatchResultWithPostInfo {
+    Proposals::create_project(
+        RuntimeOrigin::signed(*ALICE),
+        gen_hash(1),
+        bounded_vec![ProposedMilestone {
+            percentage_to_unlock: 100,
+            ..Default::default()
+        }],
+        //funds required
+        1_000_000u64,
+        CurrencyId::Native,
+        None,
+    )
+}
+
+pub fn create_project_multiple_milestones(
+    proposed_milestones: Vec<ProposedMilestone<BlockNumber>>,
+) -> DispatchResultWithPostInfo {
+    Proposals::create_project(
+        RuntimeOrigin::signed(*ALICE),
+        gen_hash(1),
+        proposed_milestones
+            .try_into()
+            .expect("proposed milestones are too long"),
+        //funds required
+        1_000_000u64,
+        CurrencyId::Native,
+        None,
+    )
+}
+
+/// Create a project funded by BOB and CHARLIE, end the contribution round and approve it.
+/// The contribution round uses round key 1.
+fn create_funded_and_approved_project(
+    bob_contribution: u64,
+    charlie_contribution: u64,
+) -> ProjectKey {
+    let project_key = ProjectCount::<Test>::get();
+    assert_ok!(create_project());
+    assert_ok!(Proposals::schedule_round(
+        RuntimeOrigin::root(),
+        System::block_number(),
+        System::block_number() + 1,
+        bounded_vec![project_key],
+        RoundType::ContributionRound,
+    ));
+    assert_ok!(Proposals::contribute(
+        RuntimeOrigin::signed(*BOB),
+        None,
+        project_key,
+        bob_contribution
+    ));
+    assert_ok!(Proposals::contribute(
+        RuntimeOrigin::signed(*CHARLIE),
+        None,
+        project_key,
+        charlie_contribution
+    ));
+    run_to_block(System::block_number() + 2);
+    assert_ok!(Proposals::approve(
+        RuntimeOrigin::root(),
+        None,
+        project_key,
+        None
+    ));
+    project_key
+}
+
+/// Create a KSM project, which requires an initiator bond, funded by BOB and CHARLIE and approved.
+/// ALICE, BOB and CHARLIE are each given 10_000_000 KSM.
+fn create_bonded_project(bob_contribution: u64, charlie_contribution: u64) -> ProjectKey {
+    for account in [*ALICE, *BOB, *CHARLIE] {
+        let _ = Tokens::deposit(CurrencyId::KSM, &account, 10_000_000u64);
+    }
+    let project_key = ProjectCount::<Test>::get();
+    assert_ok!(Proposals::create_project(
+        RuntimeOrigin::signed(*ALICE),
+        gen_hash(1),
+        bounded_vec![ProposedMilestone {
+            percentage_to_unlock: 100,
+            ..Default::default()
+        }],
+        1_000_000u64,
+        CurrencyId::KSM,
+        None
+    ));
+    assert_ok!(Proposals::schedule_round(
+        RuntimeOrigin::root(),
+        System::block_number(),
+        System::block_number() + 1,
+        bounded_vec![project_key],
+        RoundType::ContributionRound,
+    ));
+    for (contributor, contribution) in [(*BOB, bob_contribution), (*CHARLIE, charlie_contribution)]
+    {
+        assert_ok!(Proposals::contribute(
+            RuntimeOrigin::signed(contributor),
+            None,
+            project_key,
+            contribution
+        ));
+    }
+    run_to_block(System::block_number() + 2);
+    assert_ok!(Proposals::approve(
+        RuntimeOrigin::root(),
+        None,
+        project_key,
+        None
+    ));
+    project_key
+}
+
+/// Create a project funded by BOB and CHARLIE below its required funds and end the contribution round.
+fn create_underfunded_project(bob_contribution: u64, charlie_contribution: u64) -> ProjectKey {
+    let project_key = ProjectCount::<Test>::get();
+    assert_ok!(create_project());
+    let end = System::block_number() + 1;
+    assert_ok!(Proposals::schedule_round(
+        RuntimeOrigin::root(),
+        System::block_number(),
+        end,
+        bounded_vec![project_key],
+        RoundType::ContributionRound,
+    ));
+    assert_ok!(Proposals::contribute(
+        RuntimeOrigin::signed(*BOB),
+        None,
+        project_key,
+        bob_contribution
+    ));
+    assert_ok!(Proposals::contribute(
+        RuntimeOrigin::signed(*CHARLIE),
+        None,
+        project_key,
+        charlie_contribution
+    ));
+    run_to_block(end);
+    project_key
+}
+
+fn create_project_key() -> ProjectKey {
+    let project_key = ProjectCount::<Test>::get();
+    assert_ok!(create_project());
+    project_key
+}
+
+/// Schedule a contribution round which starts now and ends in 10 blocks.
+fn schedule_matching_round(project_keys: Vec<ProjectKey>) -> RoundKey {
+    assert_ok!(Proposals::schedule_round(
+        RuntimeOrigin::root(),
+        System::block_number(),
+        System::block_number() + 10,
+        project_keys.try_into().expect("too many project keys"),
+        RoundType::ContributionRound,
+    ));
+    RoundCount::<Test>::get()
+}
+
+/// Create a project whose only milestone has been rejected by BOB's vote.
+fn create_rejected_milestone() -> ProjectKey {
+    let project_key = create_funded_and_approved_project(600_000, 400_000);
+    let end = System::block_number() + <Test as Config>::MilestoneVotingWindow::get();
+    assert_ok!(Proposals::submit_milestone(
+        RuntimeOrigin::signed(*ALICE),
+        project_key,
+        0,
+        gen_hash(1)
+    ));
+    run_to_block(System::block_number() + 1);
+    assert_ok!(Proposals::vote_on_milestone(
+        RuntimeOrigin::signed(*BOB),
+        project_key,
+        0,
+        None,
+        false
+    ));
+    assert_ok!(Proposals::vote_on_milestone(
+        RuntimeOrigin::signed(*CHARLIE),
+        project_key,
+        0,
+        None,
+        true
+    ));
+    run_to_block(end);
+    project_key
+}
+
+/// Create a project whose only milestone BOB and CHARLIE have voted to approve.
+fn create_approved_milestone(bob_contribution: u64, charlie_contribution: u64) -> ProjectKey {
+    let project_key = create_funded_and_approved_project(bob_contribution, charlie_contribution);
+    assert_ok!(Proposals::submit_milestone(
+        RuntimeOrigin::signed(*ALICE),
+        project_key,
+        0,
+        gen_hash(1)
+    ));
+    run_to_block(System::block_number() + 1);
+    for contributor in [*BOB, *CHARLIE] {
+        assert_ok!(Proposals::vote_on_milestone(
+            RuntimeOrigin::signed(contributor),
+            project_key,
+            0,
+            None,
+            true
+        ));
+    }
+    assert!(Projects::<Test>::get(project_key).unwrap().milestones[&0].is_approved);
+    project_key
+}
+
+/// Create a project which streams its payouts over the given period, fund it with BOB
+/// and approve its only milestone, returning the block the milestone was approved on.
+fn create_vesting_project(vesting_period: u64) -> (ProjectKey, u64) {
+    let project_key = create_project_key();
+    assert_ok!(Proposals::set_payout_vesting(
+        RuntimeOrigin::signed(*ALICE),
+        project_key,
+        Some(vesting_period)
+    ));
+    assert_ok!(Proposals::schedule_round(
+        RuntimeOrigin::root(),
+        System::block_number(),
+        System::block_number() + 1,
+        bounded_vec![project_key],
+        RoundType::ContributionRound,
+    ));
+    assert_ok!(Proposals::contribute(
+        RuntimeOrigin::signed(*BOB),
+        None,
+        project_key,
+        1_000_000u64
+    ));
+    run_to_block(System::block_number() + 2);
+    assert_ok!(Proposals::approve(
+        RuntimeOrigin::root(),
+        None,
+        project_key,
+        None
+    ));
+    assert_ok!(Proposals::submit_milestone(
+        RuntimeOrigin::signed(*ALICE),
+        project_key,
+        0,
+        gen_hash(1)
+    ));
+    run_to_block(System::block_number() + 1);
+    assert_ok!(Proposals::vote_on_milestone(
+        RuntimeOrigin::signed(*BOB),
+        project_key,
+        0,
+        None,
+        true
+    ));
+    assert_eq!(Proposals::withdrawable_funds(project_key), 0u64);
+    (project_key, System::block_number())
+}
+
+fn create_funded_account(seed: u8) -> AccountId {
+    let account = sr25519::Public::from_raw([seed; 32]);
+    let _ = Tokens::deposit(CurrencyId::Native, &account, 10_000_000u64);
+    account
+}
+
+/// Add n funded accounts, which are neither initiators nor contributors, to the juror set.
+/// Run to the block the arbitration panel of an escalated dispute is drawn on.
+fn draw_dispute_panel(project_key: ProjectKey, milestone_key: MilestoneKey) -> Dispute<Test> {
+    run_to_block(
+        Proposals::disputes(project_key, milestone_key)
+            .unwrap()
+            .deadline,
+    );
+    Proposals::disputes(project_key, milestone_key).unwrap()
+}
+
+fn create_jurors(n: u8) -> Vec<AccountId> {
+    (0..n)
+        .map(|i| {
+            let juror = create_funded_account(i);
+            assert_ok!(Proposals::add_juror(RuntimeOrigin::root(), juror));
+            juror
+        })
+        .collect()
+}
+
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        Proposals::on_initialize(System::block_number());
+        //Bad case scenario is that we have little space. all tests must still pass.
+        if n % 2 == 0 {
+            Proposals::on_idle(System::block_number(), Weight::MAX / 90);
+        } else {
+            Proposals::on_idle(System::block_number(), Weight::MAX / 2);
+        }
+    }
+}
+
+fn _run_to_block_with_no_idle_space(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        Proposals::on_initialize(System::block_number());
+        Proposals::on_idle(System::block_number(), Weight::zero());
+    }
+}
+
+fn gen_hash(seed: u8) -> AgreementHash {
+    H256::from([seed; 32])
+}
+
+fn deduct_imbue_fee(fund: u64) -> u64 {
+    fund.saturating_sub(calc_imbue_fee(fund))
+}
+
+fn calc_imbue_fee(fund: u64) -> u64 {
+    fund.saturating_mul(<Test as Config>::ImbueFee::get() as u64) / 100
+}
+
+/// Register the first identity registrar, if needed, and give its judgement on an identity.
+fn judge_identity(registrar: AccountId, target: AccountId) {
+    if pallet_identity::Pallet::<Test>::registrars().is_empty() {
+        assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), registrar));
+    }
+    let info = pallet_identity::Pallet::<Test>::identity(target)
+        .expect("identity should be set")
+        .info;
+    assert_ok!(Identity::provide_judgement(
+        RuntimeOrigin::signed(registrar),
+        0,
+        target,
+        Judgement::Reasonable,
+        BlakeTwo256::hash_of(&info)
+    ));
+}
+
+/// Set an identity with a display name and, optionally, an email.
+fn set_identity(who: AccountId, with_email: bool) {
+    let _ =
+        <Balances as frame_support::traits::Currency<AccountId>>::make_free_balance_be(&who, 1_000);
+    let email = if with_email {
+        Data::Raw(b"user@imbue.network".to_vec().try_into().unwrap())
+    } else {
+        Data::None
+    };
+    assert_ok!(Identity::set_identity(
+        RuntimeOrigin::signed(who),
+        Box::new(IdentityInfo {
+            additional: Default::default(),
+            display: Data::Raw(b"user".to_vec().try_into().unwrap()),
+            legal: Data::None,
+            web: Data::None,
+            riot: Data::None,
+            email,
+            pgp_fingerprint: None,
+            image: Data::None,
+            twitter: Data::None,
+        })
+    ));
 }
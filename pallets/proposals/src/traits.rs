@@ -1,20 +1,22 @@
 use crate::{AccountIdOf, BalanceOf, TimestampOf};
 use crate::{
-    Contribution, Event, Milestone, MilestoneKey, Project, ProjectCount, Projects,
-    ProposedMilestone,
+    BoundedMilestones, Contribution, Event, Milestone, Project, ProjectContributions, ProjectCount,
+    ProjectKey, Projects, ProposedMilestone, MAX_PERCENTAGE,
 };
+use codec::{Decode, Encode};
 use common_types::{CurrencyId, FundingType, TreasuryOrigin, TreasuryOriginConverter};
 use frame_support::{
-    dispatch::EncodeLike, inherent::Vec, pallet_prelude::DispatchError, sp_runtime::Saturating,
-    transactional, PalletId,
+    inherent::Vec, pallet_prelude::DispatchError, sp_runtime::Saturating,
+    storage::bounded_btree_map::BoundedBTreeMap, traits::{EnsureOrigin, Randomness}, transactional,
+    PalletId,
 };
 use orml_traits::{MultiCurrency, MultiReservableCurrency, XcmTransfer};
 use orml_xtokens::Error;
 
 use sp_core::{H256, Get};
-use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::traits::{AccountIdConversion, Convert, Hash, IntegerSquareRoot};
 use sp_std::collections::btree_map::BTreeMap;
-use xcm::latest::{MultiLocation, WeightLimit};
+use xcm::latest::{Junction, Junctions, MultiLocation, WeightLimit};
 
 pub trait IntoProposal<AccountId, Balance, BlockNumber, TimeStamp> {
     /// Convert a set of milestones into a project.
@@ -26,7 +28,7 @@ pub trait IntoProposal<AccountId, Balance, BlockNumber, TimeStamp> {
         contributions: BTreeMap<AccountId, Contribution<Balance, TimeStamp>>,
         brief_hash: H256,
         benificiary: AccountId,
-        milestones: Vec<ProposedMilestone>,
+        milestones: Vec<ProposedMilestone<BlockNumber>>,
         funding_type: FundingType,
     ) -> Result<(), DispatchError>;
 }
@@ -43,26 +45,73 @@ pub trait RefundHandler<AccountId, Balance, CurrencyId> {
     ) -> Result<(), DispatchError>;
     fn get_treasury_account_id(treasury_origin: TreasuryOrigin)
         -> Result<AccountId, DispatchError>;
+    /// Send a refund paid to the local account of a remote contributor back to their account
+    /// on the chain they contributed from.
+    fn send_refund_to_location(
+        from: AccountId,
+        amount: Balance,
+        currency: CurrencyId,
+        location: MultiLocation,
+    ) -> Result<(), DispatchError>;
 }
 
+pub trait ContributionReceipts<AccountId, Balance, BlockNumber> {
+    /// Mint a non-transferable receipt for a contributor to a project,
+    /// or update the receipt they already hold with their new total contribution.
+    fn issue_receipt(
+        project_key: ProjectKey,
+        contributor: &AccountId,
+        amount: Balance,
+        currency_id: CurrencyId,
+        at: BlockNumber,
+    ) -> Result<(), DispatchError>;
+    /// Burn the receipt of a contributor once their contribution has been refunded.
+    /// Receipts are kept once a project completes as a record of its backers.
+    fn burn_receipt(project_key: ProjectKey, contributor: &AccountId) -> Result<(), DispatchError>;
+}
+
+impl<AccountId, Balance, BlockNumber> ContributionReceipts<AccountId, Balance, BlockNumber> for () {
+    fn issue_receipt(
+        _project_key: ProjectKey,
+        _contributor: &AccountId,
+        _amount: Balance,
+        _currency_id: CurrencyId,
+        _at: BlockNumber,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+    fn burn_receipt(_project_key: ProjectKey, _contributor: &AccountId) -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
 
 // Some implementations used in Imbue of the traits above.
+
+/// Verifies contributors' proofs against the merkle root of a project's whitelist.
+pub struct WhitelistVerifier;
+
+impl proofs::Hasher for WhitelistVerifier {
+    type Hash = H256;
+
+    fn hash(data: &[u8]) -> H256 {
+        sp_io::hashing::blake2_256(data).into()
+    }
+}
+
+impl proofs::Verifier for WhitelistVerifier {
+    fn hash_of(a: H256, b: H256) -> H256 {
+        proofs::hashing::sort_hash_of::<Self>(a, b)
+    }
+
+    fn initial_matches(&self, doc_root: H256) -> Option<Vec<H256>> {
+        Some(sp_std::vec![doc_root])
+    }
+}
 type BlockNumberFor<T> = <T as frame_system::Config>::BlockNumber;
 type ContributionsFor<T> = BTreeMap<AccountIdOf<T>, Contribution<BalanceOf<T>, TimestampOf<T>>>;
 
 impl<T: crate::Config> IntoProposal<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>, TimestampOf<T>>
     for crate::Pallet<T>
-where
-    Project<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>, TimestampOf<T>>: EncodeLike<
-        Project<
-            <T as frame_system::Config>::AccountId,
-            <<T as crate::Config>::MultiCurrency as MultiCurrency<
-                <T as frame_system::Config>::AccountId,
-            >>::Balance,
-            <T as frame_system::Config>::BlockNumber,
-            <T as pallet_timestamp::Config>::Moment,
-        >,
-    >,
 {
     /// The caller is used to take the storage deposit from.
     /// With briefs and grants the caller is the beneficiary, so the fee will come from them.
@@ -71,15 +120,30 @@ where
         contributions: ContributionsFor<T>,
         brief_hash: H256,
         benificiary: AccountIdOf<T>,
-        proposed_milestones: Vec<ProposedMilestone>,
+        proposed_milestones: Vec<ProposedMilestone<BlockNumberFor<T>>>,
         funding_type: FundingType,
     ) -> Result<(), DispatchError> {
+        if funding_type == FundingType::Brief {
+            if let Some(policy) = crate::Pallet::<T>::default_identity_policy() {
+                if policy.brief_applicants {
+                    crate::Pallet::<T>::ensure_identity_meets(&benificiary, &policy)?;
+                }
+            }
+        }
+
+        let contributor_count = contributions.len() as u32;
+        frame_support::ensure!(
+            contributor_count <= T::MaximumContributorsPerProject::get(),
+            crate::Error::<T>::TooManyContributors
+        );
+
         let project_key = crate::ProjectCount::<T>::get().saturating_add(1);
         crate::ProjectCount::<T>::put(project_key);
 
         <<T as crate::Config>::MultiCurrency as MultiReservableCurrency<
                         AccountIdOf<T>,
                     >>::reserve(currency_id, &benificiary, T::ProjectStorageDeposit::get())?;
+        crate::Pallet::<T>::reserve_initiator_bond(project_key, &benificiary, currency_id)?;
 
         let sum_of_contributions = contributions
             .values()
@@ -106,34 +170,49 @@ where
         }
 
         let mut milestone_key: u32 = 0;
-        let mut milestones: BTreeMap<MilestoneKey, Milestone> = BTreeMap::new();
+        let mut milestones: BoundedMilestones<T> = BoundedBTreeMap::new();
         for milestone in proposed_milestones {
             let milestone = Milestone {
                 project_key,
                 milestone_key,
                 percentage_to_unlock: milestone.percentage_to_unlock,
                 is_approved: false,
+                title_hash: milestone.title_hash,
+                content_hash: milestone.content_hash,
+                due_block: milestone.due_block,
             };
-            milestones.insert(milestone_key, milestone);
+            milestones
+                .try_insert(milestone_key, milestone)
+                .map_err(|_| crate::Error::<T>::ParamLimitExceed)?;
             milestone_key = milestone_key.saturating_add(1);
         }
 
-        let project: Project<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>, TimestampOf<T>> =
-            Project {
-                milestones,
-                contributions,
-                currency_id,
-                required_funds: sum_of_contributions,
-                withdrawn_funds: 0u32.into(),
-                raised_funds: sum_of_contributions,
-                initiator: benificiary.clone(),
-                created_on: frame_system::Pallet::<T>::block_number(),
-                approved_for_funding: true,
-                funding_threshold_met: true,
-                cancelled: false,
-                agreement_hash: brief_hash,
-                funding_type,
-            };
+        crate::ProjectContributorCount::<T>::insert(project_key, contributor_count);
+
+        let now = frame_system::Pallet::<T>::block_number();
+        for (acc, cont) in contributions {
+            T::ContributionReceipts::issue_receipt(project_key, &acc, cont.value, currency_id, now)?;
+            crate::ContributionRootSums::<T>::mutate(project_key, |sum| {
+                *sum = sum.saturating_add(cont.value.integer_sqrt())
+            });
+            ProjectContributions::<T>::insert(project_key, acc, cont);
+        }
+
+        let project: Project<T> = Project {
+            milestones,
+            currency_id,
+            required_funds: sum_of_contributions,
+            funding_threshold: MAX_PERCENTAGE,
+            withdrawn_funds: 0u32.into(),
+            raised_funds: sum_of_contributions,
+            initiator: benificiary.clone(),
+            created_on: now,
+            approved_for_funding: true,
+            funding_threshold_met: true,
+            cancelled: false,
+            agreement_hash: brief_hash,
+            funding_type,
+        };
 
         Projects::<T>::insert(project_key, project);
         let project_account = Self::project_account_id(project_key);
@@ -173,6 +252,144 @@ impl<T: crate::Config> RefundHandler<AccountIdOf<T>, BalanceOf<T>, CurrencyId>
     ) -> Result<AccountIdOf<T>, DispatchError> {
         Ok(PalletId(*b"py/trsry").into_account_truncating())
     }
+    /// Withdraws the refund as the reserve transfer would, so tests can see it has left the chain.
+    fn send_refund_to_location(
+        from: AccountIdOf<T>,
+        amount: BalanceOf<T>,
+        currency: CurrencyId,
+        _location: MultiLocation,
+    ) -> Result<(), DispatchError> {
+        <T as crate::Config>::MultiCurrency::withdraw(currency, &from, amount)
+    }
+}
+
+/// The parachain remote contributors are on in tests.
+#[cfg(feature = "std")]
+pub const MOCK_SIBLING_PARA_ID: u32 = 2000;
+
+/// Treats signed origins as if they had been dispatched by XCM `Transact` from an account
+/// of the same id on a sibling parachain.
+#[cfg(feature = "std")]
+pub struct MockXcmOrigin<T> {
+    phantom_t: sp_std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Config> MockXcmOrigin<T> {
+    /// The location of an account on the mock sibling parachain.
+    pub fn location_of(who: &AccountIdOf<T>) -> MultiLocation {
+        let id: [u8; 32] = Decode::decode(&mut &who.encode()[..]).unwrap_or_default();
+        MultiLocation::new(
+            1,
+            Junctions::X2(
+                Junction::Parachain(MOCK_SIBLING_PARA_ID),
+                Junction::AccountId32 { network: None, id },
+            ),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Config> EnsureOrigin<T::RuntimeOrigin> for MockXcmOrigin<T> {
+    type Success = MultiLocation;
+
+    fn try_origin(o: T::RuntimeOrigin) -> Result<MultiLocation, T::RuntimeOrigin> {
+        let who = frame_system::ensure_signed(o.clone()).map_err(|_| o)?;
+        Ok(Self::location_of(&who))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+        let who = AccountIdOf::<T>::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
+            .map_err(|_| ())?;
+        Ok(frame_system::RawOrigin::Signed(who).into())
+    }
+}
+
+/// Maps accounts on the mock sibling parachain back to the local account of the same id.
+#[cfg(feature = "std")]
+pub struct MockLocationToAccountId<T> {
+    phantom_t: sp_std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Config> Convert<MultiLocation, Option<AccountIdOf<T>>>
+    for MockLocationToAccountId<T>
+{
+    fn convert(location: MultiLocation) -> Option<AccountIdOf<T>> {
+        match location {
+            MultiLocation {
+                parents: 1,
+                interior:
+                    Junctions::X2(
+                        Junction::Parachain(MOCK_SIBLING_PARA_ID),
+                        Junction::AccountId32 { id, .. },
+                    ),
+            } => Decode::decode(&mut &id[..]).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Derives randomness from the subject and the current block number so tests are deterministic.
+#[cfg(feature = "std")]
+pub struct MockRandomness<T> {
+    phantom_t: sp_std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Config> Randomness<T::Hash, T::BlockNumber> for MockRandomness<T> {
+    fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+        let now = frame_system::Pallet::<T>::block_number();
+        (T::Hashing::hash_of(&(subject, now)), now)
+    }
+}
+
+/// Keeps receipts in unhashed storage so tests can inspect them without an nft pallet.
+#[cfg(feature = "std")]
+pub struct MockContributionReceipts<T> {
+    phantom_t: sp_std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Config> MockContributionReceipts<T> {
+    /// The amount, currency and block recorded on a contributor's receipt.
+    pub fn receipt(
+        project_key: ProjectKey,
+        contributor: &AccountIdOf<T>,
+    ) -> Option<(BalanceOf<T>, CurrencyId, BlockNumberFor<T>)> {
+        frame_support::storage::unhashed::get(&Self::receipt_key(project_key, contributor))
+    }
+
+    fn receipt_key(project_key: ProjectKey, contributor: &AccountIdOf<T>) -> Vec<u8> {
+        (b"contribution_receipt", project_key, contributor).encode()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Config> ContributionReceipts<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>
+    for MockContributionReceipts<T>
+{
+    fn issue_receipt(
+        project_key: ProjectKey,
+        contributor: &AccountIdOf<T>,
+        amount: BalanceOf<T>,
+        currency_id: CurrencyId,
+        at: BlockNumberFor<T>,
+    ) -> Result<(), DispatchError> {
+        frame_support::storage::unhashed::put(
+            &Self::receipt_key(project_key, contributor),
+            &(amount, currency_id, at),
+        );
+        Ok(())
+    }
+    fn burn_receipt(
+        project_key: ProjectKey,
+        contributor: &AccountIdOf<T>,
+    ) -> Result<(), DispatchError> {
+        frame_support::storage::unhashed::kill(&Self::receipt_key(project_key, contributor));
+        Ok(())
+    }
 }
 
 pub struct XcmRefundHandler<T, U> {
@@ -222,4 +439,16 @@ where
             }
         }
     }
+    /// The location is the account on a sibling parachain which dispatched the contribution.
+    #[transactional]
+    fn send_refund_to_location(
+        from: T::AccountId,
+        amount: T::Balance,
+        currency: CurrencyId,
+        location: MultiLocation,
+    ) -> Result<(), DispatchError> {
+        // TODO: dest weight limit. or specify a fee.
+        let _ = U::transfer(from, currency, amount, location, WeightLimit::Unlimited)?;
+        Ok(())
+    }
 }
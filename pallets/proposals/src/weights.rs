@@ -18,17 +18,44 @@ pub trait WeightInfo {
     fn contribute() -> Weight;
     fn approve() -> Weight;
     fn submit_milestone() -> Weight;
-    fn vote_on_milestone() -> Weight;
+    fn vote_on_milestone(d: u32) -> Weight;
     fn finalise_milestone_voting() -> Weight;
     fn withdraw() -> Weight;
-    fn raise_vote_of_no_confidence() -> Weight;
-    fn vote_on_no_confidence_round() -> Weight;
+    fn raise_vote_of_no_confidence(d: u32) -> Weight;
+    fn vote_on_no_confidence_round(d: u32) -> Weight;
     fn finalise_no_confidence_round() -> Weight;
     fn set_storage_variable() -> Weight;
     fn refund() -> Weight;
     fn fund() -> Weight;
-    fn refund_item_in_queue() -> Weight;
-    fn split_off_refunds() -> Weight;
+    fn claim_refund() -> Weight;
+    fn withdraw_contribution() -> Weight;
+    fn fund_matching_pool() -> Weight;
+    fn delegate_vote(d: u32) -> Weight;
+    fn undelegate_vote() -> Weight;
+    fn set_funding_threshold() -> Weight;
+    fn set_fee_split() -> Weight;
+    fn set_fee_rate() -> Weight;
+    fn cancel_project(c: u32) -> Weight;
+    fn add_juror() -> Weight;
+    fn remove_juror() -> Weight;
+    fn raise_dispute() -> Weight;
+    fn vote_on_dispute() -> Weight;
+    fn close_dispute() -> Weight;
+    fn draw_arbitration_panel(j: u32) -> Weight;
+    fn close_contribution_round(p: u32) -> Weight;
+    fn set_payout_vesting() -> Weight;
+    fn propose_initiator_transfer() -> Weight;
+    fn vote_on_initiator_transfer() -> Weight;
+    fn accept_initiator_transfer() -> Weight;
+    fn set_project_team() -> Weight;
+    fn set_project_whitelist_root() -> Weight;
+    fn contribute_with_proof() -> Weight;
+    fn set_default_identity_policy() -> Weight;
+    fn set_project_identity_policy() -> Weight;
+    fn contribute_via_xcm() -> Weight;
+    fn claim_refund_via_xcm() -> Weight;
+    fn withdraw_contribution_via_xcm() -> Weight;
+    fn retry_remote_refund() -> Weight;
 }
 
 /// Weights for pallet_proposals using the Substrate node, recommended hardware should be used.
@@ -66,8 +93,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
     fn contribute() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(T::DbWeight::get().reads(4_u64))
-            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(T::DbWeight::get().reads(23_u64))
+            .saturating_add(T::DbWeight::get().writes(17_u64))
     }
     fn approve() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
@@ -76,13 +103,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
     fn submit_milestone() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(T::DbWeight::get().reads(4_u64))
-            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
     }
-    fn vote_on_milestone() -> Weight {
+    fn vote_on_milestone(d: u32) -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(T::DbWeight::get().reads(4_u64))
-            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_ref_time(14_000_000_u64).saturating_mul(d.into()))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(d.into())))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
     }
     fn finalise_milestone_voting() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
@@ -94,15 +124,21 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4_u64))
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
-    fn raise_vote_of_no_confidence() -> Weight {
+    fn raise_vote_of_no_confidence(d: u32) -> Weight {
         Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(Weight::from_ref_time(14_000_000_u64).saturating_mul(d.into()))
             .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(d.into())))
             .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
     }
-    fn vote_on_no_confidence_round() -> Weight {
+    fn vote_on_no_confidence_round(d: u32) -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(T::DbWeight::get().reads(4_u64))
-            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_ref_time(14_000_000_u64).saturating_mul(d.into()))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(d.into())))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
     }
     fn finalise_no_confidence_round() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
@@ -124,16 +160,155 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4_u64))
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
-    fn refund_item_in_queue() -> Weight {
+    fn claim_refund() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    fn withdraw_contribution() -> Weight {
+        Weight::from_ref_time(53_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(23_u64))
+            .saturating_add(T::DbWeight::get().writes(18_u64))
+    }
+    fn fund_matching_pool() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+    fn delegate_vote(d: u32) -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(Weight::from_ref_time(2_000_000_u64).saturating_mul(d.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(d.into())))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+    fn undelegate_vote() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    fn set_funding_threshold() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn set_fee_split() -> Weight {
+        Weight::from_ref_time(49_000_000_u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn set_fee_rate() -> Weight {
+        Weight::from_ref_time(49_000_000_u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn cancel_project(c: u32) -> Weight {
+        Weight::from_ref_time(61_000_000_u64)
+            .saturating_add(Weight::from_ref_time(24_000_000_u64).saturating_mul(c.into()))
+            .saturating_add(T::DbWeight::get().reads(13_u64))
+            .saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(c.into())))
+            .saturating_add(T::DbWeight::get().writes(14_u64))
+            .saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(c.into())))
+    }
+    fn add_juror() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    fn remove_juror() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    fn raise_dispute() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    fn vote_on_dispute() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
             .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+    fn close_dispute() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    fn draw_arbitration_panel(j: u32) -> Weight {
+        Weight::from_ref_time(42_000_000_u64)
+            .saturating_add(Weight::from_ref_time(3_000_000_u64).saturating_mul(j.into()))
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(j.into())))
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
-    fn split_off_refunds() -> Weight {
+    fn close_contribution_round(p: u32) -> Weight {
+        Weight::from_ref_time(38_000_000_u64)
+            .saturating_add(Weight::from_ref_time(31_000_000_u64).saturating_mul(p.into()))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(p.into())))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+            .saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(p.into())))
+    }
+    fn set_payout_vesting() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn propose_initiator_transfer() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    fn vote_on_initiator_transfer() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
             .saturating_add(T::DbWeight::get().reads(4_u64))
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
+    fn accept_initiator_transfer() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    fn set_project_team() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn set_project_whitelist_root() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn contribute_with_proof() -> Weight {
+        Weight::from_ref_time(57_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(24_u64))
+            .saturating_add(T::DbWeight::get().writes(17_u64))
+    }
+    fn set_default_identity_policy() -> Weight {
+        Weight::from_ref_time(20_000_000_u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn set_project_identity_policy() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    fn contribute_via_xcm() -> Weight {
+        Weight::from_ref_time(59_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(24_u64))
+            .saturating_add(T::DbWeight::get().writes(18_u64))
+    }
+    fn claim_refund_via_xcm() -> Weight {
+        Weight::from_ref_time(55_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(21_u64))
+            .saturating_add(T::DbWeight::get().writes(17_u64))
+    }
+    fn withdraw_contribution_via_xcm() -> Weight {
+        Weight::from_ref_time(57_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(24_u64))
+            .saturating_add(T::DbWeight::get().writes(19_u64))
+    }
+    fn retry_remote_refund() -> Weight {
+        Weight::from_ref_time(41_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -170,8 +345,8 @@ impl WeightInfo for () {
     }
     fn contribute() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(RocksDbWeight::get().reads(4_u64))
-            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(RocksDbWeight::get().reads(23_u64))
+            .saturating_add(RocksDbWeight::get().writes(17_u64))
     }
     fn approve() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
@@ -180,13 +355,16 @@ impl WeightInfo for () {
     }
     fn submit_milestone() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(RocksDbWeight::get().reads(4_u64))
-            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
     }
-    fn vote_on_milestone() -> Weight {
+    fn vote_on_milestone(d: u32) -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(RocksDbWeight::get().reads(4_u64))
-            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_ref_time(14_000_000_u64).saturating_mul(d.into()))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(d.into())))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
     }
     fn finalise_milestone_voting() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
@@ -198,15 +376,21 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4_u64))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
-    fn raise_vote_of_no_confidence() -> Weight {
+    fn raise_vote_of_no_confidence(d: u32) -> Weight {
         Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(Weight::from_ref_time(14_000_000_u64).saturating_mul(d.into()))
             .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(d.into())))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
     }
-    fn vote_on_no_confidence_round() -> Weight {
+    fn vote_on_no_confidence_round(d: u32) -> Weight {
         Weight::from_ref_time(49_000_000_u64)
-            .saturating_add(RocksDbWeight::get().reads(4_u64))
-            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_ref_time(14_000_000_u64).saturating_mul(d.into()))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(d.into())))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
     }
     fn finalise_no_confidence_round() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
@@ -228,14 +412,153 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(2_u64))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
-    fn split_off_refunds() -> Weight {
+    fn claim_refund() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    fn withdraw_contribution() -> Weight {
+        Weight::from_ref_time(53_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(23_u64))
+            .saturating_add(RocksDbWeight::get().writes(18_u64))
+    }
+    fn fund_matching_pool() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+    fn delegate_vote(d: u32) -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(Weight::from_ref_time(2_000_000_u64).saturating_mul(d.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(d.into())))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+    fn undelegate_vote() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    fn set_funding_threshold() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn set_fee_split() -> Weight {
+        Weight::from_ref_time(49_000_000_u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn set_fee_rate() -> Weight {
+        Weight::from_ref_time(49_000_000_u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn cancel_project(c: u32) -> Weight {
+        Weight::from_ref_time(61_000_000_u64)
+            .saturating_add(Weight::from_ref_time(24_000_000_u64).saturating_mul(c.into()))
+            .saturating_add(RocksDbWeight::get().reads(13_u64))
+            .saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(c.into())))
+            .saturating_add(RocksDbWeight::get().writes(14_u64))
+            .saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(c.into())))
+    }
+    fn add_juror() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
             .saturating_add(RocksDbWeight::get().reads(2_u64))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
-    fn refund_item_in_queue() -> Weight {
+    fn remove_juror() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    fn raise_dispute() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    fn vote_on_dispute() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+    fn close_dispute() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    fn draw_arbitration_panel(j: u32) -> Weight {
+        Weight::from_ref_time(42_000_000_u64)
+            .saturating_add(Weight::from_ref_time(3_000_000_u64).saturating_mul(j.into()))
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(j.into())))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    fn close_contribution_round(p: u32) -> Weight {
+        Weight::from_ref_time(38_000_000_u64)
+            .saturating_add(Weight::from_ref_time(31_000_000_u64).saturating_mul(p.into()))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(p.into())))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+            .saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(p.into())))
+    }
+    fn set_payout_vesting() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn propose_initiator_transfer() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    fn vote_on_initiator_transfer() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    fn accept_initiator_transfer() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    fn set_project_team() -> Weight {
         Weight::from_ref_time(49_000_000_u64)
             .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn set_project_whitelist_root() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn contribute_with_proof() -> Weight {
+        Weight::from_ref_time(57_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(24_u64))
+            .saturating_add(RocksDbWeight::get().writes(17_u64))
+    }
+    fn set_default_identity_policy() -> Weight {
+        Weight::from_ref_time(20_000_000_u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn set_project_identity_policy() -> Weight {
+        Weight::from_ref_time(49_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    fn contribute_via_xcm() -> Weight {
+        Weight::from_ref_time(59_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(24_u64))
+            .saturating_add(RocksDbWeight::get().writes(18_u64))
+    }
+    fn claim_refund_via_xcm() -> Weight {
+        Weight::from_ref_time(55_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(21_u64))
+            .saturating_add(RocksDbWeight::get().writes(17_u64))
+    }
+    fn withdraw_contribution_via_xcm() -> Weight {
+        Weight::from_ref_time(57_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(24_u64))
+            .saturating_add(RocksDbWeight::get().writes(19_u64))
+    }
+    fn retry_remote_refund() -> Weight {
+        Weight::from_ref_time(41_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
 }
@@ -0,0 +1,99 @@
+use crate::{AccountId, Balance, BlockNumber, ProposalsPalletId, Runtime, RuntimeOrigin, Uniques};
+use codec::Encode;
+use common_types::CurrencyId;
+use frame_support::{
+    traits::{tokens::nonfungibles::Inspect, Get},
+    BoundedVec,
+};
+use pallet_proposals::{traits::ContributionReceipts, ProjectKey};
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use sp_std::{convert::TryInto, vec::Vec};
+
+/// Contribution receipts held as nfts in pallet-uniques.
+/// Each project has its own collection, keyed by the project key, owned by the proposals pallet.
+/// The collection is frozen on creation so receipts cannot be transferred.
+pub struct UniquesContributionReceipts;
+
+impl UniquesContributionReceipts {
+    fn owner() -> AccountId {
+        ProposalsPalletId::get().into_account_truncating()
+    }
+
+    /// Each contributor holds at most one receipt per project, so the item is derived from their account.
+    fn item_id(contributor: &AccountId) -> u128 {
+        u128::from_le_bytes(sp_io::hashing::blake2_128(&contributor.encode()))
+    }
+
+    fn ensure_collection(project_key: ProjectKey) -> Result<(), DispatchError> {
+        if Uniques::collection_owner(&project_key).is_some() {
+            return Ok(());
+        }
+        let owner = Self::owner();
+        Uniques::do_create_collection(
+            project_key,
+            owner.clone(),
+            owner.clone(),
+            Default::default(),
+            true,
+            pallet_uniques::Event::ForceCreated {
+                collection: project_key,
+                owner: owner.clone(),
+            },
+        )?;
+        Uniques::freeze_collection(RuntimeOrigin::signed(owner), project_key)
+    }
+
+    fn set_attribute(
+        project_key: ProjectKey,
+        item: u128,
+        key: &[u8],
+        value: Vec<u8>,
+    ) -> Result<(), DispatchError> {
+        let key: BoundedVec<u8, ReceiptKeyLimit> = key
+            .to_vec()
+            .try_into()
+            .map_err(|_| DispatchError::Other("receipt attribute key too long"))?;
+        let value: BoundedVec<u8, ReceiptValueLimit> = value
+            .try_into()
+            .map_err(|_| DispatchError::Other("receipt attribute value too long"))?;
+        Uniques::set_attribute(RuntimeOrigin::root(), project_key, Some(item), key, value)
+    }
+}
+
+impl ContributionReceipts<AccountId, Balance, BlockNumber> for UniquesContributionReceipts {
+    fn issue_receipt(
+        project_key: ProjectKey,
+        contributor: &AccountId,
+        amount: Balance,
+        currency_id: CurrencyId,
+        at: BlockNumber,
+    ) -> Result<(), DispatchError> {
+        Self::ensure_collection(project_key)?;
+        let item = Self::item_id(contributor);
+        if Uniques::owner(&project_key, &item).is_none() {
+            Uniques::do_mint(project_key, item, contributor.clone(), |_| Ok(()))?;
+        }
+        Self::set_attribute(project_key, item, b"project_key", project_key.encode())?;
+        Self::set_attribute(project_key, item, b"amount", amount.encode())?;
+        Self::set_attribute(project_key, item, b"currency_id", currency_id.encode())?;
+        Self::set_attribute(project_key, item, b"block", at.encode())
+    }
+
+    fn burn_receipt(project_key: ProjectKey, contributor: &AccountId) -> Result<(), DispatchError> {
+        let item = Self::item_id(contributor);
+        if Uniques::owner(&project_key, &item).is_none() {
+            return Ok(());
+        }
+        for key in [&b"project_key"[..], b"amount", b"currency_id", b"block"] {
+            let key: BoundedVec<u8, ReceiptKeyLimit> = key
+                .to_vec()
+                .try_into()
+                .map_err(|_| DispatchError::Other("receipt attribute key too long"))?;
+            Uniques::clear_attribute(RuntimeOrigin::root(), project_key, Some(item), key)?;
+        }
+        Uniques::do_burn(project_key, item, |_, _| Ok(()))
+    }
+}
+
+type ReceiptKeyLimit = <Runtime as pallet_uniques::Config>::KeyLimit;
+type ReceiptValueLimit = <Runtime as pallet_uniques::Config>::ValueLimit;
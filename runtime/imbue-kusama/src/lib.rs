@@ -81,6 +81,7 @@ pub use common_runtime::*;
 
 mod weights;
 
+pub mod contribution_receipts;
 pub mod xcm_config;
 
 pub use crate::xcm_config::*;
@@ -766,11 +767,41 @@ parameter_types! {
     pub const NoConfidenceTimeLimit: BlockNumber = 14 * DAYS;
     pub const PercentRequiredForVoteToPass: u8 = 75;
     pub const MaximumContributorsPerProject: u32 = 5000;
-    pub const RefundsPerBlock: u8 = 20;
     pub const IsIdentityRequired: bool = false;
     pub const MilestoneVotingWindow: BlockNumber = 100800;
     pub const ImbueFee: u8 = 5;
     pub const ProjectStorageDeposit: Balance = DOLLARS * 200;
+    pub const ExpiringRoundsPerBlock: u32 = 50;
+    pub const MaxMilestoneSubmissionAttempts: u32 = 3;
+    pub CollatorPot: AccountId = PotId::get().into_account_truncating();
+    pub const DisputeBond: Balance = DOLLARS * 100;
+    pub const DisputeThreshold: u8 = 25;
+    pub const DisputeVotingPeriod: BlockNumber = 7 * DAYS;
+    pub const JurorBond: Balance = DOLLARS * 500;
+    pub const MaxJurors: u32 = 100;
+    pub const JurorsPerDispute: u32 = 5;
+    pub const MaxTeamMembers: u32 = 20;
+    pub const MaxDelegators: u32 = 100;
+}
+
+parameter_type_with_key! {
+    pub InitiatorBond: |currency_id: CurrencyId| -> Balance {
+        match currency_id {
+            CurrencyId::Native => 100 * DOLLARS,
+            CurrencyId::AUSD => 100 * DOLLARS,
+            CurrencyId::KSM => DOLLARS,
+            _ => 0,
+        }
+    };
+}
+
+parameter_type_with_key! {
+    pub MinimumContribution: |currency_id: CurrencyId| -> Balance {
+        match currency_id {
+            CurrencyId::Native => 10 * CENTS,
+            _ => CENTS,
+        }
+    };
 }
 
 impl pallet_proposals::Config for Runtime {
@@ -783,7 +814,6 @@ impl pallet_proposals::Config for Runtime {
     type NoConfidenceTimeLimit = NoConfidenceTimeLimit;
     type PercentRequiredForVoteToPass = PercentRequiredForVoteToPass;
     type MaximumContributorsPerProject = MaximumContributorsPerProject;
-    type RefundsPerBlock = RefundsPerBlock;
     // TODO: weight info.
     type WeightInfo = ();
     type IsIdentityRequired = IsIdentityRequired;
@@ -792,6 +822,55 @@ impl pallet_proposals::Config for Runtime {
     type MaxMilestonesPerProject = MaxMilestonesPerProject;
     type ProjectStorageDeposit = ProjectStorageDeposit;
     type ImbueFee = ImbueFee;
+    type ExpiringRoundsPerBlock = ExpiringRoundsPerBlock;
+    type MaxMilestoneSubmissionAttempts = MaxMilestoneSubmissionAttempts;
+    type CollatorPot = CollatorPot;
+    type DisputeBond = DisputeBond;
+    type DisputeThreshold = DisputeThreshold;
+    type DisputeVotingPeriod = DisputeVotingPeriod;
+    type JurorBond = JurorBond;
+    type MaxJurors = MaxJurors;
+    type JurorsPerDispute = JurorsPerDispute;
+    type MaxTeamMembers = MaxTeamMembers;
+    type MaxDelegators = MaxDelegators;
+    type InitiatorBond = InitiatorBond;
+    type MinimumContribution = MinimumContribution;
+    type ContributionReceipts = contribution_receipts::UniquesContributionReceipts;
+    type XcmOrigin = EnsureSiblingAccount;
+    type LocationToAccountId = LocationToLocalAccount;
+    type Randomness = RandomnessCollectiveFlip;
+}
+
+impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
+
+parameter_types! {
+    pub const UniquesStringLimit: u32 = 128;
+    pub const UniquesKeyLimit: u32 = 32;
+    pub const UniquesValueLimit: u32 = 64;
+}
+
+// Collections are only created by the proposals pallet to hold contribution receipts.
+impl pallet_uniques::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type CollectionId = u32;
+    type ItemId = u128;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type CreateOrigin = frame_support::traits::AsEnsureOriginWithArg<
+        frame_support::traits::NeverEnsureOrigin<AccountId>,
+    >;
+    type Locker = ();
+    type CollectionDeposit = ConstU128<0>;
+    type ItemDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type AttributeDepositBase = ConstU128<0>;
+    type DepositPerByte = ConstU128<0>;
+    type StringLimit = UniquesStringLimit;
+    type KeyLimit = UniquesKeyLimit;
+    type ValueLimit = UniquesValueLimit;
+    type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type Helper = ();
 }
 
 parameter_types! {
@@ -879,6 +958,8 @@ construct_runtime! {
         OrmlTokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>} = 33,
         OrmlXcm: orml_xcm::{Pallet, Call, Event<T>} = 34,
         UnknownTokens: orml_unknown_tokens::{Pallet, Storage, Event} = 35,
+        Uniques: pallet_uniques::{Pallet, Storage, Event<T>} = 36,
+        RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip::{Pallet, Storage} = 37,
 
 
         // Imbue Pallets
@@ -1068,6 +1149,48 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_proposals::runtime_api::QuadraticFundingApi<Block, Balance> for Runtime {
+        fn matching_estimate(round_key: pallet_proposals::RoundKey) -> Vec<(pallet_proposals::ProjectKey, Balance)> {
+            ImbueProposals::matching_estimate(round_key)
+        }
+    }
+
+    impl pallet_proposals::runtime_api::ProposalsApi<Block, AccountId, Balance, Moment, pallet_proposals::Project<Runtime>> for Runtime {
+        fn project(project_key: pallet_proposals::ProjectKey) -> Option<pallet_proposals::Project<Runtime>> {
+            ImbueProposals::projects(project_key)
+        }
+
+        fn withdrawable_funds(project_key: pallet_proposals::ProjectKey) -> Balance {
+            ImbueProposals::withdrawable_funds(project_key)
+        }
+
+        fn milestone_votes(project_key: pallet_proposals::ProjectKey) -> Vec<(pallet_proposals::MilestoneKey, pallet_proposals::Vote<Balance>)> {
+            ImbueProposals::project_milestone_votes(project_key)
+        }
+
+        fn active_rounds(project_key: pallet_proposals::ProjectKey) -> Vec<pallet_proposals::RoundKey> {
+            ImbueProposals::project_active_rounds(project_key)
+        }
+
+        fn contribution(project_key: pallet_proposals::ProjectKey, account_id: AccountId) -> Option<pallet_proposals::Contribution<Balance, Moment>> {
+            ImbueProposals::project_contribution(project_key, account_id)
+        }
+    }
+
+    impl pallet_briefs::runtime_api::BriefsApi<Block, AccountId, Balance, Moment, pallet_briefs::BriefData<Runtime>> for Runtime {
+        fn get_remaining_bounty(brief_id: pallet_briefs::BriefHash) -> Balance {
+            ImbueBriefs::get_remaining_bounty(brief_id)
+        }
+
+        fn get_brief(brief_id: pallet_briefs::BriefHash) -> Option<pallet_briefs::BriefData<Runtime>> {
+            ImbueBriefs::briefs(brief_id)
+        }
+
+        fn get_brief_contributions(brief_id: pallet_briefs::BriefHash) -> Vec<(AccountId, pallet_proposals::Contribution<Balance, Moment>)> {
+            ImbueBriefs::brief_contributions(brief_id).into_iter().collect()
+        }
+    }
+
 
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
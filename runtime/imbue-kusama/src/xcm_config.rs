@@ -1,6 +1,8 @@
 use crate::{AllPalletsWithSystem, Balances, ConstU32};
+use codec::Encode;
+use sp_io::hashing::blake2_256;
 use sp_runtime::traits::{Convert, Zero};
-use sp_std::{marker::PhantomData, prelude::*};
+use sp_std::{borrow::Borrow, marker::PhantomData, prelude::*};
 
 // A few exports that help ease life for downstream crates.
 pub use common_runtime::{
@@ -16,8 +18,8 @@ pub use frame_support::{
     dispatch::DispatchClass,
     ensure, parameter_types,
     traits::{
-        fungibles, Contains, Currency as PalletCurrency, EnsureOriginWithArg, EqualPrivilegeOnly,
-        Everything, Get, Imbalance, IsInVec, Nothing, OnUnbalanced, Randomness,
+        fungibles, Contains, Currency as PalletCurrency, EnsureOrigin, EnsureOriginWithArg,
+        EqualPrivilegeOnly, Everything, Get, Imbalance, IsInVec, Nothing, OnUnbalanced, Randomness,
     },
     weights::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
@@ -75,6 +77,89 @@ pub type LocationToAccountId = (
     AccountId32Aliases<RelayNetwork, AccountId>,
 );
 
+/// Converts the location assets are deposited to into the local account holding them.
+/// Accounts on sibling parachains hold their assets in a derived account. It is kept out of
+/// `LocationToAccountId` so a sibling account can never dispatch calls with a `Signed` origin.
+pub type AssetLocationToAccountId = (
+    LocationToAccountId,
+    // Accounts on sibling parachains convert to an `AccountId` derived from the parachain and account.
+    SiblingAccountId32Derived,
+);
+
+/// Converts the location of an account on a sibling parachain into a local account derived from
+/// the parachain id and the remote account, so the same key on two chains never shares funds.
+///
+/// This lets a remote account contribute to a project in one message. From its sovereign origin the
+/// sibling sends `ReserveAssetDeposited`, `BuyExecution`, `DescendOrigin(AccountId32)`, a
+/// `DepositAsset` to `(1, X2(Parachain, AccountId32))` and a `Transact` of
+/// `Proposals::contribute_via_xcm` with `OriginKind::Xcm`, the only calls it can make are the ones
+/// accepting `EnsureSiblingAccount`.
+pub struct SiblingAccountId32Derived;
+impl xcm_executor::traits::Convert<MultiLocation, AccountId> for SiblingAccountId32Derived {
+    fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+        match location.borrow() {
+            MultiLocation {
+                parents: 1,
+                interior: X2(Parachain(para_id), AccountId32 { id, .. }),
+            } => Ok((b"SiblingAccount", para_id, id)
+                .using_encoded(blake2_256)
+                .into()),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Matches the locations of accounts on sibling parachains.
+pub struct SiblingAccounts;
+impl Contains<MultiLocation> for SiblingAccounts {
+    fn contains(location: &MultiLocation) -> bool {
+        matches!(
+            location,
+            MultiLocation {
+                parents: 1,
+                interior: X2(Parachain(_), AccountId32 { .. }),
+            }
+        )
+    }
+}
+
+/// The origin of calls dispatched by `Transact` with `OriginKind::Xcm` from an account on a sibling
+/// parachain, resolving to the location of that account.
+pub struct EnsureSiblingAccount;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingAccount {
+    type Success = MultiLocation;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+        pallet_xcm::EnsureXcm::<SiblingAccounts>::try_origin(o)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(pallet_xcm::Origin::Xcm(MultiLocation::new(
+            1,
+            X2(
+                Parachain(parachains::kusama::karura::ID),
+                AccountId32 {
+                    network: None,
+                    id: [0u8; 32],
+                },
+            ),
+        ))
+        .into())
+    }
+}
+
+/// The local account of a remote location, as used when assets are deposited to it.
+pub struct LocationToLocalAccount;
+impl Convert<MultiLocation, Option<AccountId>> for LocationToLocalAccount {
+    fn convert(location: MultiLocation) -> Option<AccountId> {
+        <AssetLocationToAccountId as xcm_executor::traits::Convert<MultiLocation, AccountId>>::convert(
+            location,
+        )
+        .ok()
+    }
+}
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -153,7 +238,7 @@ pub type LocalAssetTransactor = MultiCurrencyAdapter<
     UnknownTokens,
     IsNativeConcrete<CurrencyId, CurrencyIdConvert>,
     AccountId,
-    LocationToAccountId,
+    AssetLocationToAccountId,
     CurrencyId,
     CurrencyIdConvert,
     DepositFailureHandler,